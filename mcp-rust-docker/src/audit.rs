@@ -0,0 +1,119 @@
+use chrono::prelude::*;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+
+/// Whether a security check let an operation through or blocked it. Kept
+/// separate from the human-readable `log`/`ErrorLogger` output so the
+/// audit trail stays a clean, append-only, machine-parseable stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditDecision {
+    Allow,
+    Deny,
+}
+
+/// One security-relevant event: who did what, whether it was allowed, and
+/// (for denials) which rule matched. Serialized as a single JSON object per
+/// line in `audit_file`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEvent {
+    pub timestamp: String,
+    pub actor: Option<String>,
+    pub tool: String,
+    pub decision: AuditDecision,
+    pub matched_rule: Option<String>,
+    pub target: Option<String>,
+}
+
+impl AuditEvent {
+    pub fn new(tool: impl Into<String>, decision: AuditDecision) -> Self {
+        Self {
+            timestamp: Local::now().to_rfc3339(),
+            actor: None,
+            tool: tool.into(),
+            decision,
+            matched_rule: None,
+            target: None,
+        }
+    }
+
+    pub fn with_actor(mut self, actor: impl Into<String>) -> Self {
+        self.actor = Some(actor.into());
+        self
+    }
+
+    pub fn with_matched_rule(mut self, rule: impl Into<String>) -> Self {
+        self.matched_rule = Some(rule.into());
+        self
+    }
+
+    pub fn with_target(mut self, target: impl Into<String>) -> Self {
+        self.target = Some(target.into());
+        self
+    }
+}
+
+/// Destination for audit events. A trait (rather than hardcoding the file
+/// sink) so tests or alternate deployments can swap in something else
+/// (stdout, a remote collector, ...) without touching the call sites.
+pub trait AuditSink: Send + Sync {
+    fn record(&self, event: &AuditEvent);
+}
+
+/// Appends one JSON object per line to a file, matching `audit_file`'s
+/// newline-delimited-JSON contract.
+pub struct FileAuditSink {
+    file: Mutex<std::fs::File>,
+}
+
+impl FileAuditSink {
+    pub fn open(path: &Path) -> Result<Self, String> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| format!("Failed to open audit log file {:?}: {}", path, e))?;
+
+        Ok(Self {
+            file: Mutex::new(file),
+        })
+    }
+}
+
+impl AuditSink for FileAuditSink {
+    fn record(&self, event: &AuditEvent) {
+        let Ok(json) = serde_json::to_string(event) else {
+            return;
+        };
+
+        if let Ok(mut file) = self.file.lock() {
+            let _ = writeln!(file, "{}", json);
+        }
+    }
+}
+
+static AUDIT_SINK: Lazy<Mutex<Option<Box<dyn AuditSink>>>> = Lazy::new(|| Mutex::new(None));
+
+/// Configures the process-wide audit sink. Call once at startup with
+/// `LoggingSettings::audit_file`; passing `None` (or leaving this uncalled)
+/// disables audit recording entirely.
+pub fn init_audit_sink(audit_file: Option<&Path>) -> Result<(), String> {
+    let mut sink = AUDIT_SINK.lock().unwrap();
+    *sink = match audit_file {
+        Some(path) => Some(Box::new(FileAuditSink::open(path)?) as Box<dyn AuditSink>),
+        None => None,
+    };
+    Ok(())
+}
+
+/// Records an audit event if a sink is configured; a no-op otherwise so
+/// call sites don't need to check whether auditing is enabled.
+pub fn record(event: AuditEvent) {
+    if let Some(sink) = AUDIT_SINK.lock().unwrap().as_ref() {
+        sink.record(&event);
+    }
+}
@@ -1,15 +1,24 @@
 use anyhow::Result;
 use config::{Config, File, Environment};
-use std::path::Path;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use log::{info, error};
 
 use crate::config::types::ServerConfig;
+use crate::protocol::error::McpError;
 
-pub fn load_config<P: AsRef<Path>>(path: Option<P>) -> Result<ServerConfig> {
+/// Loads configuration, optionally layering a named profile on top of the
+/// shared base section. `profile` (an explicit `--profile` argument) wins
+/// over the `DOCKER_MCP_PROFILE` env var; if neither is set, no profile
+/// layer is added and the file's `profiles` section (if any) is ignored.
+pub fn load_config<P: AsRef<Path>>(path: Option<P>, profile: Option<&str>) -> Result<ServerConfig> {
     info!("Loading configuration");
 
     let mut builder = Config::builder();
     let mut config_sources = Vec::<String>::new();
+    let mut last_user_source: Option<(String, config::FileFormat)> = None;
+    let mut file_sources: Vec<(PathBuf, config::FileFormat)> = Vec::new();
 
     // Start with embedded default settings to ensure we always have a baseline config
     builder = builder.add_source(File::from_str(
@@ -18,21 +27,34 @@ pub fn load_config<P: AsRef<Path>>(path: Option<P>) -> Result<ServerConfig> {
     ));
     config_sources.push("embedded default config".to_string());
 
-    // Try to load from default config file locations
-    let default_locations = vec![
-        "config/default.yaml",
-        "/etc/docker-mcp-server/config.yaml",
-        "./config.yaml",
-    ];
+    // If the Docker CLI has an active context (`docker context use ...`),
+    // follow it the same way `docker` itself would, so operators don't
+    // have to duplicate that switch in our own config. Sits above the
+    // embedded default but below every explicit source below it (config
+    // files, then `DOCKER_MCP_DOCKER_HOST`), so any of those can still
+    // override it.
+    if let Some(host) = docker_context_host() {
+        info!("Using Docker host {} from active Docker CLI context", host);
+        builder = builder.add_source(File::from_str(
+            &format!("docker:\n  host: {:?}\n", host),
+            config::FileFormat::Yaml,
+        ));
+        config_sources.push("Docker CLI context".to_string());
+    }
 
-    for location in default_locations {
+    // Try to load from default config file locations
+    for location in candidate_locations() {
         let path = std::path::Path::new(location);
         if path.exists() {
+            let Some(format) = file_format_for(path) else {
+                info!("Skipping config file with unrecognized extension: {}", location);
+                continue;
+            };
             info!("Found config file at: {}", location);
-            builder = builder.add_source(
-                File::from(path).required(false).format(config::FileFormat::Yaml)
-            );
+            builder = builder.add_source(File::from(path).required(false).format(format));
             config_sources.push(location.to_string());
+            last_user_source = Some((location.to_string(), format));
+            file_sources.push((path.to_path_buf(), format));
         }
     }
 
@@ -40,13 +62,19 @@ pub fn load_config<P: AsRef<Path>>(path: Option<P>) -> Result<ServerConfig> {
     if let Some(config_path) = path {
         let config_path = config_path.as_ref();
         if config_path.exists() {
-            info!("Using specified config file: {:?}", config_path);
-            builder = builder.add_source(
-                File::from(config_path)
-                    .required(true)
-                    .format(config::FileFormat::Yaml),
-            );
+            let format = file_format_for(config_path).ok_or_else(|| {
+                let err_msg = format!(
+                    "Specified config file has an unrecognized extension (expected .yaml/.yml/.toml/.json): {:?}",
+                    config_path
+                );
+                error!("{}", err_msg);
+                anyhow::anyhow!(err_msg)
+            })?;
+            info!("Using specified config file: {:?} (format: {:?})", config_path, format);
+            builder = builder.add_source(File::from(config_path).required(true).format(format));
             config_sources.push(config_path.to_string_lossy().to_string());
+            last_user_source = Some((config_path.to_string_lossy().to_string(), format));
+            file_sources.push((config_path.to_path_buf(), format));
         } else {
             let err_msg = format!("Specified config file not found: {:?}", config_path);
             error!("{}", err_msg);
@@ -54,6 +82,42 @@ pub fn load_config<P: AsRef<Path>>(path: Option<P>) -> Result<ServerConfig> {
         }
     }
 
+    // Layer a named profile (a `profiles.<name>` sub-document in one of the
+    // files above) on top of the shared base section, so operators can keep
+    // one config file per environment instead of maintaining divergent
+    // copies. An explicit `--profile` argument wins over `DOCKER_MCP_PROFILE`;
+    // requesting a profile that isn't defined in any loaded file is a hard
+    // error rather than a silent no-op.
+    let profile = profile
+        .map(|s| s.to_string())
+        .or_else(|| std::env::var("DOCKER_MCP_PROFILE").ok())
+        .filter(|s| !s.is_empty());
+
+    if let Some(profile) = &profile {
+        let mut found = false;
+        for (file_path, format) in &file_sources {
+            let profile_value = Config::builder()
+                .add_source(File::from(file_path.as_path()).format(*format))
+                .build()
+                .ok()
+                .and_then(|c| c.get::<HashMap<String, serde_json::Value>>("profiles").ok())
+                .and_then(|profiles| profiles.get(profile).cloned());
+
+            if let Some(value) = profile_value {
+                let json = serde_json::to_string(&value)?;
+                builder = builder.add_source(File::from_str(&json, config::FileFormat::Json));
+                config_sources.push(format!("profile '{}' from {:?}", profile, file_path));
+                found = true;
+            }
+        }
+
+        if !found {
+            let err_msg = format!("Config profile '{}' is not defined in any loaded config file", profile);
+            error!("{}", err_msg);
+            return Err(McpError::InvalidRequest(err_msg).into());
+        }
+    }
+
     // Add environment variables with prefix DOCKER_MCP_
     builder = builder.add_source(
         Environment::with_prefix("DOCKER_MCP")
@@ -73,7 +137,12 @@ pub fn load_config<P: AsRef<Path>>(path: Option<P>) -> Result<ServerConfig> {
                     Ok(config)
                 },
                 Err(e) => {
-                    let err_msg = format!("Failed to deserialize configuration: {}", e);
+                    let err_msg = match &last_user_source {
+                        Some((file, format)) => {
+                            format!("Failed to deserialize configuration from {} (detected format: {:?}): {}", file, format, e)
+                        }
+                        None => format!("Failed to deserialize configuration: {}", e),
+                    };
                     error!("{}", err_msg);
                     Err(anyhow::anyhow!(err_msg))
                 }
@@ -85,4 +154,139 @@ pub fn load_config<P: AsRef<Path>>(path: Option<P>) -> Result<ServerConfig> {
             Err(anyhow::anyhow!(err_msg))
         }
     }
+}
+
+/// Default config file locations searched in addition to an explicit
+/// `--config` path, in ascending priority order. Shared with
+/// [`config_file_candidates`] so `watcher::ConfigWatcher` watches exactly
+/// the files `load_config` actually reads.
+fn candidate_locations() -> Vec<&'static str> {
+    vec![
+        "config/default.yaml",
+        "config/default.toml",
+        "/etc/docker-mcp-server/config.yaml",
+        "./config.yaml",
+        "./config.toml",
+        "./config.json",
+    ]
+}
+
+/// Resolves the config file(s) `load_config` would read for `path` -
+/// every default location that currently exists, plus `path` itself if
+/// given and present - so a caller like `watcher::ConfigWatcher` can watch
+/// the same files without duplicating the search logic.
+pub fn config_file_candidates<P: AsRef<Path>>(path: Option<P>) -> Vec<PathBuf> {
+    let mut candidates: Vec<PathBuf> = candidate_locations()
+        .into_iter()
+        .map(PathBuf::from)
+        .filter(|p| p.exists())
+        .collect();
+
+    if let Some(path) = path {
+        let path = path.as_ref().to_path_buf();
+        if path.exists() {
+            candidates.push(path);
+        }
+    }
+
+    candidates
+}
+
+/// Picks the `config::FileFormat` a config file should be parsed with from
+/// its extension. `None` means the extension isn't one we recognize, which
+/// callers treat as "skip" for the default search locations but as a hard
+/// error for an explicitly-passed path.
+fn file_format_for(path: &Path) -> Option<config::FileFormat> {
+    match path.extension().and_then(|ext| ext.to_str())?.to_lowercase().as_str() {
+        "yaml" | "yml" => Some(config::FileFormat::Yaml),
+        "toml" => Some(config::FileFormat::Toml),
+        "json" => Some(config::FileFormat::Json),
+        _ => None,
+    }
+}
+
+/// Resolves `$DOCKER_CONFIG`, falling back to `$HOME/.docker` — the
+/// directory Docker CLI's own `config.json` and context metadata both live
+/// under.
+fn docker_config_dir() -> Option<PathBuf> {
+    std::env::var_os("DOCKER_CONFIG")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".docker")))
+}
+
+/// Reads `currentContext` out of `docker_dir/config.json`. `None` covers
+/// both failure to read/parse it and `currentContext` being absent, empty,
+/// or `"default"` — the default context has no metadata file of its own to
+/// resolve, so callers treat it the same as "no context override".
+fn current_context_name(docker_dir: &Path) -> Option<String> {
+    let config: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(docker_dir.join("config.json")).ok()?).ok()?;
+    let current_context = config.get("currentContext").and_then(|v| v.as_str())?;
+    (!current_context.is_empty() && current_context != "default").then(|| current_context.to_string())
+}
+
+/// Resolves `name`'s `docker` endpoint host from its metadata file under
+/// `contexts/meta/<sha256(name)>/meta.json`, the same layout `docker
+/// context inspect` reads from.
+fn context_host(docker_dir: &Path, name: &str) -> Option<String> {
+    let context_id = format!("{:x}", Sha256::digest(name.as_bytes()));
+    let meta_path = docker_dir.join("contexts").join("meta").join(context_id).join("meta.json");
+    let meta: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(meta_path).ok()?).ok()?;
+    meta.get("Endpoints")?.get("docker")?.get("Host")?.as_str().map(String::from)
+}
+
+/// Mirrors how the Docker CLI itself picks an endpoint: read
+/// `$DOCKER_CONFIG/config.json` (falling back to `$HOME/.docker/config.json`)
+/// for `currentContext`, then resolve that context's metadata to its
+/// `docker` endpoint host. Returns `None` (rather than an error) whenever
+/// any step fails or `currentContext` is absent/`"default"` — a missing or
+/// malformed Docker CLI config shouldn't be able to fail our own startup.
+fn docker_context_host() -> Option<String> {
+    let docker_dir = docker_config_dir()?;
+    let current_context = current_context_name(&docker_dir)?;
+    context_host(&docker_dir, &current_context)
+}
+
+/// Active Docker CLI context, for the `docker://context` resource and
+/// `run_diagnostic`'s output. `name` is `"default"` whenever
+/// `currentContext` is unset/absent/`"default"` itself, in which case
+/// `host` is `None` (the default context resolves to the local daemon
+/// directly rather than through a metadata file).
+pub struct DockerContextInfo {
+    pub name: String,
+    pub host: Option<String>,
+}
+
+pub fn active_docker_context() -> DockerContextInfo {
+    let docker_dir = docker_config_dir();
+    let name = docker_dir.as_deref().and_then(current_context_name).unwrap_or_else(|| "default".to_string());
+    let host = docker_dir.as_deref().and_then(|dir| context_host(dir, &name));
+    DockerContextInfo { name, host }
+}
+
+/// Names of every Docker CLI context with metadata under
+/// `~/.docker/contexts/meta/*` (or `$DOCKER_CONFIG/contexts/meta/*`), for
+/// `run_diagnostic`'s output. Always includes `"default"`, since it has no
+/// metadata file of its own to discover, even when no Docker CLI config
+/// directory exists at all.
+pub fn list_docker_contexts() -> Vec<String> {
+    let mut names = vec!["default".to_string()];
+
+    if let Some(docker_dir) = docker_config_dir() {
+        let meta_dir = docker_dir.join("contexts").join("meta");
+        if let Ok(entries) = std::fs::read_dir(&meta_dir) {
+            for entry in entries.flatten() {
+                let meta_path = entry.path().join("meta.json");
+                let Ok(content) = std::fs::read_to_string(&meta_path) else { continue };
+                let Ok(meta) = serde_json::from_str::<serde_json::Value>(&content) else { continue };
+                if let Some(name) = meta.get("Name").and_then(|v| v.as_str()) {
+                    names.push(name.to_string());
+                }
+            }
+        }
+    }
+
+    names.sort();
+    names.dedup();
+    names
 }
\ No newline at end of file
@@ -0,0 +1,4 @@
+pub mod loader;
+pub mod types;
+pub mod validate;
+pub mod watcher;
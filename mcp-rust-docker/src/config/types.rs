@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::time::Duration;
 
@@ -9,6 +9,62 @@ pub struct ServerConfig {
     pub docker: DockerSettings,
     pub security: SecuritySettings,
     pub logging: LoggingSettings,
+    #[serde(default)]
+    pub metrics: MetricsSettings,
+    /// Enables the direct OCI runtime backend (`oci/state`, `oci/create`,
+    /// `oci/start`, `oci/kill`, `oci/delete`) alongside the Docker backend,
+    /// for hosts managed by runc/crun/youki without a Docker daemon. Unset
+    /// by default; the tools return an error until this is configured.
+    #[serde(default)]
+    pub oci: Option<OciRuntimeSettings>,
+    /// Live config reload: re-reads and re-layers the config file(s) on
+    /// change and publishes the result to running components via
+    /// `config::watcher::ConfigWatcher`. Disabled by default.
+    #[serde(default)]
+    pub reload: ReloadSettings,
+}
+
+/// Configuration for the background file-watching subsystem that re-runs
+/// `load_config` whenever one of its resolved config files changes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReloadSettings {
+    /// Whether the config file watcher runs at all.
+    #[serde(default)]
+    pub enabled: bool,
+    /// How long to wait after a change before reloading, coalescing the
+    /// burst of write/rename events many editors and config-management
+    /// tools produce for a single logical save.
+    #[serde(with = "humantime_serde", default = "default_reload_debounce")]
+    pub debounce: Duration,
+}
+
+impl Default for ReloadSettings {
+    fn default() -> Self {
+        Self { enabled: false, debounce: default_reload_debounce() }
+    }
+}
+
+fn default_reload_debounce() -> Duration {
+    Duration::from_millis(200)
+}
+
+/// Configures the direct OCI runtime backend (`oci::OciRuntime`): which
+/// runtime binary to shell out to and where the OCI bundle (`config.json`
+/// + rootfs) for `oci/create` lives.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OciRuntimeSettings {
+    /// Path to the OCI runtime binary: `runc`, `crun`, `youki`, or anything
+    /// else implementing the same `state`/`create`/`start`/`kill`/`delete`
+    /// CLI surface.
+    #[serde(default = "default_oci_runtime_path")]
+    pub runtime_path: PathBuf,
+    /// Bundle directory (containing `config.json` and the rootfs) that
+    /// `oci/create` hands to the runtime.
+    pub bundle_dir: PathBuf,
+}
+
+fn default_oci_runtime_path() -> PathBuf {
+    "runc".into()
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,13 +75,45 @@ pub struct ServerSettings {
     pub transport: TransportType,
     #[serde(with = "humantime_serde", default = "default_request_timeout")]
     pub request_timeout: Duration,
+    /// How long graceful shutdown waits for in-flight requests to drain
+    /// after SIGTERM/SIGINT before giving up and shutting down anyway.
+    #[serde(with = "humantime_serde", default = "default_shutdown_grace")]
+    pub shutdown_grace: Duration,
+    /// Address the SSE, TCP, and WebSocket transports bind to (ignored for stdio)
+    #[serde(default = "default_bind_address")]
+    pub bind_address: String,
+    /// Port the SSE, TCP, and WebSocket transports listen on (ignored for stdio)
+    #[serde(default = "default_bind_port")]
+    pub bind_port: u16,
+    /// Maximum number of requests processed concurrently by a transport
+    #[serde(default = "default_max_in_flight")]
+    pub max_in_flight: usize,
+    /// TLS certificate/key, enabling HTTPS for the `http`/`sse` transport.
+    /// Ignored by stdio, TCP, and WebSocket.
+    #[serde(default)]
+    pub tls: Option<TlsSettings>,
+    /// Whether `ShutdownHandle::begin_shutdown` tears down compose projects
+    /// this server itself brought up (via `compose-up`) before resolving, so
+    /// an abruptly disconnecting MCP client doesn't leak the containers,
+    /// networks, and volumes it asked for. Off by default, since a client
+    /// that expects its containers to outlive the server process shouldn't
+    /// have them vanish out from under it.
+    #[serde(default)]
+    pub cleanup_on_exit: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum TransportType {
     Stdio,
-    Sse,
+    /// Streamable HTTP: `POST` for JSON-RPC requests, pushed back over
+    /// Server-Sent Events (see `transport::sse`). `sse` is kept as an alias
+    /// for configs written before this transport was named after its HTTP
+    /// side rather than its push side.
+    #[serde(alias = "sse")]
+    Http,
+    Tcp,
+    WebSocket,
 }
 
 impl Default for TransportType {
@@ -34,20 +122,53 @@ impl Default for TransportType {
     }
 }
 
+/// Certificate/key pair for a rustls-based TLS acceptor.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TlsSettings {
+    /// Path to a PEM-encoded certificate chain
+    pub cert_path: PathBuf,
+    /// Path to a PEM-encoded private key
+    pub key_path: PathBuf,
+}
+
 impl Default for DockerSettings {
     fn default() -> Self {
         Self {
+            backend: DockerBackendKind::default(),
             host: default_docker_host(),
             api_version: None,
             allowed_compose_projects: None,
             compose_path: default_compose_path(),
+            docker_path: default_docker_path(),
             operation_timeout: default_operation_timeout(),
             read_only: false,
             max_log_size: default_max_log_size(),
+            tls: None,
+            connections: HashMap::new(),
+            speed: default_speed(),
+            max_jobs: default_max_jobs(),
+            health_watcher: HealthWatcherSettings::default(),
+            preconditions: PreconditionSettings::default(),
         }
     }
 }
 
+/// Which `DockerClientImpl` backs `DockerBackend`. `Api` is the default and
+/// falls back to `Cli` automatically if the Engine API socket isn't
+/// reachable at startup; `Cli` is only ever explicit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DockerBackendKind {
+    Cli,
+    Api,
+}
+
+impl Default for DockerBackendKind {
+    fn default() -> Self {
+        DockerBackendKind::Api
+    }
+}
+
 impl Default for SecuritySettings {
     fn default() -> Self {
         Self {
@@ -81,6 +202,18 @@ impl Default for SecuritySettings {
                 allowed_commands: None,
                 denied_commands: std::collections::HashSet::new(),
             },
+            casbin: CasbinSettings::default(),
+        }
+    }
+}
+
+impl Default for CasbinSettings {
+    fn default() -> Self {
+        Self {
+            model_path: None,
+            policy_path: None,
+            policies: Vec::new(),
+            default_subject: default_casbin_subject(),
         }
     }
 }
@@ -100,6 +233,12 @@ impl Default for LoggingSettings {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DockerSettings {
+    /// Which backend implements Docker operations: the Engine API over a
+    /// socket/pipe (`api`, the default) or the `docker`/`docker compose`
+    /// CLI (`cli`). `api` falls back to `cli` automatically if the socket
+    /// isn't reachable at startup.
+    #[serde(default)]
+    pub backend: DockerBackendKind,
     /// Docker socket path or TCP endpoint
     #[serde(default = "default_docker_host")]
     pub host: String,
@@ -111,6 +250,11 @@ pub struct DockerSettings {
     /// Path to docker-compose binary
     #[serde(default = "default_compose_path")]
     pub compose_path: PathBuf,
+    /// Path to the `docker` CLI binary, used by the `cli` backend (or by
+    /// the `api` backend's automatic fallback) in place of the Engine API
+    /// socket.
+    #[serde(default = "default_docker_path")]
+    pub docker_path: PathBuf,
     /// Default timeout for Docker operations
     #[serde(with = "humantime_serde", default = "default_operation_timeout")]
     pub operation_timeout: Duration,
@@ -120,6 +264,174 @@ pub struct DockerSettings {
     /// Maximum log size to return in bytes
     #[serde(default = "default_max_log_size")]
     pub max_log_size: usize,
+    /// TLS client settings, for a `tcp://`/`https://` `host` (this one or
+    /// one in `connections`) speaking HTTPS with certificate auth, the way
+    /// `docker --tlsverify` does.
+    #[serde(default)]
+    pub tls: Option<DockerTlsSettings>,
+    /// Additional named Docker endpoints beyond the default connection
+    /// above (local socket plus remote hosts). `tools/call` routes to one
+    /// via a `connection` argument matching a key here; omitted (or
+    /// `"default"`), it uses the default connection built from the rest
+    /// of this struct. One server can orchestrate containers across
+    /// several hosts this way.
+    #[serde(default)]
+    pub connections: HashMap<String, DockerConnectionSettings>,
+    /// Relative scheduling weight for this (the default) endpoint, used
+    /// the same way [`DockerConnectionSettings::speed`] is for named ones:
+    /// among endpoints with a free job slot, `handle_call_tool` prefers
+    /// the one with the highest `speed`.
+    #[serde(default = "default_speed")]
+    pub speed: f64,
+    /// Maximum number of tool calls this endpoint runs concurrently before
+    /// `handle_call_tool`'s scheduler considers it fully loaded and looks
+    /// elsewhere.
+    #[serde(default = "default_max_jobs")]
+    pub max_jobs: usize,
+    /// Optional self-healing watcher that restarts containers stuck
+    /// `unhealthy` past a grace period. Disabled by default.
+    #[serde(default)]
+    pub health_watcher: HealthWatcherSettings,
+    /// Minimum daemon/API versions and locally-present images `diagnostic`
+    /// (and, if `fail_on_unmet`, startup itself) checks the live Docker
+    /// connection against. Unset by default, i.e. no preconditions enforced.
+    #[serde(default)]
+    pub preconditions: PreconditionSettings,
+}
+
+/// Declarable startup preconditions: a known-good Docker environment an
+/// operator can pin so a mismatch is caught with a clear message instead
+/// of surfacing as an obscure API error mid-operation. `config::validate`
+/// checks all of these against the live daemon; `main` additionally
+/// refuses to start over them when `fail_on_unmet` is set.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PreconditionSettings {
+    /// Minimum Docker Engine version (e.g. `"24.0.0"`), compared
+    /// component-wise rather than lexicographically.
+    #[serde(default)]
+    pub required_docker_version: Option<String>,
+    /// Minimum Docker Engine API version (e.g. `"1.43"`), compared the
+    /// same way as `required_docker_version`.
+    #[serde(default)]
+    pub required_api_version: Option<String>,
+    /// Image references (e.g. `"alpine:3.19"`) that must already be
+    /// present locally.
+    #[serde(default)]
+    pub required_images: Vec<String>,
+    /// Refuse to start the server if any precondition above is unmet,
+    /// rather than only reporting it via the `diagnostic` tool.
+    #[serde(default)]
+    pub fail_on_unmet: bool,
+}
+
+/// Configuration for the background subsystem that watches for containers
+/// marked `unhealthy` by their `HEALTHCHECK` and restarts them once they've
+/// stayed that way longer than `unhealthy_timeout`. Only containers bearing
+/// `label` are ever touched, so operators opt individual containers in
+/// rather than the watcher acting on everything unhealthy in the daemon.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthWatcherSettings {
+    /// Whether the watcher runs at all.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Label a container must carry to be eligible for auto-restart.
+    #[serde(default = "default_health_watcher_label")]
+    pub label: String,
+    /// How often to poll for unhealthy containers.
+    #[serde(with = "humantime_serde", default = "default_health_watcher_interval")]
+    pub interval: Duration,
+    /// How long a container must stay unhealthy before it's restarted.
+    #[serde(with = "humantime_serde", default = "default_health_watcher_unhealthy_timeout")]
+    pub unhealthy_timeout: Duration,
+}
+
+impl Default for HealthWatcherSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            label: default_health_watcher_label(),
+            interval: default_health_watcher_interval(),
+            unhealthy_timeout: default_health_watcher_unhealthy_timeout(),
+        }
+    }
+}
+
+fn default_health_watcher_label() -> String {
+    "auto-restart.unhealthy".to_string()
+}
+
+fn default_health_watcher_interval() -> Duration {
+    Duration::from_secs(30)
+}
+
+fn default_health_watcher_unhealthy_timeout() -> Duration {
+    Duration::from_secs(60)
+}
+
+/// CA certificate plus client certificate/key, for connecting to a TLS-
+/// secured Docker daemon over TCP.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DockerTlsSettings {
+    pub ca_path: PathBuf,
+    pub cert_path: PathBuf,
+    pub key_path: PathBuf,
+}
+
+/// One additional named Docker endpoint. Only the fields that are
+/// genuinely host-specific live here; everything else a `DockerBackend`
+/// needs (compose path, operation timeout, log size caps, ...) is shared
+/// from the default connection's `DockerSettings` via [`Self::resolve`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DockerConnectionSettings {
+    #[serde(default)]
+    pub backend: DockerBackendKind,
+    pub host: String,
+    #[serde(default)]
+    pub api_version: Option<String>,
+    #[serde(default)]
+    pub read_only: bool,
+    #[serde(default)]
+    pub tls: Option<DockerTlsSettings>,
+    /// Relative scheduling weight: among endpoints with a free job slot
+    /// (see `max_jobs`), `handle_call_tool`'s scheduler prefers whichever
+    /// has the highest `speed`, ties broken by fewest jobs currently
+    /// in flight. Has no meaning on its own — only relative to other
+    /// endpoints' `speed`.
+    #[serde(default = "default_speed")]
+    pub speed: f64,
+    /// Maximum number of tool calls this endpoint runs concurrently;
+    /// enforced with a `tokio::sync::Semaphore` the scheduler acquires a
+    /// permit from before dispatching to this endpoint. An endpoint with
+    /// no free permit is skipped in favor of one that has one, regardless
+    /// of `speed`.
+    #[serde(default = "default_max_jobs")]
+    pub max_jobs: usize,
+}
+
+impl DockerConnectionSettings {
+    /// Builds the full `DockerSettings` this connection's `DockerBackend`
+    /// is constructed from: this connection's host-specific fields layered
+    /// over everything else the default connection already has configured.
+    pub fn resolve(&self, default: &DockerSettings) -> DockerSettings {
+        DockerSettings {
+            backend: self.backend,
+            host: self.host.clone(),
+            api_version: self.api_version.clone(),
+            read_only: self.read_only,
+            tls: self.tls.clone(),
+            speed: self.speed,
+            max_jobs: self.max_jobs,
+            ..default.clone()
+        }
+    }
+}
+
+fn default_speed() -> f64 {
+    1.0
+}
+
+fn default_max_jobs() -> usize {
+    4
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -136,6 +448,32 @@ pub struct SecuritySettings {
     pub networks: NetworkSettings,
     /// Command execution restrictions
     pub commands: CommandSettings,
+    /// Casbin policy engine backing `SecurityValidator`. The legacy
+    /// allow/deny lists above are still honored — they're auto-translated
+    /// into policies at load time — but this is where finer-grained RBAC
+    /// and glob-matched rules live.
+    #[serde(default)]
+    pub casbin: CasbinSettings,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CasbinSettings {
+    /// Path to a Casbin `.conf` model file. Falls back to the server's
+    /// embedded default model (subject/object/action with RBAC and a
+    /// deny-overrides effect) when unset.
+    #[serde(default)]
+    pub model_path: Option<PathBuf>,
+    /// Path to a Casbin `.csv` policy file, loaded in addition to
+    /// `policies` and the translated legacy lists.
+    #[serde(default)]
+    pub policy_path: Option<PathBuf>,
+    /// Inline policy/grouping lines, e.g. `"p, admin, docker://image/*, read"`
+    /// or `"g, alice, admin"`.
+    #[serde(default)]
+    pub policies: Vec<String>,
+    /// Subject used for requests until per-client identity exists.
+    #[serde(default = "default_casbin_subject")]
+    pub default_subject: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -236,6 +574,29 @@ pub struct LoggingSettings {
     pub audit_file: Option<PathBuf>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricsSettings {
+    /// Whether to serve Prometheus text exposition metrics over HTTP
+    #[serde(default)]
+    pub enabled: bool,
+    /// Address the `/metrics` endpoint binds to
+    #[serde(default = "default_metrics_bind_address")]
+    pub bind_address: String,
+    /// Port the `/metrics` endpoint listens on
+    #[serde(default = "default_metrics_bind_port")]
+    pub bind_port: u16,
+}
+
+impl Default for MetricsSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bind_address: default_metrics_bind_address(),
+            bind_port: default_metrics_bind_port(),
+        }
+    }
+}
+
 // Default value functions
 fn default_true() -> bool {
     true
@@ -245,6 +606,10 @@ fn default_request_timeout() -> Duration {
     Duration::from_secs(30)
 }
 
+fn default_shutdown_grace() -> Duration {
+    Duration::from_secs(30)
+}
+
 fn default_operation_timeout() -> Duration {
     Duration::from_secs(60)
 }
@@ -261,6 +626,30 @@ fn default_compose_path() -> PathBuf {
     "docker-compose".into()
 }
 
+fn default_docker_path() -> PathBuf {
+    "docker".into()
+}
+
+fn default_bind_address() -> String {
+    "127.0.0.1".to_string()
+}
+
+fn default_bind_port() -> u16 {
+    8787
+}
+
+fn default_metrics_bind_address() -> String {
+    "127.0.0.1".to_string()
+}
+
+fn default_metrics_bind_port() -> u16 {
+    9464
+}
+
+fn default_max_in_flight() -> usize {
+    16
+}
+
 fn default_rate_limit() -> u32 {
     60
 }
@@ -291,4 +680,8 @@ fn default_log_level() -> String {
 
 fn default_log_format() -> String {
     "text".to_string()
+}
+
+fn default_casbin_subject() -> String {
+    "anonymous".to_string()
 }
\ No newline at end of file
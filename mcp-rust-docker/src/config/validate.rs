@@ -0,0 +1,246 @@
+//! Desired-state preflight for a loaded `ServerConfig`: checks the live
+//! Docker daemon and the config's own internal consistency without
+//! applying anything, the way `terraform plan`/`kubectl diff` check a
+//! live system against a declarative spec. Backs the CLI's `--test` path,
+//! which prints pass/fail per assertion and exits non-zero if any fail.
+
+use std::sync::Arc;
+
+use crate::config::types::ServerConfig;
+use crate::docker::{DockerBackend, DockerClient};
+
+/// One checked fact: what the config implies (`expected`), what's
+/// actually true (`actual`), and whether the two agree.
+#[derive(Debug, Clone)]
+pub struct Assertion {
+    pub resource: String,
+    pub expected: String,
+    pub actual: String,
+    pub in_desired_state: bool,
+}
+
+impl Assertion {
+    fn pass(resource: impl Into<String>, expected: impl Into<String>) -> Self {
+        let expected = expected.into();
+        Self { resource: resource.into(), actual: expected.clone(), expected, in_desired_state: true }
+    }
+
+    fn fail(resource: impl Into<String>, expected: impl Into<String>, actual: impl Into<String>) -> Self {
+        Self { resource: resource.into(), expected: expected.into(), actual: actual.into(), in_desired_state: false }
+    }
+}
+
+/// The full set of assertions from one `validate` run.
+#[derive(Debug, Clone, Default)]
+pub struct DesiredStateReport {
+    pub assertions: Vec<Assertion>,
+}
+
+impl DesiredStateReport {
+    pub fn all_passed(&self) -> bool {
+        self.assertions.iter().all(|a| a.in_desired_state)
+    }
+}
+
+/// Checks `config` against the live environment: that the Docker daemon
+/// is reachable, that networks and base images it references actually
+/// exist, and that its own quota/security settings are internally
+/// consistent. Never mutates anything - a failed assertion is reported,
+/// not corrected.
+pub async fn validate(config: &ServerConfig) -> DesiredStateReport {
+    let mut assertions = check_internal_consistency(config);
+
+    match DockerBackend::new(&config.docker).await {
+        Ok(docker) => {
+            assertions.push(Assertion::pass("docker.daemon", "reachable"));
+
+            let docker = Arc::new(docker);
+            assertions.extend(check_networks(&docker, config).await);
+            assertions.extend(check_images(&docker, config).await);
+            assertions.extend(check_preconditions(&docker, config).await);
+        }
+        Err(e) => {
+            assertions.push(Assertion::fail("docker.daemon", "reachable", format!("unreachable: {}", e)));
+        }
+    }
+
+    DesiredStateReport { assertions }
+}
+
+/// Consistency checks that need no Docker connection: quota sanity and
+/// allow/deny lists that can never agree with each other.
+fn check_internal_consistency(config: &ServerConfig) -> Vec<Assertion> {
+    let mut assertions = Vec::new();
+    let quotas = &config.security.quotas;
+
+    if quotas.enabled {
+        assertions.push(if quotas.max_containers > 0 {
+            Assertion::pass("security.quotas.max_containers", "> 0")
+        } else {
+            Assertion::fail("security.quotas.max_containers", "> 0", "0")
+        });
+
+        assertions.push(if quotas.max_images > 0 {
+            Assertion::pass("security.quotas.max_images", "> 0")
+        } else {
+            Assertion::fail("security.quotas.max_images", "> 0", "0")
+        });
+    }
+
+    if let Some(allowed) = &config.security.registries.allowed_registries {
+        assertions.push(disjoint_assertion(
+            "security.registries",
+            allowed,
+            &config.security.registries.denied_registries,
+        ));
+    }
+
+    if let Some(allowed) = &config.security.networks.allowed_networks {
+        assertions.push(disjoint_assertion("security.networks", allowed, &config.security.networks.denied_networks));
+    }
+
+    if let Some(allowed) = &config.security.volumes.allowed_mounts {
+        assertions.push(disjoint_assertion("security.volumes", allowed, &config.security.volumes.denied_mounts));
+    }
+
+    assertions
+}
+
+fn disjoint_assertion(
+    resource: &str,
+    allowed: &std::collections::HashSet<String>,
+    denied: &std::collections::HashSet<String>,
+) -> Assertion {
+    let overlap: Vec<&String> = allowed.intersection(denied).collect();
+    if overlap.is_empty() {
+        Assertion::pass(resource, "allowed/denied lists disjoint")
+    } else {
+        Assertion::fail(resource, "allowed/denied lists disjoint", format!("both list {:?}", overlap))
+    }
+}
+
+/// Verifies every network in `NetworkSettings::allowed_networks` actually
+/// exists on the daemon, so a typo in the allow-list fails loudly here
+/// instead of silently denying every container that asks to join it.
+async fn check_networks(docker: &Arc<DockerBackend>, config: &ServerConfig) -> Vec<Assertion> {
+    let Some(allowed) = &config.security.networks.allowed_networks else {
+        return Vec::new();
+    };
+
+    let live = match docker.list_network_names().await {
+        Ok(names) => names,
+        Err(e) => return vec![Assertion::fail("docker.networks", "listable", format!("failed to list: {}", e))],
+    };
+
+    allowed
+        .iter()
+        .map(|name| {
+            let resource = format!("network.{}", name);
+            if live.contains(name) {
+                Assertion::pass(resource, "exists")
+            } else {
+                Assertion::fail(resource, "exists", "not found")
+            }
+        })
+        .collect()
+}
+
+/// Verifies every image in `RegistrySettings::allowed_base_images` is
+/// present locally, so a reference to an image that was never pulled is
+/// caught before the first tool call that needs it.
+async fn check_images(docker: &Arc<DockerBackend>, config: &ServerConfig) -> Vec<Assertion> {
+    let Some(allowed) = &config.security.registries.allowed_base_images else {
+        return Vec::new();
+    };
+
+    let mut assertions = Vec::with_capacity(allowed.len());
+    for image in allowed {
+        let resource = format!("image.{}", image);
+        match docker.get_image_details(image).await {
+            Ok(_) => assertions.push(Assertion::pass(resource, "present locally")),
+            Err(e) => assertions.push(Assertion::fail(resource, "present locally", format!("not found: {}", e))),
+        }
+    }
+    assertions
+}
+
+/// Splits a dotted version string (`"24.0.7"`, `"1.43"`) into numeric
+/// components, discarding anything non-numeric (a `"-rc1"`-style
+/// suffix, for instance) so `version_at_least` compares the parts that
+/// actually carry ordering.
+fn version_components(version: &str) -> Vec<u64> {
+    version.split('.').filter_map(|part| part.chars().take_while(|c| c.is_ascii_digit()).collect::<String>().parse().ok()).collect()
+}
+
+/// Component-wise `actual >= required` (missing trailing components count
+/// as `0`), so `"9.0"` doesn't rank above `"10.0"` the way a plain string
+/// comparison would.
+pub fn version_at_least(actual: &str, required: &str) -> bool {
+    let actual = version_components(actual);
+    let required = version_components(required);
+
+    for i in 0..actual.len().max(required.len()) {
+        let a = actual.get(i).copied().unwrap_or(0);
+        let r = required.get(i).copied().unwrap_or(0);
+        if a != r {
+            return a > r;
+        }
+    }
+    true
+}
+
+/// Checks `config.docker.preconditions` against the live daemon: the
+/// reported `Version`/`ApiVersion` against the configured minimums, and
+/// that every `required_images` entry is present locally (the same
+/// presence check [`check_images`] runs for `allowed_base_images`). Both
+/// version checks are skipped if neither is configured, so a plain
+/// `get_docker_version` call isn't made on every `validate`/`diagnostic`
+/// run for operators who don't use this feature.
+pub async fn check_preconditions(docker: &Arc<DockerBackend>, config: &ServerConfig) -> Vec<Assertion> {
+    let preconditions = &config.docker.preconditions;
+    let mut assertions = Vec::new();
+
+    if preconditions.required_docker_version.is_some() || preconditions.required_api_version.is_some() {
+        match docker.get_docker_version().await {
+            Ok(raw) => {
+                let parsed: serde_json::Value = serde_json::from_str(&raw).unwrap_or_default();
+                let engine_version = parsed.get("Version").and_then(|v| v.as_str()).unwrap_or_default();
+                let api_version = parsed.get("ApiVersion").and_then(|v| v.as_str()).unwrap_or_default();
+
+                if let Some(required) = &preconditions.required_docker_version {
+                    assertions.push(version_assertion("precondition.docker_version", required, engine_version));
+                }
+                if let Some(required) = &preconditions.required_api_version {
+                    assertions.push(version_assertion("precondition.api_version", required, api_version));
+                }
+            }
+            Err(e) => {
+                let message = format!("failed to query daemon version: {}", e);
+                if preconditions.required_docker_version.is_some() {
+                    assertions.push(Assertion::fail("precondition.docker_version", "queryable", message.clone()));
+                }
+                if preconditions.required_api_version.is_some() {
+                    assertions.push(Assertion::fail("precondition.api_version", "queryable", message));
+                }
+            }
+        }
+    }
+
+    for image in &preconditions.required_images {
+        let resource = format!("precondition.image.{}", image);
+        match docker.get_image_details(image).await {
+            Ok(_) => assertions.push(Assertion::pass(resource, "present locally")),
+            Err(e) => assertions.push(Assertion::fail(resource, "present locally", format!("not found: {}", e))),
+        }
+    }
+
+    assertions
+}
+
+fn version_assertion(resource: &str, required: &str, actual: &str) -> Assertion {
+    if version_at_least(actual, required) {
+        Assertion::pass(resource, format!(">= {}", required))
+    } else {
+        Assertion::fail(resource, format!(">= {}", required), actual.to_string())
+    }
+}
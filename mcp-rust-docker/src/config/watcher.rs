@@ -0,0 +1,101 @@
+//! Live config reload: watches the config file(s) `loader::load_config`
+//! resolved and re-runs the full layered build (embedded defaults → file
+//! locations → explicit file → `DOCKER_MCP_*` env) whenever one of them
+//! changes, publishing the result through a `watch::Receiver` so running
+//! components can observe updates without a restart. Purely opt-in via
+//! `ReloadSettings::enabled`; a reload that fails to parse logs the error
+//! and keeps serving the last-known-good config rather than crashing.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use tokio::sync::{mpsc, watch};
+use tokio::task::AbortHandle;
+
+use super::loader;
+use super::types::ServerConfig;
+
+/// Owns the background reload task and the underlying `notify` watcher;
+/// dropping (or calling `stop`) tears both down.
+pub struct ConfigWatcher {
+    _notify_watcher: RecommendedWatcher,
+    abort: AbortHandle,
+}
+
+impl ConfigWatcher {
+    /// Watches every config file `loader::config_file_candidates` resolves
+    /// for `config_path` and spawns the reload loop, seeding the returned
+    /// receiver with `initial`. Rapid successive change events (editors
+    /// routinely emit several for one save) are coalesced by waiting for
+    /// `debounce` of quiet after the first before reloading.
+    ///
+    /// If none of the candidate files exist yet, this still starts
+    /// cleanly - it just has nothing to watch, in keeping with
+    /// `load_config` never requiring a file to be present. The watched set
+    /// is resolved once here; a candidate file created after `start` is
+    /// not watched.
+    pub fn start(
+        config_path: Option<PathBuf>,
+        profile: Option<String>,
+        initial: Arc<ServerConfig>,
+        debounce: Duration,
+    ) -> anyhow::Result<(Self, watch::Receiver<Arc<ServerConfig>>)> {
+        let (tx, rx) = watch::channel(initial);
+
+        let watched_paths = loader::config_file_candidates(config_path.as_ref());
+
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        let mut notify_watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            if let Ok(event) = res {
+                let _ = event_tx.send(event);
+            }
+        })?;
+
+        for path in &watched_paths {
+            match notify_watcher.watch(path, RecursiveMode::NonRecursive) {
+                Ok(()) => log::info!("config watcher: watching {:?} for changes", path),
+                Err(e) => log::warn!("config watcher: failed to watch {:?}: {}", path, e),
+            }
+        }
+
+        let handle = tokio::spawn(async move {
+            loop {
+                if event_rx.recv().await.is_none() {
+                    return;
+                }
+
+                // Drain further events until the stream goes quiet for
+                // `debounce`, so a burst of writes only triggers one reload.
+                loop {
+                    match tokio::time::timeout(debounce, event_rx.recv()).await {
+                        Ok(Some(_)) => continue,
+                        Ok(None) => return,
+                        Err(_elapsed) => break,
+                    }
+                }
+
+                match loader::load_config(config_path.clone(), profile.as_deref()) {
+                    Ok(new_config) => {
+                        log::info!("config watcher: reloaded configuration");
+                        let _ = tx.send(Arc::new(new_config));
+                    }
+                    Err(e) => {
+                        log::warn!(
+                            "config watcher: reload failed, keeping last-known-good config: {}",
+                            e
+                        );
+                    }
+                }
+            }
+        });
+
+        Ok((Self { _notify_watcher: notify_watcher, abort: handle.abort_handle() }, rx))
+    }
+
+    /// Stops the reload loop. Safe to call more than once.
+    pub fn stop(&self) {
+        self.abort.abort();
+    }
+}
@@ -0,0 +1,349 @@
+//! Runtime-selectable Docker backend: wraps either the CLI-based
+//! (`docker`/`docker compose`) or Engine API (bollard-over-socket)
+//! `DockerClientImpl` behind one handle, so `McpServer` doesn't need to
+//! know at compile time which one is live. Selected by
+//! `DockerSettings::backend`; `api` falls back to `cli` automatically if
+//! the daemon socket isn't reachable at startup, since the two have very
+//! different failure modes (a missing socket vs. a missing/incompatible
+//! `docker` binary) and operators shouldn't have to guess which one works
+//! on a given host.
+
+use std::collections::HashMap;
+
+use futures::stream::BoxStream;
+use serde_json::Value;
+
+use crate::config::types::{DockerBackendKind, DockerSettings};
+use crate::protocol::error::McpError;
+use crate::protocol::types::CallToolResult;
+
+use super::cli;
+use super::socket;
+use super::types::{ContainerStateSummary, ContainerStatsSample, ExecHandle, LogChunk, MountInfo, VolumeUsageSummary};
+use super::DockerClient;
+
+enum Inner {
+    Cli(cli::DockerClientImpl),
+    Api(socket::DockerClientImpl),
+}
+
+pub struct DockerBackend {
+    inner: Inner,
+    active: &'static str,
+}
+
+impl DockerBackend {
+    /// Builds the configured backend. `api` is verified reachable with a
+    /// cheap `get_docker_version` call before being committed to; if that
+    /// fails (or the socket connection itself fails) this falls back to
+    /// `cli` rather than failing startup outright.
+    pub async fn new(settings: &DockerSettings) -> Result<Self, McpError> {
+        match settings.backend {
+            DockerBackendKind::Cli => Ok(Self {
+                inner: Inner::Cli(cli::DockerClientImpl::new(settings)?),
+                active: "cli",
+            }),
+            DockerBackendKind::Api => match Self::try_api(settings).await {
+                Some(client) => Ok(Self { inner: Inner::Api(client), active: "api" }),
+                None => {
+                    log::warn!(
+                        "Docker Engine API at '{}' is unreachable; falling back to the `docker` CLI backend",
+                        settings.host
+                    );
+                    Ok(Self {
+                        inner: Inner::Cli(cli::DockerClientImpl::new(settings)?),
+                        active: "cli",
+                    })
+                }
+            },
+        }
+    }
+
+    async fn try_api(settings: &DockerSettings) -> Option<socket::DockerClientImpl> {
+        let client = socket::DockerClientImpl::new(settings).ok()?;
+        client.get_docker_version().await.ok()?;
+        Some(client)
+    }
+
+    /// Name of the backend actually in use (`"cli"` or `"api"`), surfaced
+    /// by the `diagnostic` tool so operators can see which path is live
+    /// without reading logs.
+    pub fn active_backend(&self) -> &'static str {
+        self.active
+    }
+
+    pub fn get_compose_path(&self) -> &std::path::Path {
+        match &self.inner {
+            Inner::Cli(c) => c.get_compose_path(),
+            Inner::Api(c) => c.get_compose_path(),
+        }
+    }
+
+    pub fn follow_logs(&self, container_id: &str) -> BoxStream<'_, Result<LogChunk, McpError>> {
+        match &self.inner {
+            Inner::Cli(c) => c.follow_logs(container_id),
+            Inner::Api(c) => c.follow_logs(container_id),
+        }
+    }
+
+    pub fn stream_events(
+        &self,
+        since: Option<i64>,
+        until: Option<i64>,
+        filters: HashMap<String, Vec<String>>,
+    ) -> BoxStream<'_, Result<Value, McpError>> {
+        match &self.inner {
+            Inner::Cli(c) => c.stream_events(since, until, filters),
+            Inner::Api(c) => c.stream_events(since, until, filters),
+        }
+    }
+
+    pub async fn start_exec(
+        &self,
+        container_id: &str,
+        cmd: Vec<String>,
+        working_dir: Option<String>,
+        env: Option<Vec<String>>,
+        tty: bool,
+    ) -> Result<(String, ExecHandle), McpError> {
+        match &self.inner {
+            Inner::Cli(c) => c.start_exec(container_id, cmd, working_dir, env, tty).await,
+            Inner::Api(c) => c.start_exec(container_id, cmd, working_dir, env, tty).await,
+        }
+    }
+
+    pub async fn exec_exit_code(&self, exec_id: &str) -> Result<Option<i64>, McpError> {
+        match &self.inner {
+            Inner::Cli(c) => c.exec_exit_code(exec_id).await,
+            Inner::Api(c) => c.exec_exit_code(exec_id).await,
+        }
+    }
+
+    pub async fn resize_exec(&self, exec_id: &str, rows: u16, cols: u16) -> Result<(), McpError> {
+        match &self.inner {
+            Inner::Cli(c) => c.resize_exec(exec_id, rows, cols).await,
+            Inner::Api(c) => c.resize_exec(exec_id, rows, cols).await,
+        }
+    }
+
+    pub async fn get_container_stats(&self, container_id: &str) -> Result<ContainerStatsSample, McpError> {
+        match &self.inner {
+            Inner::Cli(c) => c.get_container_stats(container_id).await,
+            Inner::Api(c) => c.get_container_stats(container_id).await,
+        }
+    }
+
+    pub async fn get_container_mounts(&self, container_id: &str) -> Result<Vec<MountInfo>, McpError> {
+        match &self.inner {
+            Inner::Cli(c) => c.get_container_mounts(container_id).await,
+            Inner::Api(c) => c.get_container_mounts(container_id).await,
+        }
+    }
+
+    pub async fn inspect_state(&self, container_id: &str) -> Result<ContainerStateSummary, McpError> {
+        match &self.inner {
+            Inner::Cli(c) => c.inspect_state(container_id).await,
+            Inner::Api(c) => c.inspect_state(container_id).await,
+        }
+    }
+
+    pub async fn owned_volumes_usage(&self) -> Result<VolumeUsageSummary, McpError> {
+        match &self.inner {
+            Inner::Cli(c) => c.owned_volumes_usage().await,
+            Inner::Api(c) => c.owned_volumes_usage().await,
+        }
+    }
+
+    /// IDs of containers carrying `label` that the daemon currently reports
+    /// as `unhealthy`, for the health watcher (`crate::health_watcher`).
+    pub async fn list_unhealthy_containers(&self, label: &str) -> Result<Vec<String>, McpError> {
+        match &self.inner {
+            Inner::Cli(c) => c.list_unhealthy_containers(label).await,
+            Inner::Api(c) => c.list_unhealthy_containers(label).await,
+        }
+    }
+
+    pub async fn restart_container(&self, container_id: &str) -> Result<(), McpError> {
+        match &self.inner {
+            Inner::Cli(c) => c.restart_container(container_id).await,
+            Inner::Api(c) => c.restart_container(container_id).await,
+        }
+    }
+
+    pub(crate) fn check_read_only(&self, operation: &str) -> Result<(), McpError> {
+        match &self.inner {
+            Inner::Cli(c) => c.check_read_only(operation),
+            Inner::Api(c) => c.check_read_only(operation),
+        }
+    }
+}
+
+impl DockerClient for DockerBackend {
+    async fn list_containers(&self, args: Value) -> Result<CallToolResult, McpError> {
+        match &self.inner {
+            Inner::Cli(c) => c.list_containers(args).await,
+            Inner::Api(c) => c.list_containers(args).await,
+        }
+    }
+
+    async fn container_start(&self, args: Value) -> Result<CallToolResult, McpError> {
+        match &self.inner {
+            Inner::Cli(c) => c.container_start(args).await,
+            Inner::Api(c) => c.container_start(args).await,
+        }
+    }
+
+    async fn container_stop(&self, args: Value) -> Result<CallToolResult, McpError> {
+        match &self.inner {
+            Inner::Cli(c) => c.container_stop(args).await,
+            Inner::Api(c) => c.container_stop(args).await,
+        }
+    }
+
+    async fn container_logs(&self, args: Value) -> Result<CallToolResult, McpError> {
+        match &self.inner {
+            Inner::Cli(c) => c.container_logs(args).await,
+            Inner::Api(c) => c.container_logs(args).await,
+        }
+    }
+
+    async fn list_images(&self, args: Value) -> Result<CallToolResult, McpError> {
+        match &self.inner {
+            Inner::Cli(c) => c.list_images(args).await,
+            Inner::Api(c) => c.list_images(args).await,
+        }
+    }
+
+    async fn image_build(&self, args: Value) -> Result<CallToolResult, McpError> {
+        match &self.inner {
+            Inner::Cli(c) => c.image_build(args).await,
+            Inner::Api(c) => c.image_build(args).await,
+        }
+    }
+
+    async fn docker_events(&self, args: Value) -> Result<CallToolResult, McpError> {
+        match &self.inner {
+            Inner::Cli(c) => c.docker_events(args).await,
+            Inner::Api(c) => c.docker_events(args).await,
+        }
+    }
+
+    async fn container_stats(&self, args: Value) -> Result<CallToolResult, McpError> {
+        match &self.inner {
+            Inner::Cli(c) => c.container_stats(args).await,
+            Inner::Api(c) => c.container_stats(args).await,
+        }
+    }
+
+    async fn container_copy_in(&self, args: Value) -> Result<CallToolResult, McpError> {
+        match &self.inner {
+            Inner::Cli(c) => c.container_copy_in(args).await,
+            Inner::Api(c) => c.container_copy_in(args).await,
+        }
+    }
+
+    async fn container_copy_out(&self, args: Value) -> Result<CallToolResult, McpError> {
+        match &self.inner {
+            Inner::Cli(c) => c.container_copy_out(args).await,
+            Inner::Api(c) => c.container_copy_out(args).await,
+        }
+    }
+
+    async fn compose_up(&self, args: Value) -> Result<CallToolResult, McpError> {
+        match &self.inner {
+            Inner::Cli(c) => c.compose_up(args).await,
+            Inner::Api(c) => c.compose_up(args).await,
+        }
+    }
+
+    async fn compose_down(&self, args: Value) -> Result<CallToolResult, McpError> {
+        match &self.inner {
+            Inner::Cli(c) => c.compose_down(args).await,
+            Inner::Api(c) => c.compose_down(args).await,
+        }
+    }
+
+    async fn validate_compose(&self, args: Value) -> Result<CallToolResult, McpError> {
+        match &self.inner {
+            Inner::Cli(c) => c.validate_compose(args).await,
+            Inner::Api(c) => c.validate_compose(args).await,
+        }
+    }
+
+    async fn list_volumes(&self, args: Value) -> Result<CallToolResult, McpError> {
+        match &self.inner {
+            Inner::Cli(c) => c.list_volumes(args).await,
+            Inner::Api(c) => c.list_volumes(args).await,
+        }
+    }
+
+    async fn create_volume(&self, args: Value) -> Result<CallToolResult, McpError> {
+        match &self.inner {
+            Inner::Cli(c) => c.create_volume(args).await,
+            Inner::Api(c) => c.create_volume(args).await,
+        }
+    }
+
+    async fn remove_volume(&self, args: Value) -> Result<CallToolResult, McpError> {
+        match &self.inner {
+            Inner::Cli(c) => c.remove_volume(args).await,
+            Inner::Api(c) => c.remove_volume(args).await,
+        }
+    }
+
+    async fn prune_volumes(&self, args: Value) -> Result<CallToolResult, McpError> {
+        match &self.inner {
+            Inner::Cli(c) => c.prune_volumes(args).await,
+            Inner::Api(c) => c.prune_volumes(args).await,
+        }
+    }
+
+    async fn get_docker_info(&self) -> Result<String, McpError> {
+        match &self.inner {
+            Inner::Cli(c) => c.get_docker_info().await,
+            Inner::Api(c) => c.get_docker_info().await,
+        }
+    }
+
+    async fn get_docker_version(&self) -> Result<String, McpError> {
+        match &self.inner {
+            Inner::Cli(c) => c.get_docker_version().await,
+            Inner::Api(c) => c.get_docker_version().await,
+        }
+    }
+
+    async fn get_container_details(&self, container_id: &str) -> Result<String, McpError> {
+        match &self.inner {
+            Inner::Cli(c) => c.get_container_details(container_id).await,
+            Inner::Api(c) => c.get_container_details(container_id).await,
+        }
+    }
+
+    async fn get_image_details(&self, image_id: &str) -> Result<String, McpError> {
+        match &self.inner {
+            Inner::Cli(c) => c.get_image_details(image_id).await,
+            Inner::Api(c) => c.get_image_details(image_id).await,
+        }
+    }
+
+    async fn get_volume_details(&self, volume_name: &str) -> Result<String, McpError> {
+        match &self.inner {
+            Inner::Cli(c) => c.get_volume_details(volume_name).await,
+            Inner::Api(c) => c.get_volume_details(volume_name).await,
+        }
+    }
+
+    async fn get_compose_status(&self, project_directory: &str) -> Result<String, McpError> {
+        match &self.inner {
+            Inner::Cli(c) => c.get_compose_status(project_directory).await,
+            Inner::Api(c) => c.get_compose_status(project_directory).await,
+        }
+    }
+
+    async fn list_network_names(&self) -> Result<Vec<String>, McpError> {
+        match &self.inner {
+            Inner::Cli(c) => c.list_network_names().await,
+            Inner::Api(c) => c.list_network_names().await,
+        }
+    }
+}
@@ -0,0 +1,1309 @@
+//! CLI fallback backend: every operation shells out to the `docker` binary
+//! instead of talking to the Engine API directly. Selected at runtime by
+//! `DockerBackend`, either explicitly (`docker.backend = "cli"`) or as the
+//! automatic fallback when the `api` backend's socket isn't reachable —
+//! the only thing it needs is a `docker` binary on `PATH` (or at
+//! `docker.docker_path`).
+//!
+//! Exec sessions have no Engine API `exec_id` to key off of here, since each
+//! one is just a spawned `docker exec` child process; we mint our own id and
+//! track exit codes in `exec_exit_codes` until the caller asks for them.
+
+use std::collections::HashMap;
+use std::process::Stdio;
+use std::sync::Arc;
+
+use base64::Engine;
+use futures::stream::{BoxStream, StreamExt};
+use serde_json::Value;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::process::Command;
+use tokio::sync::{mpsc, Mutex};
+
+use crate::config::types::DockerSettings;
+use crate::protocol::error::McpError;
+use crate::protocol::types::{CallToolResult, Content, TextContent};
+
+use super::compose;
+use super::readonly::check_read_only;
+use super::types::{
+    classify_container_state, ContainerStateSummary, ContainerStatsSample, ExecChunk, ExecHandle, ExecStream, LogChunk, LogStream,
+    MountInfo, VolumeUsageSummary,
+};
+use super::DockerClient;
+
+pub struct DockerClientImpl {
+    settings: DockerSettings,
+    exec_exit_codes: Arc<Mutex<HashMap<String, Option<i64>>>>,
+}
+
+impl DockerClientImpl {
+    pub fn get_compose_path(&self) -> &std::path::Path {
+        &self.settings.compose_path
+    }
+
+    pub fn new(settings: &DockerSettings) -> Result<Self, McpError> {
+        Ok(Self {
+            settings: settings.clone(),
+            exec_exit_codes: Arc::new(Mutex::new(HashMap::new())),
+        })
+    }
+
+    fn command(&self) -> Command {
+        Command::new(&self.settings.docker_path)
+    }
+
+    /// Tails `docker logs -f` for a container, tagging each line with the
+    /// pipe it arrived on. Unlike the socket backend there's no multiplexed
+    /// framing to undo — stdout and stderr are already separate pipes.
+    pub fn follow_logs(&self, container_id: &str) -> BoxStream<'static, Result<LogChunk, McpError>> {
+        let mut command = self.command();
+        command
+            .arg("logs")
+            .arg("-f")
+            .arg("--tail")
+            .arg("0")
+            .arg(container_id)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+
+        spawn_piped_lines(command)
+    }
+
+    /// Streams `docker events --format '{{json .}}'`, applying the same
+    /// since/until/filter scoping the `list_containers`/`list_images`
+    /// filter argument already uses.
+    pub fn stream_events(
+        &self,
+        since: Option<i64>,
+        until: Option<i64>,
+        filters: HashMap<String, Vec<String>>,
+    ) -> BoxStream<'static, Result<Value, McpError>> {
+        let mut command = self.command();
+        command.arg("events").arg("--format").arg("{{json .}}");
+
+        if let Some(since) = since {
+            command.arg("--since").arg(since.to_string());
+        }
+        if let Some(until) = until {
+            command.arg("--until").arg(until.to_string());
+        }
+        for (key, values) in filters {
+            for value in values {
+                command.arg("--filter").arg(format!("{}={}", key, value));
+            }
+        }
+        command.stdout(Stdio::piped()).stderr(Stdio::null());
+
+        let mut child = match command.spawn() {
+            Ok(child) => child,
+            Err(e) => {
+                let err = McpError::DockerError(format!("Failed to start docker events: {}", e));
+                return Box::pin(futures::stream::once(async move { Err(err) }));
+            }
+        };
+
+        let Some(stdout) = child.stdout.take() else {
+            return Box::pin(futures::stream::once(async move {
+                Err(McpError::InternalError("Missing docker events stdout".to_string()))
+            }));
+        };
+
+        Box::pin(
+            tokio_stream_lines(stdout)
+                .map(|line| serde_json::from_str::<Value>(&line).map_err(McpError::from)),
+        )
+    }
+
+    /// Starts `cmd` inside `container_id` via `docker exec -i` and hands
+    /// back a handle the caller can use to feed stdin and drain stdout/
+    /// stderr chunks as they arrive, same shape as the socket backend.
+    pub async fn start_exec(
+        &self,
+        container_id: &str,
+        cmd: Vec<String>,
+        working_dir: Option<String>,
+        env: Option<Vec<String>>,
+        tty: bool,
+    ) -> Result<(String, ExecHandle), McpError> {
+        let exec_id = uuid::Uuid::new_v4().to_string();
+
+        let mut command = self.command();
+        command.arg("exec").arg("-i");
+        if tty {
+            command.arg("-t");
+        }
+        if let Some(dir) = &working_dir {
+            command.arg("-w").arg(dir);
+        }
+        for kv in env.unwrap_or_default() {
+            command.arg("-e").arg(kv);
+        }
+        command.arg(container_id);
+        command.args(&cmd);
+        command.stdin(Stdio::piped()).stdout(Stdio::piped()).stderr(Stdio::piped());
+
+        let mut child = command
+            .spawn()
+            .map_err(|e| McpError::DockerError(format!("Failed to start docker exec: {}", e)))?;
+
+        let mut stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| McpError::InternalError("Missing exec stdin".to_string()))?;
+        let mut stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| McpError::InternalError("Missing exec stdout".to_string()))?;
+        let mut stderr = child
+            .stderr
+            .take()
+            .ok_or_else(|| McpError::InternalError("Missing exec stderr".to_string()))?;
+
+        let (stdin_tx, mut stdin_rx) = mpsc::channel::<Vec<u8>>(32);
+        tokio::spawn(async move {
+            while let Some(bytes) = stdin_rx.recv().await {
+                if stdin.write_all(&bytes).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        let (chunk_tx, chunk_rx) = mpsc::channel::<Result<ExecChunk, McpError>>(32);
+
+        let out_tx = chunk_tx.clone();
+        tokio::spawn(async move {
+            let mut buf = [0u8; 4096];
+            loop {
+                match stdout.read(&mut buf).await {
+                    Ok(0) => break,
+                    Ok(n) => {
+                        if out_tx
+                            .send(Ok(ExecChunk { stream: ExecStream::Stdout, data: buf[..n].to_vec() }))
+                            .await
+                            .is_err()
+                        {
+                            break;
+                        }
+                    }
+                    Err(e) => {
+                        let _ = out_tx.send(Err(McpError::DockerError(format!("Exec stdout error: {}", e)))).await;
+                        break;
+                    }
+                }
+            }
+        });
+
+        let err_tx = chunk_tx.clone();
+        tokio::spawn(async move {
+            let mut buf = [0u8; 4096];
+            loop {
+                match stderr.read(&mut buf).await {
+                    Ok(0) => break,
+                    Ok(n) => {
+                        if err_tx
+                            .send(Ok(ExecChunk { stream: ExecStream::Stderr, data: buf[..n].to_vec() }))
+                            .await
+                            .is_err()
+                        {
+                            break;
+                        }
+                    }
+                    Err(e) => {
+                        let _ = err_tx.send(Err(McpError::DockerError(format!("Exec stderr error: {}", e)))).await;
+                        break;
+                    }
+                }
+            }
+        });
+        drop(chunk_tx);
+
+        self.exec_exit_codes.lock().await.insert(exec_id.clone(), None);
+        let exit_codes = self.exec_exit_codes.clone();
+        let eid = exec_id.clone();
+        tokio::spawn(async move {
+            if let Ok(status) = child.wait().await {
+                exit_codes.lock().await.insert(eid, status.code().map(i64::from));
+            }
+        });
+
+        let output = Box::pin(futures::stream::unfold(chunk_rx, |mut rx| async move {
+            rx.recv().await.map(|item| (item, rx))
+        }));
+
+        Ok((exec_id, ExecHandle { stdin_tx, output }))
+    }
+
+    pub async fn exec_exit_code(&self, exec_id: &str) -> Result<Option<i64>, McpError> {
+        Ok(self.exec_exit_codes.lock().await.get(exec_id).copied().flatten())
+    }
+
+    /// One resource-usage sample for `container_id`, parsed from `docker
+    /// stats --no-stream`'s single-line JSON. Unlike the `api` backend,
+    /// this can't report per-core usage or memory cache separately — the
+    /// CLI's formatted output doesn't carry them — and `cpu_percent`
+    /// reflects dockerd's own precomputed figure rather than one derived
+    /// from our own two successive samples.
+    pub async fn get_container_stats(&self, container_id: &str) -> Result<ContainerStatsSample, McpError> {
+        let output = self
+            .command()
+            .arg("stats")
+            .arg("--no-stream")
+            .arg("--format")
+            .arg("{{json .}}")
+            .arg(container_id)
+            .output()
+            .await
+            .map_err(|e| McpError::DockerError(format!("Failed to run docker stats: {}", e)))?;
+
+        if !output.status.success() {
+            return Err(McpError::DockerError(format!(
+                "Failed to get stats for container {}: {}",
+                container_id,
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        let line = String::from_utf8_lossy(&output.stdout);
+        let line = line.lines().next().unwrap_or_default();
+        let raw: Value = serde_json::from_str(line)
+            .map_err(|e| McpError::DockerError(format!("Failed to parse docker stats output: {}", e)))?;
+
+        let get_str = |key: &str| raw.get(key).and_then(|v| v.as_str()).unwrap_or_default();
+
+        let cpu_percent = get_str("CPUPerc").trim_end_matches('%').parse().unwrap_or(0.0);
+
+        let (mem_usage, mem_limit) = parse_slash_pair(get_str("MemUsage"));
+        let (blk_read, blk_write) = parse_slash_pair(get_str("BlockIO"));
+        let (network_rx, network_tx) = parse_slash_pair(get_str("NetIO"));
+        let pids_current = get_str("PIDs").parse().unwrap_or(0);
+
+        Ok(ContainerStatsSample {
+            cpu_percent,
+            cpu_total_usage: 0,
+            per_cpu_usage: Vec::new(),
+            memory_usage: mem_usage,
+            memory_limit: mem_limit,
+            memory_cache: 0,
+            pids_current,
+            pids_limit: None,
+            blk_read,
+            blk_write,
+            network_rx,
+            network_tx,
+        })
+    }
+
+    /// The container's bind mounts and volumes, for [`super::paths`]'s
+    /// host↔container path translation. `docker inspect` already reports
+    /// the same `Source`/`Destination`/`RW` triple the `api` backend reads
+    /// off `ContainerInspectResponse::mounts`.
+    pub async fn get_container_mounts(&self, container_id: &str) -> Result<Vec<MountInfo>, McpError> {
+        let output = self
+            .command()
+            .arg("inspect")
+            .arg("--format")
+            .arg("{{json .Mounts}}")
+            .arg(container_id)
+            .output()
+            .await
+            .map_err(|e| McpError::DockerError(format!("Failed to inspect container mounts: {}", e)))?;
+
+        if !output.status.success() {
+            return Err(McpError::DockerError(format!(
+                "Failed to inspect mounts for container {}: {}",
+                container_id,
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        let raw: Vec<Value> = serde_json::from_str(String::from_utf8_lossy(&output.stdout).trim())
+            .map_err(|e| McpError::DockerError(format!("Failed to parse container mounts: {}", e)))?;
+
+        Ok(raw
+            .into_iter()
+            .map(|m| MountInfo {
+                source: m.get("Source").and_then(|v| v.as_str()).unwrap_or_default().to_string(),
+                destination: m.get("Destination").and_then(|v| v.as_str()).unwrap_or_default().to_string(),
+                read_only: !m.get("RW").and_then(|v| v.as_bool()).unwrap_or(true),
+            })
+            .collect())
+    }
+
+    /// `State` out of `docker inspect`, for `wait_for_container`'s polling
+    /// loop. `--format '{{json .State}}'` always returns a single object,
+    /// sidestepping the array-vs-object inconsistency `get_container_details`
+    /// has between backends.
+    pub async fn inspect_state(&self, container_id: &str) -> Result<ContainerStateSummary, McpError> {
+        let output = self
+            .command()
+            .arg("inspect")
+            .arg("--format")
+            .arg("{{json .State}}")
+            .arg(container_id)
+            .output()
+            .await
+            .map_err(|e| McpError::DockerError(format!("Failed to inspect container state: {}", e)))?;
+
+        if !output.status.success() {
+            return Err(McpError::DockerError(format!(
+                "Failed to inspect state for container {}: {}",
+                container_id,
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        let raw: Value = serde_json::from_str(String::from_utf8_lossy(&output.stdout).trim())
+            .map_err(|e| McpError::DockerError(format!("Failed to parse container state: {}", e)))?;
+
+        let status = raw.get("Status").and_then(|v| v.as_str()).unwrap_or_default();
+        let health_status = raw.get("Health").and_then(|h| h.get("Status")).and_then(|v| v.as_str()).map(String::from);
+        let exit_code = raw.get("ExitCode").and_then(|v| v.as_i64()).unwrap_or(0);
+        let restarting = raw.get("Restarting").and_then(|v| v.as_bool()).unwrap_or(false);
+
+        Ok(ContainerStateSummary {
+            state: classify_container_state(status, health_status.as_deref(), exit_code),
+            health_status,
+            restarting,
+        })
+    }
+
+    /// Counts volumes carrying `VOLUME_OWNER_LABEL` for `run_diagnostic`'s
+    /// owned-volume report. `docker volume ls` has no per-volume size
+    /// column (that's `docker system df -v`'s human-formatted output, which
+    /// isn't worth parsing for one number), so `total_reclaimable_bytes`
+    /// stays `None` on this backend - use the `api` backend for that figure.
+    pub async fn owned_volumes_usage(&self) -> Result<VolumeUsageSummary, McpError> {
+        let output = self
+            .command()
+            .arg("volume")
+            .arg("ls")
+            .arg("--filter")
+            .arg(format!("label={}={}", super::VOLUME_OWNER_LABEL, super::VOLUME_OWNER_VALUE))
+            .arg("-q")
+            .output()
+            .await
+            .map_err(|e| McpError::DockerError(format!("Failed to list owned volumes: {}", e)))?;
+
+        if !output.status.success() {
+            return Err(McpError::DockerError(format!(
+                "docker volume ls failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        let owned_count = String::from_utf8_lossy(&output.stdout).lines().filter(|line| !line.trim().is_empty()).count();
+
+        Ok(VolumeUsageSummary { owned_count, total_reclaimable_bytes: None })
+    }
+
+    /// The `docker` CLI has no `exec resize` subcommand, so a session
+    /// started with `tty: true` through this backend can't have its
+    /// terminal size updated after the fact; use the `api` backend for
+    /// resizable PTY sessions.
+    pub async fn resize_exec(&self, _exec_id: &str, _rows: u16, _cols: u16) -> Result<(), McpError> {
+        Err(McpError::OperationNotPermitted(
+            "Resizing a PTY is only supported by the `api` Docker backend".to_string(),
+        ))
+    }
+
+    pub(crate) fn check_read_only(&self, operation: &str) -> Result<(), McpError> {
+        check_read_only(&self.settings, operation)
+    }
+
+    pub async fn list_unhealthy_containers(&self, label: &str) -> Result<Vec<String>, McpError> {
+        let output = self
+            .command()
+            .arg("ps")
+            .arg("-a")
+            .arg("-q")
+            .arg("--filter")
+            .arg(format!("label={}", label))
+            .arg("--filter")
+            .arg("health=unhealthy")
+            .output()
+            .await
+            .map_err(|e| McpError::DockerError(format!("Failed to list unhealthy containers: {}", e)))?;
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(|line| line.trim().to_string())
+            .filter(|line| !line.is_empty())
+            .collect())
+    }
+
+    pub async fn restart_container(&self, container_id: &str) -> Result<(), McpError> {
+        let output = self
+            .command()
+            .arg("restart")
+            .arg(container_id)
+            .output()
+            .await
+            .map_err(|e| McpError::DockerError(format!("Failed to restart container {}: {}", container_id, e)))?;
+
+        if !output.status.success() {
+            return Err(McpError::DockerError(format!(
+                "docker restart {} failed: {}",
+                container_id,
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// Spawns `command` and turns its stdout/stderr into a stream of tagged
+/// `LogChunk` lines, the `cli` backend's equivalent of the socket backend's
+/// multiplexed `LogOutput`.
+fn spawn_piped_lines(mut command: Command) -> BoxStream<'static, Result<LogChunk, McpError>> {
+    let mut child = match command.spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            let err = McpError::DockerError(format!("Failed to start docker logs: {}", e));
+            return Box::pin(futures::stream::once(async move { Err(err) }));
+        }
+    };
+
+    let (tx, rx) = mpsc::channel::<Result<LogChunk, McpError>>(32);
+
+    if let Some(stdout) = child.stdout.take() {
+        let tx = tx.clone();
+        tokio::spawn(async move {
+            let mut lines = BufReader::new(stdout).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                if tx.send(Ok(LogChunk { stream: LogStream::Stdout, text: line })).await.is_err() {
+                    break;
+                }
+            }
+        });
+    }
+
+    if let Some(stderr) = child.stderr.take() {
+        let tx = tx.clone();
+        tokio::spawn(async move {
+            let mut lines = BufReader::new(stderr).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                if tx.send(Ok(LogChunk { stream: LogStream::Stderr, text: line })).await.is_err() {
+                    break;
+                }
+            }
+        });
+    }
+    drop(tx);
+
+    tokio::spawn(async move {
+        let _ = child.wait().await;
+    });
+
+    Box::pin(futures::stream::unfold(rx, |mut rx| async move { rx.recv().await.map(|item| (item, rx)) }))
+}
+
+/// Turns a child's stdout pipe into a stream of UTF-8 lines, used by
+/// `stream_events` where every line is its own independent JSON object.
+fn tokio_stream_lines(stdout: tokio::process::ChildStdout) -> BoxStream<'static, String> {
+    let (tx, rx) = mpsc::channel::<String>(32);
+    tokio::spawn(async move {
+        let mut lines = BufReader::new(stdout).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            if tx.send(line).await.is_err() {
+                break;
+            }
+        }
+    });
+    Box::pin(futures::stream::unfold(rx, |mut rx| async move { rx.recv().await.map(|item| (item, rx)) }))
+}
+
+/// Splits a `docker stats` "used / limit" field (e.g. `"10MiB / 1.944GiB"`)
+/// and parses each side with [`parse_docker_size`].
+fn parse_slash_pair(field: &str) -> (u64, u64) {
+    let mut parts = field.split('/').map(str::trim);
+    let used = parts.next().map(parse_docker_size).unwrap_or(0);
+    let limit = parts.next().map(parse_docker_size).unwrap_or(0);
+    (used, limit)
+}
+
+/// Parses a `docker stats` human-readable size (`"1.944GiB"`, `"796kB"`,
+/// `"0B"`) into bytes. Binary suffixes (`KiB`/`MiB`/`GiB`/`TiB`) are
+/// powers of 1024; decimal ones (`kB`/`MB`/`GB`/`TB`) are powers of 1000,
+/// matching how the CLI itself formats `go-units.BytesSize`.
+fn parse_docker_size(s: &str) -> u64 {
+    const UNITS: &[(&str, f64)] = &[
+        ("TiB", 1024.0 * 1024.0 * 1024.0 * 1024.0),
+        ("GiB", 1024.0 * 1024.0 * 1024.0),
+        ("MiB", 1024.0 * 1024.0),
+        ("KiB", 1024.0),
+        ("TB", 1_000.0 * 1_000.0 * 1_000.0 * 1_000.0),
+        ("GB", 1_000.0 * 1_000.0 * 1_000.0),
+        ("MB", 1_000.0 * 1_000.0),
+        ("kB", 1_000.0),
+        ("B", 1.0),
+    ];
+
+    let s = s.trim();
+    for (suffix, multiplier) in UNITS {
+        if let Some(number) = s.strip_suffix(suffix) {
+            if let Ok(value) = number.trim().parse::<f64>() {
+                return (value * multiplier) as u64;
+            }
+        }
+    }
+
+    s.parse().unwrap_or(0)
+}
+
+impl DockerClient for DockerClientImpl {
+    async fn list_containers(&self, args: Value) -> Result<CallToolResult, McpError> {
+        self.check_read_only("list_containers")?;
+
+        let all = args.get("all").and_then(|v| v.as_bool()).unwrap_or(false);
+        let limit = args.get("limit").and_then(|v| v.as_u64());
+        let filter = args.get("filter").and_then(|v| v.as_str());
+
+        let mut command = self.command();
+        command.arg("ps").arg("--format").arg("{{json .}}").arg("--no-trunc");
+        if all {
+            command.arg("-a");
+        }
+        if let Some(limit) = limit {
+            command.arg("-n").arg(limit.to_string());
+        }
+        if let Some(filter) = filter {
+            command.arg("--filter").arg(filter);
+        }
+
+        let output = tokio::time::timeout(self.settings.operation_timeout, command.output())
+            .await
+            .map_err(|_| McpError::OperationTimeout)?
+            .map_err(|e| McpError::DockerError(format!("Failed to list containers: {}", e)))?;
+
+        if !output.status.success() {
+            return Err(McpError::DockerError(format!(
+                "docker ps failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        let containers: Vec<Value> = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect();
+
+        Ok(CallToolResult {
+            content: vec![Content::Text(TextContent {
+                r#type: "text".to_string(),
+                text: serde_json::to_string_pretty(&containers)?,
+            })],
+            is_error: false,
+        })
+    }
+
+    async fn container_start(&self, args: Value) -> Result<CallToolResult, McpError> {
+        self.check_read_only("container_start")?;
+
+        let container_id = args
+            .get("container_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| McpError::InvalidParams("Missing container_id parameter".to_string()))?;
+
+        let output = self
+            .command()
+            .arg("start")
+            .arg(container_id)
+            .output()
+            .await
+            .map_err(|e| McpError::DockerError(format!("Failed to start container: {}", e)))?;
+
+        if !output.status.success() {
+            return Err(McpError::DockerError(format!(
+                "Failed to start container {}: {}",
+                container_id,
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        Ok(CallToolResult {
+            content: vec![Content::Text(TextContent {
+                r#type: "text".to_string(),
+                text: format!("Container {} started successfully", container_id),
+            })],
+            is_error: false,
+        })
+    }
+
+    async fn container_stop(&self, args: Value) -> Result<CallToolResult, McpError> {
+        self.check_read_only("container_stop")?;
+
+        let container_id = args
+            .get("container_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| McpError::InvalidParams("Missing container_id parameter".to_string()))?;
+
+        let timeout = args.get("timeout").and_then(|v| v.as_u64()).unwrap_or(10);
+
+        let output = self
+            .command()
+            .arg("stop")
+            .arg("-t")
+            .arg(timeout.to_string())
+            .arg(container_id)
+            .output()
+            .await
+            .map_err(|e| McpError::DockerError(format!("Failed to stop container: {}", e)))?;
+
+        if !output.status.success() {
+            return Err(McpError::DockerError(format!(
+                "Failed to stop container {}: {}",
+                container_id,
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        Ok(CallToolResult {
+            content: vec![Content::Text(TextContent {
+                r#type: "text".to_string(),
+                text: format!("Container {} stopped successfully", container_id),
+            })],
+            is_error: false,
+        })
+    }
+
+    async fn container_logs(&self, args: Value) -> Result<CallToolResult, McpError> {
+        self.check_read_only("container_logs")?;
+
+        let container_id = args
+            .get("container_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| McpError::InvalidParams("Missing container_id parameter".to_string()))?;
+
+        let tail = args.get("tail").and_then(|v| v.as_str()).unwrap_or("all");
+        let since = args.get("since").and_then(|v| v.as_str());
+
+        let mut command = self.command();
+        command.arg("logs").arg(container_id);
+        if tail != "all" {
+            command.arg("--tail").arg(tail);
+        }
+        if let Some(since) = since {
+            command.arg("--since").arg(since);
+        }
+
+        let output = tokio::time::timeout(self.settings.operation_timeout, command.output())
+            .await
+            .map_err(|_| McpError::OperationTimeout)?
+            .map_err(|e| McpError::DockerError(format!("Failed to get container logs: {}", e)))?;
+
+        let mut log_text = String::from_utf8_lossy(&output.stdout).to_string();
+        log_text.push_str(&String::from_utf8_lossy(&output.stderr));
+
+        if log_text.len() > self.settings.max_log_size {
+            log_text.truncate(self.settings.max_log_size);
+            log_text.push_str("\n... (log truncated due to size limit)");
+        }
+
+        Ok(CallToolResult {
+            content: vec![Content::Text(TextContent {
+                r#type: "text".to_string(),
+                text: log_text,
+            })],
+            is_error: false,
+        })
+    }
+
+    async fn list_images(&self, args: Value) -> Result<CallToolResult, McpError> {
+        self.check_read_only("list_images")?;
+
+        let all = args.get("all").and_then(|v| v.as_bool()).unwrap_or(false);
+        let filter = args.get("filter").and_then(|v| v.as_str());
+
+        let mut command = self.command();
+        command.arg("images").arg("--format").arg("{{json .}}").arg("--no-trunc");
+        if all {
+            command.arg("-a");
+        }
+        if let Some(filter) = filter {
+            command.arg("--filter").arg(filter);
+        }
+
+        let output = command
+            .output()
+            .await
+            .map_err(|e| McpError::DockerError(format!("Failed to list images: {}", e)))?;
+
+        if !output.status.success() {
+            return Err(McpError::DockerError(format!(
+                "docker images failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        let images: Vec<Value> = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect();
+
+        Ok(CallToolResult {
+            content: vec![Content::Text(TextContent {
+                r#type: "text".to_string(),
+                text: serde_json::to_string_pretty(&images)?,
+            })],
+            is_error: false,
+        })
+    }
+
+    async fn image_build(&self, args: Value) -> Result<CallToolResult, McpError> {
+        self.check_read_only("image_build")?;
+
+        let tag = args
+            .get("tag")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| McpError::InvalidParams("Missing tag parameter".to_string()))?;
+
+        let build_args: Vec<String> = args
+            .get("build_args")
+            .and_then(|v| v.as_object())
+            .map(|obj| obj.iter().filter_map(|(k, v)| v.as_str().map(|s| format!("{}={}", k, s))).collect())
+            .unwrap_or_default();
+
+        let context_tar = super::build_context_tar(&args)?;
+
+        // `docker build -t tag -` reads the build context as a tar stream
+        // from stdin, the same contract `container_copy_in` relies on for
+        // piping an archive into `docker cp`.
+        let mut command = self.command();
+        command.arg("build").arg("-t").arg(tag);
+        for build_arg in &build_args {
+            command.arg("--build-arg").arg(build_arg);
+        }
+        command.arg("-").stdin(Stdio::piped()).stdout(Stdio::piped()).stderr(Stdio::piped());
+
+        let mut child = command
+            .spawn()
+            .map_err(|e| McpError::DockerError(format!("Failed to start docker build: {}", e)))?;
+        let mut stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| McpError::InternalError("Missing docker build stdin".to_string()))?;
+
+        let run = async {
+            stdin
+                .write_all(&context_tar)
+                .await
+                .map_err(|e| McpError::DockerError(format!("Failed to write build context: {}", e)))?;
+            drop(stdin);
+            child
+                .wait_with_output()
+                .await
+                .map_err(|e| McpError::DockerError(format!("Failed to wait for docker build: {}", e)))
+        };
+
+        let output = tokio::time::timeout(self.settings.operation_timeout, run)
+            .await
+            .map_err(|_| McpError::OperationTimeout)??;
+
+        let mut log_text = String::new();
+        log_text.push_str(&String::from_utf8_lossy(&output.stdout));
+        log_text.push_str(&String::from_utf8_lossy(&output.stderr));
+
+        if log_text.len() > self.settings.max_log_size {
+            log_text.truncate(self.settings.max_log_size);
+        }
+
+        Ok(CallToolResult {
+            content: vec![Content::Text(TextContent { r#type: "text".to_string(), text: log_text })],
+            is_error: !output.status.success(),
+        })
+    }
+
+    async fn docker_events(&self, args: Value) -> Result<CallToolResult, McpError> {
+        self.check_read_only("docker_events")?;
+
+        let since = args.get("since").and_then(|v| v.as_str()).and_then(super::parse_time_arg);
+        let until = args.get("until").and_then(|v| v.as_str()).and_then(super::parse_time_arg);
+
+        let filters: HashMap<String, Vec<String>> = args
+            .get("filters")
+            .and_then(|v| v.as_object())
+            .map(|obj| {
+                obj.iter()
+                    .map(|(k, v)| {
+                        let values = v
+                            .as_array()
+                            .map(|arr| arr.iter().filter_map(|s| s.as_str().map(String::from)).collect())
+                            .unwrap_or_default();
+                        (k.clone(), values)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        use futures::stream::TryStreamExt;
+        let events = tokio::time::timeout(
+            self.settings.operation_timeout,
+            self.stream_events(since, until, filters).try_collect::<Vec<_>>(),
+        )
+        .await
+        .map_err(|_| McpError::OperationTimeout)?
+        .map_err(|e| McpError::DockerError(format!("Failed to collect Docker events: {}", e)))?;
+
+        Ok(CallToolResult {
+            content: vec![Content::Text(TextContent {
+                r#type: "text".to_string(),
+                text: serde_json::to_string_pretty(&events)?,
+            })],
+            is_error: false,
+        })
+    }
+
+    async fn container_stats(&self, args: Value) -> Result<CallToolResult, McpError> {
+        self.check_read_only("container_stats")?;
+
+        let container_id = args
+            .get("container_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| McpError::InvalidParams("Missing container_id parameter".to_string()))?;
+
+        if args.get("stream").and_then(|v| v.as_bool()).unwrap_or(false) {
+            return Err(McpError::InvalidParams(
+                "container_stats only returns a one-shot sample; use docker/stats/subscribe for continuous streaming"
+                    .to_string(),
+            ));
+        }
+
+        let sample = tokio::time::timeout(self.settings.operation_timeout, self.get_container_stats(container_id))
+            .await
+            .map_err(|_| McpError::OperationTimeout)??;
+
+        Ok(CallToolResult {
+            content: vec![Content::Text(TextContent {
+                r#type: "text".to_string(),
+                text: serde_json::to_string_pretty(&super::types::container_stats_to_json(&sample))?,
+            })],
+            is_error: false,
+        })
+    }
+
+    async fn container_copy_in(&self, args: Value) -> Result<CallToolResult, McpError> {
+        self.check_read_only("container_copy_in")?;
+
+        let container_id = args
+            .get("container_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| McpError::InvalidParams("Missing container_id parameter".to_string()))?;
+        let path = args
+            .get("path")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| McpError::InvalidParams("Missing path parameter".to_string()))?;
+        let tar_base64 = args
+            .get("tar_base64")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| McpError::InvalidParams("Missing tar_base64 parameter".to_string()))?;
+
+        let tar_bytes = base64::engine::general_purpose::STANDARD
+            .decode(tar_base64)
+            .map_err(|e| McpError::InvalidParams(format!("Invalid base64 tar payload: {}", e)))?;
+
+        // `docker cp - container:path` reads a tar archive from stdin and
+        // extracts it at `path`, the same format the daemon's upload
+        // archive endpoint expects — so both backends share one wire
+        // format for `tar_base64` despite going through different paths.
+        let mut command = self.command();
+        command
+            .arg("cp")
+            .arg("-")
+            .arg(format!("{}:{}", container_id, path))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped());
+
+        let mut child = command
+            .spawn()
+            .map_err(|e| McpError::DockerError(format!("Failed to start docker cp: {}", e)))?;
+        let mut stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| McpError::InternalError("Missing docker cp stdin".to_string()))?;
+
+        let run = async {
+            stdin
+                .write_all(&tar_bytes)
+                .await
+                .map_err(|e| McpError::DockerError(format!("Failed to write tar payload: {}", e)))?;
+            drop(stdin);
+            child
+                .wait_with_output()
+                .await
+                .map_err(|e| McpError::DockerError(format!("Failed to wait for docker cp: {}", e)))
+        };
+
+        let output = tokio::time::timeout(self.settings.operation_timeout, run)
+            .await
+            .map_err(|_| McpError::OperationTimeout)??;
+
+        if !output.status.success() {
+            return Err(McpError::DockerError(format!(
+                "docker cp failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        Ok(CallToolResult {
+            content: vec![Content::Text(TextContent {
+                r#type: "text".to_string(),
+                text: format!("Copied archive into {}:{}", container_id, path),
+            })],
+            is_error: false,
+        })
+    }
+
+    async fn container_copy_out(&self, args: Value) -> Result<CallToolResult, McpError> {
+        self.check_read_only("container_copy_out")?;
+
+        let container_id = args
+            .get("container_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| McpError::InvalidParams("Missing container_id parameter".to_string()))?;
+        let path = args
+            .get("path")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| McpError::InvalidParams("Missing path parameter".to_string()))?;
+
+        // `docker cp container:path -` streams a tar archive of `path` to
+        // stdout instead of writing it to the host filesystem.
+        let mut command = self.command();
+        command.arg("cp").arg(format!("{}:{}", container_id, path)).arg("-");
+
+        let output = tokio::time::timeout(self.settings.operation_timeout, command.output())
+            .await
+            .map_err(|_| McpError::OperationTimeout)?
+            .map_err(|e| McpError::DockerError(format!("Failed to copy from container: {}", e)))?;
+
+        if !output.status.success() {
+            return Err(McpError::DockerError(format!(
+                "docker cp failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        let mut tar_bytes = output.stdout;
+        let truncated = tar_bytes.len() > self.settings.max_log_size;
+        if truncated {
+            tar_bytes.truncate(self.settings.max_log_size);
+        }
+
+        let tar_base64 = base64::engine::general_purpose::STANDARD.encode(&tar_bytes);
+
+        Ok(CallToolResult {
+            content: vec![Content::Text(TextContent {
+                r#type: "text".to_string(),
+                text: serde_json::json!({ "tar_base64": tar_base64, "truncated": truncated }).to_string(),
+            })],
+            is_error: false,
+        })
+    }
+
+    async fn compose_up(&self, args: Value) -> Result<CallToolResult, McpError> {
+        self.check_read_only("compose_up")?;
+        compose::compose_up(&self.settings, args).await
+    }
+
+    async fn compose_down(&self, args: Value) -> Result<CallToolResult, McpError> {
+        self.check_read_only("compose_down")?;
+        compose::compose_down(&self.settings, args).await
+    }
+
+    async fn validate_compose(&self, args: Value) -> Result<CallToolResult, McpError> {
+        self.check_read_only("validate_compose")?;
+        compose::validate_compose(&self.settings, args).await
+    }
+
+    async fn list_volumes(&self, args: Value) -> Result<CallToolResult, McpError> {
+        self.check_read_only("list_volumes")?;
+
+        let filter = args.get("filter").and_then(|v| v.as_str());
+
+        let mut command = self.command();
+        command.arg("volume").arg("ls").arg("--format").arg("{{json .}}");
+        if let Some(filter) = filter {
+            command.arg("--filter").arg(filter);
+        }
+
+        let output = command
+            .output()
+            .await
+            .map_err(|e| McpError::DockerError(format!("Failed to list volumes: {}", e)))?;
+
+        if !output.status.success() {
+            return Err(McpError::DockerError(format!(
+                "docker volume ls failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        let volumes: Vec<Value> = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect();
+
+        Ok(CallToolResult {
+            content: vec![Content::Text(TextContent {
+                r#type: "text".to_string(),
+                text: serde_json::to_string_pretty(&volumes)?,
+            })],
+            is_error: false,
+        })
+    }
+
+    async fn create_volume(&self, args: Value) -> Result<CallToolResult, McpError> {
+        self.check_read_only("create_volume")?;
+
+        let name = args.get("name").and_then(|v| v.as_str());
+        let driver = args.get("driver").and_then(|v| v.as_str());
+        let labels: Vec<String> = args
+            .get("labels")
+            .and_then(|v| v.as_object())
+            .map(|obj| obj.iter().filter_map(|(k, v)| v.as_str().map(|s| format!("{}={}", k, s))).collect())
+            .unwrap_or_default();
+
+        let mut command = self.command();
+        command.arg("volume").arg("create");
+        if let Some(driver) = driver {
+            command.arg("--driver").arg(driver);
+        }
+        command.arg("--label").arg(format!("{}={}", super::VOLUME_OWNER_LABEL, super::VOLUME_OWNER_VALUE));
+        for label in &labels {
+            command.arg("--label").arg(label);
+        }
+        if let Some(name) = name {
+            command.arg(name);
+        }
+
+        let output = command
+            .output()
+            .await
+            .map_err(|e| McpError::DockerError(format!("Failed to create volume: {}", e)))?;
+
+        if !output.status.success() {
+            return Err(McpError::DockerError(format!(
+                "docker volume create failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        Ok(CallToolResult {
+            content: vec![Content::Text(TextContent {
+                r#type: "text".to_string(),
+                text: String::from_utf8_lossy(&output.stdout).trim().to_string(),
+            })],
+            is_error: false,
+        })
+    }
+
+    async fn remove_volume(&self, args: Value) -> Result<CallToolResult, McpError> {
+        self.check_read_only("remove_volume")?;
+
+        let name = args
+            .get("name")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| McpError::InvalidParams("Missing name parameter".to_string()))?;
+        let force = args.get("force").and_then(|v| v.as_bool()).unwrap_or(false);
+
+        let mut command = self.command();
+        command.arg("volume").arg("rm");
+        if force {
+            command.arg("--force");
+        }
+        command.arg(name);
+
+        let output = command
+            .output()
+            .await
+            .map_err(|e| McpError::DockerError(format!("Failed to remove volume: {}", e)))?;
+
+        if !output.status.success() {
+            return Err(McpError::DockerError(format!(
+                "Failed to remove volume {}: {}",
+                name,
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        Ok(CallToolResult {
+            content: vec![Content::Text(TextContent {
+                r#type: "text".to_string(),
+                text: format!("Removed volume {}", name),
+            })],
+            is_error: false,
+        })
+    }
+
+    /// Prunes dangling (unattached) volumes. Defaults to only those carrying
+    /// `VOLUME_OWNER_LABEL` — volumes this server itself created — so a
+    /// careless prune doesn't take out unrelated data the host's other
+    /// workloads still expect to find by name later; `all: true` opts into
+    /// `docker volume prune`'s normal behavior of removing every dangling
+    /// volume regardless of origin.
+    async fn prune_volumes(&self, args: Value) -> Result<CallToolResult, McpError> {
+        self.check_read_only("prune_volumes")?;
+
+        let prune_all = args.get("all").and_then(|v| v.as_bool()).unwrap_or(false);
+
+        let mut command = self.command();
+        command.arg("volume").arg("prune").arg("--force");
+        if !prune_all {
+            command.arg("--filter").arg(format!("label={}={}", super::VOLUME_OWNER_LABEL, super::VOLUME_OWNER_VALUE));
+        }
+
+        let output = command
+            .output()
+            .await
+            .map_err(|e| McpError::DockerError(format!("Failed to prune volumes: {}", e)))?;
+
+        if !output.status.success() {
+            return Err(McpError::DockerError(format!(
+                "docker volume prune failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        Ok(CallToolResult {
+            content: vec![Content::Text(TextContent {
+                r#type: "text".to_string(),
+                text: String::from_utf8_lossy(&output.stdout).trim().to_string(),
+            })],
+            is_error: false,
+        })
+    }
+
+    async fn get_docker_info(&self) -> Result<String, McpError> {
+        self.check_read_only("get_docker_info")?;
+
+        let output = self
+            .command()
+            .arg("info")
+            .arg("--format")
+            .arg("{{json .}}")
+            .output()
+            .await
+            .map_err(|e| McpError::DockerError(format!("Failed to get docker info: {}", e)))?;
+
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    async fn get_docker_version(&self) -> Result<String, McpError> {
+        self.check_read_only("get_docker_version")?;
+
+        let output = self
+            .command()
+            .arg("version")
+            .arg("--format")
+            .arg("{{json .}}")
+            .output()
+            .await
+            .map_err(|e| McpError::DockerError(format!("Failed to get docker version: {}", e)))?;
+
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    async fn get_container_details(&self, container_id: &str) -> Result<String, McpError> {
+        self.check_read_only("get_container_details")?;
+
+        let output = self
+            .command()
+            .arg("inspect")
+            .arg(container_id)
+            .output()
+            .await
+            .map_err(|e| McpError::DockerError(format!("Failed to inspect container: {}", e)))?;
+
+        if !output.status.success() {
+            return Err(McpError::DockerError(format!(
+                "Failed to inspect container {}: {}",
+                container_id,
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    async fn get_image_details(&self, image_id: &str) -> Result<String, McpError> {
+        self.check_read_only("get_image_details")?;
+
+        let output = self
+            .command()
+            .arg("inspect")
+            .arg(image_id)
+            .output()
+            .await
+            .map_err(|e| McpError::DockerError(format!("Failed to inspect image: {}", e)))?;
+
+        if !output.status.success() {
+            return Err(McpError::DockerError(format!(
+                "Failed to inspect image {}: {}",
+                image_id,
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    async fn get_volume_details(&self, volume_name: &str) -> Result<String, McpError> {
+        self.check_read_only("get_volume_details")?;
+
+        let output = self
+            .command()
+            .arg("volume")
+            .arg("inspect")
+            .arg(volume_name)
+            .output()
+            .await
+            .map_err(|e| McpError::DockerError(format!("Failed to inspect volume: {}", e)))?;
+
+        if !output.status.success() {
+            return Err(McpError::DockerError(format!(
+                "Failed to inspect volume {}: {}",
+                volume_name,
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    async fn get_compose_status(&self, project_directory: &str) -> Result<String, McpError> {
+        self.check_read_only("get_compose_status")?;
+        compose::get_compose_status(&self.settings, project_directory).await
+    }
+
+    async fn list_network_names(&self) -> Result<Vec<String>, McpError> {
+        self.check_read_only("list_network_names")?;
+
+        let output = self
+            .command()
+            .arg("network")
+            .arg("ls")
+            .arg("--format")
+            .arg("{{.Name}}")
+            .output()
+            .await
+            .map_err(|e| McpError::DockerError(format!("Failed to list networks: {}", e)))?;
+
+        if !output.status.success() {
+            return Err(McpError::DockerError(format!(
+                "docker network ls failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(str::to_string)
+            .filter(|line| !line.trim().is_empty())
+            .collect())
+    }
+}
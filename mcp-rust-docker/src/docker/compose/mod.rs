@@ -0,0 +1,213 @@
+//! Compose operations. `validate_compose` is pure parsing ([`model`]) plus
+//! a [`model::topological_order`] graph check — no daemon involved — and is
+//! shared by both backends. `compose_up`/`compose_down`/`get_compose_status`
+//! here still shell out to the `docker-compose` binary for the `cli`
+//! backend, since there's no Engine API equivalent bollard exposes for
+//! running a compose file directly; the `api` backend instead drives
+//! `create_network`/`create_volume`/`create_container` itself, and derives
+//! status from `list_containers`, natively, in [`native`].
+
+use std::process::Command;
+
+use serde_json::Value;
+
+use crate::config::types::DockerSettings;
+use crate::protocol::error::McpError;
+use crate::protocol::types::{CallToolResult, Content, TextContent};
+
+pub mod model;
+pub mod native;
+
+fn check_allowed_project(settings: &DockerSettings, project_directory: &str) -> Result<(), McpError> {
+    if let Some(allowed_projects) = &settings.allowed_compose_projects {
+        if !allowed_projects.contains(project_directory) {
+            return Err(McpError::OperationNotPermitted(format!(
+                "Project directory '{}' is not in the allowed list",
+                project_directory
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn format_output(stdout: &str, stderr: &str) -> String {
+    let mut result = String::new();
+    if !stdout.is_empty() {
+        result.push_str(&format!("STDOUT:\n{}", stdout));
+    }
+    if !stderr.is_empty() {
+        if !result.is_empty() {
+            result.push('\n');
+        }
+        result.push_str(&format!("STDERR:\n{}", stderr));
+    }
+    result
+}
+
+pub async fn compose_up(settings: &DockerSettings, args: Value) -> Result<CallToolResult, McpError> {
+    let project_directory = args
+        .get("project_directory")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| McpError::InvalidParams("Missing project_directory parameter".to_string()))?;
+
+    check_allowed_project(settings, project_directory)?;
+
+    let detach = args.get("detach").and_then(|v| v.as_bool()).unwrap_or(true);
+    let services: Vec<String> = args
+        .get("services")
+        .and_then(|v| v.as_array())
+        .map(|arr| arr.iter().filter_map(|s| s.as_str().map(String::from)).collect())
+        .unwrap_or_default();
+
+    let mut command = Command::new(&settings.compose_path);
+    command.current_dir(project_directory);
+    command.arg("up");
+
+    if detach {
+        command.arg("-d");
+    }
+
+    for service in services {
+        command.arg(&service);
+    }
+
+    let output = tokio::process::Command::from(command)
+        .output()
+        .await
+        .map_err(|e| McpError::DockerError(format!("Failed to execute docker-compose: {}", e)))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    let result = format_output(&stdout, &stderr);
+
+    Ok(CallToolResult {
+        content: vec![Content::Text(TextContent {
+            r#type: "text".to_string(),
+            text: if output.status.success() {
+                format!("Docker Compose up successful for {}:\n{}", project_directory, result)
+            } else {
+                format!("Docker Compose up failed for {}:\n{}", project_directory, result)
+            },
+        })],
+        is_error: !output.status.success(),
+    })
+}
+
+pub async fn compose_down(settings: &DockerSettings, args: Value) -> Result<CallToolResult, McpError> {
+    let project_directory = args
+        .get("project_directory")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| McpError::InvalidParams("Missing project_directory parameter".to_string()))?;
+
+    check_allowed_project(settings, project_directory)?;
+
+    let volumes = args.get("volumes").and_then(|v| v.as_bool()).unwrap_or(false);
+    let remove_images = args.get("remove_images").and_then(|v| v.as_str());
+
+    let mut command = Command::new(&settings.compose_path);
+    command.current_dir(project_directory);
+    command.arg("down");
+
+    if volumes {
+        command.arg("-v");
+    }
+
+    if let Some(images) = remove_images {
+        match images {
+            "all" => {
+                command.arg("--rmi").arg("all");
+            }
+            "local" => {
+                command.arg("--rmi").arg("local");
+            }
+            _ => {}
+        }
+    }
+
+    let output = tokio::process::Command::from(command)
+        .output()
+        .await
+        .map_err(|e| McpError::DockerError(format!("Failed to execute docker-compose: {}", e)))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    let result = format_output(&stdout, &stderr);
+
+    Ok(CallToolResult {
+        content: vec![Content::Text(TextContent {
+            r#type: "text".to_string(),
+            text: if output.status.success() {
+                format!("Docker Compose down successful for {}:\n{}", project_directory, result)
+            } else {
+                format!("Docker Compose down failed for {}:\n{}", project_directory, result)
+            },
+        })],
+        is_error: !output.status.success(),
+    })
+}
+
+/// Validates a compose file by deserializing it through [`model::parse`]
+/// (which itself runs [`model::topological_order`] to reject a cyclic
+/// `depends_on` graph, rejecting the file outright on a schema-level
+/// problem like a missing `image` or an undefined `depends_on` target),
+/// then, once it parses, running [`model::lint`]'s semantic passes
+/// (undeclared volumes, duplicate published host ports, images pinned to
+/// `latest`) and reporting every violation found rather than stopping at
+/// the first — so `generate-compose` output, or anything else producing a
+/// compose file, can be round-tripped through a real check instead of
+/// trusted blindly. No temp file, no `docker-compose` subprocess, no
+/// Docker daemon at all, shared by both backends.
+pub async fn validate_compose(_settings: &DockerSettings, args: Value) -> Result<CallToolResult, McpError> {
+    let compose_content = args
+        .get("compose_content")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| McpError::InvalidParams("Missing compose_content parameter".to_string()))?;
+
+    let (diagnostics, is_error) = match model::parse(compose_content) {
+        Ok(compose) => {
+            let mut services: Vec<&String> = compose.services.keys().collect();
+            services.sort();
+            let violations = model::lint(&compose);
+            let is_error = !violations.is_empty();
+            (
+                serde_json::json!({
+                    "valid": !is_error,
+                    "version": compose.version,
+                    "services": services,
+                    "volumes": compose.volumes.iter().flatten().map(|(name, _)| name).collect::<Vec<_>>(),
+                    "networks": compose.networks.iter().flatten().map(|(name, _)| name).collect::<Vec<_>>(),
+                    "violations": violations,
+                    "errors": Vec::<String>::new(),
+                }),
+                is_error,
+            )
+        }
+        Err(e) => (serde_json::json!({ "valid": false, "violations": [{ "path": "$", "rule": "schema", "message": e.to_string() }], "errors": [e.to_string()] }), true),
+    };
+
+    Ok(CallToolResult {
+        content: vec![Content::Text(TextContent { r#type: "text".to_string(), text: diagnostics.to_string() })],
+        is_error,
+    })
+}
+
+pub async fn get_compose_status(settings: &DockerSettings, project_directory: &str) -> Result<String, McpError> {
+    check_allowed_project(settings, project_directory)?;
+
+    let mut command = Command::new(&settings.compose_path);
+    command.current_dir(project_directory);
+    command.arg("ps");
+    command.arg("--format").arg("json");
+
+    let output = tokio::process::Command::from(command)
+        .output()
+        .await
+        .map_err(|e| McpError::DockerError(format!("Failed to execute docker-compose: {}", e)))?;
+
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        Err(McpError::DockerError(format!("Failed to get compose status: {}", stderr)))
+    }
+}
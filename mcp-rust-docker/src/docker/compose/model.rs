@@ -0,0 +1,233 @@
+//! Typed representation of a Docker Compose file, deserialized directly
+//! via `serde_yaml` instead of only ever being handed to the
+//! `docker-compose` binary as an opaque blob. Covers the subset of the
+//! Compose spec this server actually acts on: [`super::native`] translates
+//! it into `create_network`/`create_volume`/`create_container` calls, and
+//! [`super::validate_compose`] runs [`parse`] (and, transitively,
+//! [`topological_order`]) plus [`lint`]'s semantic checks against it
+//! directly, with no daemon involved.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::protocol::error::McpError;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DockerCompose {
+    pub version: Option<String>,
+    pub services: HashMap<String, Service>,
+    #[serde(default)]
+    pub volumes: Option<HashMap<String, Volume>>,
+    #[serde(default)]
+    pub networks: Option<HashMap<String, Network>>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Service {
+    pub image: Option<String>,
+    pub container_name: Option<String>,
+    #[serde(default)]
+    pub ports: Vec<String>,
+    #[serde(default)]
+    pub volumes: Vec<String>,
+    #[serde(default)]
+    pub environment: Vec<String>,
+    pub restart: Option<String>,
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Volume {
+    pub driver: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Network {
+    pub driver: Option<String>,
+}
+
+/// Deserializes `content` and runs the semantic checks `docker-compose
+/// config` used to do for us: every service needs an `image` (this server
+/// doesn't build images, so `build:`-only services are rejected),
+/// `depends_on` can't name a service that doesn't exist or itself, and the
+/// `depends_on` graph as a whole can't contain a cycle (checked via
+/// [`topological_order`], the same pass `compose_up` orders services with).
+pub fn parse(content: &str) -> Result<DockerCompose, McpError> {
+    let compose: DockerCompose = serde_yaml::from_str(content)
+        .map_err(|e| McpError::InvalidParams(format!("Invalid compose file: {}", e)))?;
+
+    for (name, service) in &compose.services {
+        if service.image.is_none() {
+            return Err(McpError::InvalidParams(format!(
+                "Service '{}' has no image (build: is not supported)",
+                name
+            )));
+        }
+        for dep in &service.depends_on {
+            if dep == name {
+                return Err(McpError::InvalidParams(format!("Service '{}' depends on itself", name)));
+            }
+            if !compose.services.contains_key(dep) {
+                return Err(McpError::InvalidParams(format!(
+                    "Service '{}' depends on undefined service '{}'",
+                    name, dep
+                )));
+            }
+        }
+    }
+
+    topological_order(&compose)?;
+
+    Ok(compose)
+}
+
+/// Orders `compose`'s services via Kahn's algorithm so every service comes
+/// after everything in its `depends_on`, for `compose_up` to create/start
+/// against in turn (and `compose_down` to tear down in reverse). Repeatedly
+/// emits every service whose remaining in-degree (unsatisfied `depends_on`
+/// count) is zero, decrementing its dependents' in-degree in turn; if no
+/// service is left with in-degree zero but services remain, those
+/// remaining services form a `depends_on` cycle and the caller gets a
+/// named error back instead of an arbitrary order.
+pub fn topological_order(compose: &DockerCompose) -> Result<Vec<(&str, &Service)>, McpError> {
+    let mut in_degree: HashMap<&str, usize> = compose.services.keys().map(|name| (name.as_str(), 0)).collect();
+    let mut dependents: HashMap<&str, Vec<&str>> = HashMap::new();
+
+    for (name, service) in &compose.services {
+        for dep in &service.depends_on {
+            *in_degree.get_mut(name.as_str()).expect("name is a key of compose.services") += 1;
+            dependents.entry(dep.as_str()).or_default().push(name.as_str());
+        }
+    }
+
+    let mut ready: Vec<&str> = in_degree.iter().filter(|(_, &degree)| degree == 0).map(|(&name, _)| name).collect();
+
+    let mut ordered = Vec::with_capacity(compose.services.len());
+    while !ready.is_empty() {
+        ready.sort();
+        let name = ready.remove(0);
+        ordered.push((name, &compose.services[name]));
+
+        for &dependent in dependents.get(name).map(Vec::as_slice).unwrap_or_default() {
+            let degree = in_degree.get_mut(dependent).expect("dependent is a key of compose.services");
+            *degree -= 1;
+            if *degree == 0 {
+                ready.push(dependent);
+            }
+        }
+    }
+
+    if ordered.len() != compose.services.len() {
+        let mut cyclic: Vec<&str> = in_degree.into_iter().filter(|&(_, degree)| degree > 0).map(|(name, _)| name).collect();
+        cyclic.sort();
+        return Err(McpError::InvalidParams(format!(
+            "depends_on forms a cycle among service(s): {}",
+            cyclic.join(", ")
+        )));
+    }
+
+    Ok(ordered)
+}
+
+/// One rule `lint` found broken: `path` locates it the way a JSON Pointer
+/// would (`services.web.ports[0]`), `rule` is a stable machine-readable
+/// name a caller could filter on, `message` is what a human reads.
+#[derive(Debug, Clone, Serialize)]
+pub struct Violation {
+    pub path: String,
+    pub rule: String,
+    pub message: String,
+}
+
+fn violation(path: impl Into<String>, rule: &'static str, message: impl Into<String>) -> Violation {
+    Violation { path: path.into(), rule: rule.to_string(), message: message.into() }
+}
+
+/// Named-volume half of a `service.volumes` entry (`"myvol:/data"`), or
+/// `None` for a bind mount (an absolute/relative/home-relative host path)
+/// or an anonymous volume (no `:` at all) — neither of those names
+/// anything under top-level `volumes` for [`lint`] to check.
+fn named_volume_source(mapping: &str) -> Option<&str> {
+    let (source, _) = mapping.split_once(':')?;
+    if source.is_empty() || source.starts_with(['/', '.', '~']) {
+        return None;
+    }
+    Some(source)
+}
+
+/// Host half of a `service.ports` entry (`"8080:80"`), or `None` for a
+/// bare container port (`"80"`) — Docker assigns that a random host port,
+/// so it can never collide with another service's.
+fn host_port(mapping: &str) -> Option<&str> {
+    mapping.split_once(':').map(|(host, _)| host)
+}
+
+/// Runs every lint [`parse`] can't express as a hard parse failure, over an
+/// already-[`parse`]d (so already schema-valid and `depends_on`-sound)
+/// compose document: volume references that aren't declared under
+/// top-level `volumes`, host ports published by more than one service, and
+/// images pinned to `latest` (either explicitly or by omitting a tag,
+/// which resolves to `latest` anyway). Returns every violation found
+/// rather than stopping at the first, so a caller can report them all at
+/// once instead of round-tripping through the tool one fix at a time.
+pub fn lint(compose: &DockerCompose) -> Vec<Violation> {
+    let mut violations = Vec::new();
+
+    let declared_volumes: HashSet<&str> = compose.volumes.iter().flatten().map(|(name, _)| name.as_str()).collect();
+    let mut services: Vec<&String> = compose.services.keys().collect();
+    services.sort();
+
+    let mut host_ports: HashMap<&str, Vec<&str>> = HashMap::new();
+
+    for name in services {
+        let service = &compose.services[name];
+
+        for (i, mapping) in service.volumes.iter().enumerate() {
+            if let Some(source) = named_volume_source(mapping) {
+                if !declared_volumes.contains(source) {
+                    violations.push(violation(
+                        format!("services.{}.volumes[{}]", name, i),
+                        "undeclared-volume",
+                        format!("Volume '{}' is not declared under top-level `volumes`", source),
+                    ));
+                }
+            }
+        }
+
+        for mapping in &service.ports {
+            if let Some(port) = host_port(mapping) {
+                host_ports.entry(port).or_default().push(name.as_str());
+            }
+        }
+
+        if let Some(image) = &service.image {
+            let pinned_to_latest = match image.rsplit_once(':') {
+                // Distinguish a tag from a registry port (e.g. "host:5000/image"):
+                // a real tag's segment after ':' never contains '/'.
+                Some((_, tag)) if !tag.contains('/') => tag == "latest",
+                _ => true,
+            };
+            if pinned_to_latest {
+                violations.push(violation(
+                    format!("services.{}.image", name),
+                    "image-pinned-to-latest",
+                    format!("Image '{}' resolves to the `latest` tag; pin an explicit version", image),
+                ));
+            }
+        }
+    }
+
+    for (port, owners) in host_ports {
+        if owners.len() > 1 {
+            violations.push(violation(
+                "services",
+                "duplicate-host-port",
+                format!("Host port {} is published by more than one service: {}", port, owners.join(", ")),
+            ));
+        }
+    }
+
+    violations
+}
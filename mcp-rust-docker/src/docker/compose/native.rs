@@ -0,0 +1,314 @@
+//! `compose_up`/`compose_down` for the `api` backend, run directly through
+//! its `bollard::Docker` client instead of shelling out to
+//! `docker-compose`. Created containers are tagged with `PROJECT_LABEL`/
+//! `SERVICE_LABEL` so `compose_down` can find everything belonging to a
+//! project without relying on `docker-compose`'s own naming convention.
+
+use std::collections::HashMap;
+
+use bollard::container::{
+    Config, CreateContainerOptions, ListContainersOptions, RemoveContainerOptions, StartContainerOptions,
+    StopContainerOptions,
+};
+use bollard::models::{HostConfig, PortBinding, RestartPolicy, RestartPolicyNameEnum};
+use bollard::network::CreateNetworkOptions;
+use bollard::volume::CreateVolumeOptions;
+use bollard::Docker;
+use serde_json::Value;
+
+use crate::config::types::DockerSettings;
+use crate::protocol::error::McpError;
+use crate::protocol::types::{CallToolResult, Content, TextContent};
+
+use super::model::{self, DockerCompose, Service};
+
+const PROJECT_LABEL: &str = "mcp.compose.project";
+const SERVICE_LABEL: &str = "mcp.compose.service";
+
+fn project_name(project_directory: &str) -> String {
+    std::path::Path::new(project_directory)
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| project_directory.to_string())
+}
+
+fn container_name(project: &str, service: &str, explicit: &Option<String>) -> String {
+    explicit.clone().unwrap_or_else(|| format!("{}_{}_1", project, service))
+}
+
+fn read_compose_file(project_directory: &str) -> Result<DockerCompose, McpError> {
+    let path = std::path::Path::new(project_directory).join("docker-compose.yml");
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| McpError::InvalidParams(format!("Failed to read {}: {}", path.display(), e)))?;
+    model::parse(&content)
+}
+
+fn restart_policy(restart: &Option<String>) -> Option<RestartPolicy> {
+    let name = match restart.as_deref() {
+        Some("always") => RestartPolicyNameEnum::ALWAYS,
+        Some("on-failure") => RestartPolicyNameEnum::ON_FAILURE,
+        Some("unless-stopped") => RestartPolicyNameEnum::UNLESS_STOPPED,
+        _ => return None,
+    };
+    Some(RestartPolicy { name: Some(name), maximum_retry_count: None })
+}
+
+fn port_bindings(ports: &[String]) -> Option<HashMap<String, Option<Vec<PortBinding>>>> {
+    if ports.is_empty() {
+        return None;
+    }
+
+    let mut bindings: HashMap<String, Option<Vec<PortBinding>>> = HashMap::new();
+    for mapping in ports {
+        // "8080:80" (host:container) or just "80" (container only, random host port)
+        let (host_port, container_port) = match mapping.split_once(':') {
+            Some((host, container)) => (Some(host.to_string()), container.to_string()),
+            None => (None, mapping.clone()),
+        };
+        bindings.insert(format!("{}/tcp", container_port), Some(vec![PortBinding { host_ip: None, host_port }]));
+    }
+    Some(bindings)
+}
+
+fn host_config(service: &Service) -> HostConfig {
+    HostConfig {
+        binds: if service.volumes.is_empty() { None } else { Some(service.volumes.clone()) },
+        port_bindings: port_bindings(&service.ports),
+        restart_policy: restart_policy(&service.restart),
+        ..Default::default()
+    }
+}
+
+/// Creates declared networks and volumes, then creates and starts a
+/// container per service in `depends_on` order (per
+/// [`model::topological_order`] — a cyclic `depends_on` graph aborts here
+/// before anything is created). Not transactional: a failure partway
+/// through leaves earlier networks/volumes/services in place, the same as
+/// `docker-compose up` stopping mid-way would.
+pub async fn compose_up(client: &Docker, settings: &DockerSettings, args: Value) -> Result<CallToolResult, McpError> {
+    let project_directory = args
+        .get("project_directory")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| McpError::InvalidParams("Missing project_directory parameter".to_string()))?;
+
+    super::check_allowed_project(settings, project_directory)?;
+
+    let requested_services: Option<Vec<String>> = args
+        .get("services")
+        .and_then(|v| v.as_array())
+        .map(|arr| arr.iter().filter_map(|s| s.as_str().map(String::from)).collect());
+
+    let compose = read_compose_file(project_directory)?;
+    let project = project_name(project_directory);
+
+    // Best-effort: re-running `compose_up` against a project whose
+    // networks/volumes already exist shouldn't fail the whole call.
+    for (name, volume) in compose.volumes.iter().flatten() {
+        let _ = client
+            .create_volume(CreateVolumeOptions {
+                name: name.clone(),
+                driver: volume.driver.clone().unwrap_or_default(),
+                ..Default::default()
+            })
+            .await;
+    }
+    for (name, network) in compose.networks.iter().flatten() {
+        let _ = client
+            .create_network(CreateNetworkOptions {
+                name: name.clone(),
+                driver: network.driver.clone().unwrap_or_default(),
+                ..Default::default()
+            })
+            .await;
+    }
+
+    let mut started = Vec::new();
+    for (name, service) in model::topological_order(&compose)? {
+        if let Some(requested) = &requested_services {
+            if !requested.iter().any(|r| r == name) {
+                continue;
+            }
+        }
+
+        // `model::parse` already rejected services with no image.
+        let image = service.image.clone().expect("compose services without an image are rejected by model::parse");
+        let container = container_name(&project, name, &service.container_name);
+
+        let mut labels = HashMap::new();
+        labels.insert(PROJECT_LABEL.to_string(), project.clone());
+        labels.insert(SERVICE_LABEL.to_string(), name.to_string());
+
+        let config = Config {
+            image: Some(image),
+            env: if service.environment.is_empty() { None } else { Some(service.environment.clone()) },
+            labels: Some(labels),
+            host_config: Some(host_config(service)),
+            ..Default::default()
+        };
+
+        client
+            .create_container(Some(CreateContainerOptions { name: container.clone(), platform: None }), config)
+            .await
+            .map_err(|e| McpError::DockerError(format!("Failed to create '{}': {}", container, e)))?;
+
+        client
+            .start_container(&container, None::<StartContainerOptions<String>>)
+            .await
+            .map_err(|e| McpError::DockerError(format!("Failed to start '{}': {}", container, e)))?;
+
+        started.push(container);
+    }
+
+    Ok(CallToolResult {
+        content: vec![Content::Text(TextContent {
+            r#type: "text".to_string(),
+            text: format!(
+                "Started {} service(s) for project '{}': {}",
+                started.len(),
+                project,
+                started.join(", ")
+            ),
+        })],
+        is_error: false,
+    })
+}
+
+async fn stop_and_remove(client: &Docker, id: &str, remove_anonymous_volumes: bool) -> Result<(), McpError> {
+    // Best-effort: a container that's already stopped shouldn't block the
+    // rest of the teardown.
+    let _ = client.stop_container(id, Some(StopContainerOptions { t: 10 })).await;
+
+    client
+        .remove_container(id, Some(RemoveContainerOptions { force: true, v: remove_anonymous_volumes, ..Default::default() }))
+        .await
+        .map_err(|e| McpError::DockerError(format!("Failed to remove container {}: {}", id, e)))
+}
+
+/// Stops and removes every container labeled with `project`, in reverse
+/// `depends_on` order when the project's compose file can still be read
+/// (so a service is torn down only after everything that depends on it),
+/// falling back to whatever order `list_containers` returns them in
+/// otherwise. Containers that don't match a known service (e.g. left over
+/// from a since-edited compose file) are removed last, in no particular
+/// order — mirrors `docker-compose down` tearing down everything it finds
+/// for a project. `volumes: true` additionally removes the project's named
+/// volumes, and `remove_images: "all"` removes every image a service in
+/// the compose file referenced (`remove_images: "local"` is a no-op here,
+/// since this server doesn't support `build:` and so never builds an image
+/// "local" would otherwise mean).
+pub async fn compose_down(client: &Docker, settings: &DockerSettings, args: Value) -> Result<CallToolResult, McpError> {
+    let project_directory = args
+        .get("project_directory")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| McpError::InvalidParams("Missing project_directory parameter".to_string()))?;
+
+    super::check_allowed_project(settings, project_directory)?;
+
+    let remove_volumes = args.get("volumes").and_then(|v| v.as_bool()).unwrap_or(false);
+    let remove_images = args.get("remove_images").and_then(|v| v.as_str());
+    let project = project_name(project_directory);
+
+    let mut filters = HashMap::new();
+    filters.insert("label".to_string(), vec![format!("{}={}", PROJECT_LABEL, project)]);
+
+    let containers = client
+        .list_containers(Some(ListContainersOptions { all: true, filters, ..Default::default() }))
+        .await
+        .map_err(|e| McpError::DockerError(format!("Failed to list containers for project '{}': {}", project, e)))?;
+
+    let mut by_service: HashMap<String, Vec<String>> = HashMap::new();
+    for container in containers {
+        let id = match container.id {
+            Some(id) => id,
+            None => continue,
+        };
+        let service = container.labels.as_ref().and_then(|labels| labels.get(SERVICE_LABEL)).cloned().unwrap_or_default();
+        by_service.entry(service).or_default().push(id);
+    }
+
+    let compose = read_compose_file(project_directory).ok();
+    let teardown_order: Vec<String> = match &compose {
+        Some(compose) => {
+            let mut order: Vec<String> = model::topological_order(compose)
+                .map(|ordered| ordered.into_iter().map(|(name, _)| name.to_string()).collect())
+                .unwrap_or_else(|_| compose.services.keys().cloned().collect());
+            order.reverse();
+            order
+        }
+        None => by_service.keys().cloned().collect(),
+    };
+
+    let mut removed = Vec::new();
+    for service in &teardown_order {
+        for id in by_service.remove(service).unwrap_or_default() {
+            stop_and_remove(client, &id, remove_volumes).await?;
+            removed.push(id);
+        }
+    }
+    // Left over: containers whose service label didn't match anything in
+    // `teardown_order` (unknown compose file, or a service removed from it
+    // since the containers were created).
+    for (_, ids) in by_service {
+        for id in ids {
+            stop_and_remove(client, &id, remove_volumes).await?;
+            removed.push(id);
+        }
+    }
+
+    if remove_volumes {
+        for name in compose.iter().flat_map(|c| c.volumes.iter().flatten()).map(|(name, _)| name) {
+            let _ = client.remove_volume(name, None).await;
+        }
+    }
+
+    if remove_images == Some("all") {
+        for image in compose.iter().flat_map(|c| c.services.values()).filter_map(|s| s.image.as_deref()) {
+            let _ = client.remove_image(image, None, None).await;
+        }
+    }
+
+    Ok(CallToolResult {
+        content: vec![Content::Text(TextContent {
+            r#type: "text".to_string(),
+            text: format!("Removed {} container(s) for project '{}'", removed.len(), project),
+        })],
+        is_error: false,
+    })
+}
+
+/// Status for `docker://compose/<project>` resources on the `api` backend:
+/// every container labeled with the project, with its `SERVICE_LABEL`
+/// resolved back to a service name, rather than parsing `docker-compose
+/// ps`'s text/JSON output (the `cli` backend's [`super::get_compose_status`]
+/// still does that, since it has no daemon connection of its own to list
+/// containers directly against).
+pub async fn get_compose_status(client: &Docker, settings: &DockerSettings, project_directory: &str) -> Result<String, McpError> {
+    super::check_allowed_project(settings, project_directory)?;
+
+    let project = project_name(project_directory);
+
+    let mut filters = HashMap::new();
+    filters.insert("label".to_string(), vec![format!("{}={}", PROJECT_LABEL, project)]);
+
+    let containers = client
+        .list_containers(Some(ListContainersOptions { all: true, filters, ..Default::default() }))
+        .await
+        .map_err(|e| McpError::DockerError(format!("Failed to list containers for project '{}': {}", project, e)))?;
+
+    let statuses: Vec<Value> = containers
+        .into_iter()
+        .map(|c| {
+            let service = c.labels.as_ref().and_then(|labels| labels.get(SERVICE_LABEL)).cloned().unwrap_or_default();
+            serde_json::json!({
+                "service": service,
+                "id": c.id,
+                "name": c.names.and_then(|names| names.into_iter().next()),
+                "image": c.image,
+                "state": c.state,
+                "status": c.status,
+            })
+        })
+        .collect();
+
+    serde_json::to_string_pretty(&statuses).map_err(McpError::from)
+}
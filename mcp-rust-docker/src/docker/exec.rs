@@ -0,0 +1,183 @@
+//! Stream demultiplexing for Docker's attach/exec framing, plus the
+//! plumbing needed to run an interactive command in a container and stream
+//! its stdout/stderr back incrementally instead of buffering it all.
+//!
+//! When a TTY is *not* allocated, Docker multiplexes stdout and stderr onto
+//! one byte stream: each frame is an 8-byte header — byte 0 is the stream
+//! type (0=stdin, 1=stdout, 2=stderr), bytes 4..8 a big-endian `u32` payload
+//! length — followed by exactly that many payload bytes. `StreamDemuxer`
+//! implements that framing directly; bollard's own `start_exec` already
+//! does the equivalent parsing for us over its hijacked connection, so the
+//! higher-level helpers below convert its `LogOutput` into the same
+//! `ExecChunk` shape rather than re-parsing bytes bollard has already split.
+
+use bollard::exec::{CreateExecOptions, ResizeExecOptions, StartExecResults};
+use bollard::Docker;
+use bytes::{Buf, BytesMut};
+use futures::stream::StreamExt;
+use tokio::sync::mpsc;
+
+use crate::protocol::error::McpError;
+
+use super::types::{ExecChunk, ExecHandle, ExecStream};
+
+/// Incrementally parses Docker's multiplexed stream framing. Frames can
+/// arrive split across reads, so `push` just appends to an internal buffer
+/// and `drain_frames` pulls out whatever complete frames are now available,
+/// leaving a trailing partial frame (if any) for the next call.
+#[derive(Default)]
+pub struct StreamDemuxer {
+    buffer: BytesMut,
+}
+
+impl StreamDemuxer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    pub fn drain_frames(&mut self) -> Vec<ExecChunk> {
+        let mut chunks = Vec::new();
+
+        loop {
+            if self.buffer.len() < 8 {
+                break;
+            }
+
+            let stream_type = self.buffer[0];
+            let size = u32::from_be_bytes([
+                self.buffer[4],
+                self.buffer[5],
+                self.buffer[6],
+                self.buffer[7],
+            ]) as usize;
+
+            if self.buffer.len() < 8 + size {
+                break;
+            }
+
+            let mut frame = self.buffer.split_to(8 + size);
+            frame.advance(8);
+
+            let stream = match stream_type {
+                0 => ExecStream::Stdin,
+                2 => ExecStream::Stderr,
+                _ => ExecStream::Stdout,
+            };
+
+            chunks.push(ExecChunk {
+                stream,
+                data: frame.to_vec(),
+            });
+        }
+
+        chunks
+    }
+}
+
+/// Starts `cmd` inside `container_id` and returns a channel to feed it
+/// stdin plus a stream of demultiplexed stdout/stderr chunks. The caller is
+/// responsible for draining `output` to completion (or dropping it, which
+/// aborts the underlying task) and for fetching the exit code afterwards
+/// via `inspect_exec_exit_code`.
+pub async fn start_exec(
+    client: Docker,
+    container_id: &str,
+    cmd: Vec<String>,
+    working_dir: Option<String>,
+    env: Option<Vec<String>>,
+    tty: bool,
+) -> Result<(String, ExecHandle), McpError> {
+    let exec = client
+        .create_exec(
+            container_id,
+            CreateExecOptions {
+                cmd: Some(cmd),
+                attach_stdout: Some(true),
+                attach_stderr: Some(true),
+                attach_stdin: Some(true),
+                tty: Some(tty),
+                working_dir,
+                env,
+                ..Default::default()
+            },
+        )
+        .await
+        .map_err(|e| McpError::DockerError(format!("Failed to create exec: {}", e)))?;
+
+    let exec_id = exec.id.clone();
+
+    let started = client
+        .start_exec(&exec.id, None)
+        .await
+        .map_err(|e| McpError::DockerError(format!("Failed to start exec: {}", e)))?;
+
+    let StartExecResults::Attached { mut output, mut input } = started else {
+        return Err(McpError::InternalError(
+            "Exec unexpectedly started detached".to_string(),
+        ));
+    };
+
+    let (stdin_tx, mut stdin_rx) = mpsc::channel::<Vec<u8>>(32);
+    tokio::spawn(async move {
+        use tokio::io::AsyncWriteExt;
+        while let Some(bytes) = stdin_rx.recv().await {
+            if input.write_all(&bytes).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    let output = Box::pin(output.map(|frame| {
+        frame
+            .map(|log_output| match log_output {
+                bollard::container::LogOutput::StdOut { message } => ExecChunk {
+                    stream: ExecStream::Stdout,
+                    data: message.to_vec(),
+                },
+                bollard::container::LogOutput::StdErr { message } => ExecChunk {
+                    stream: ExecStream::Stderr,
+                    data: message.to_vec(),
+                },
+                bollard::container::LogOutput::StdIn { message } => ExecChunk {
+                    stream: ExecStream::Stdin,
+                    data: message.to_vec(),
+                },
+                bollard::container::LogOutput::Console { message } => ExecChunk {
+                    stream: ExecStream::Stdout,
+                    data: message.to_vec(),
+                },
+            })
+            .map_err(|e| McpError::DockerError(format!("Exec stream error: {}", e)))
+    }));
+
+    Ok((
+        exec_id,
+        ExecHandle {
+            stdin_tx,
+            output,
+        },
+    ))
+}
+
+pub async fn inspect_exec_exit_code(client: &Docker, exec_id: &str) -> Result<Option<i64>, McpError> {
+    let inspect = client
+        .inspect_exec(exec_id)
+        .await
+        .map_err(|e| McpError::DockerError(format!("Failed to inspect exec: {}", e)))?;
+
+    Ok(inspect.exit_code)
+}
+
+/// Updates the terminal size of a `tty: true` exec session. Only meaningful
+/// for sessions started with a PTY allocated; the Engine API accepts the
+/// call either way; callers should only need it in the PTY case.
+pub async fn resize_exec(client: &Docker, exec_id: &str, rows: u16, cols: u16) -> Result<(), McpError> {
+    client
+        .resize_exec(exec_id, ResizeExecOptions { height: rows, width: cols })
+        .await
+        .map_err(|e| McpError::DockerError(format!("Failed to resize exec: {}", e)))
+}
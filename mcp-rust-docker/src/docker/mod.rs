@@ -1,15 +1,84 @@
-use bollard::container::{ListContainersOptions, LogsOptions, StartContainerOptions, StopContainerOptions};
-use bollard::image::ListImagesOptions;
-use bollard::Docker;
+use base64::Engine;
 use serde_json::Value;
-use std::collections::HashMap;
-use std::process::Command;
 
-use crate::config::types::DockerSettings;
 use crate::protocol::error::McpError;
-use crate::protocol::types::{CallToolResult, Content, TextContent};
-use futures::stream::TryStreamExt;
+use crate::protocol::types::CallToolResult;
+
+pub mod types;
+pub use types::{ExecChunk, ExecHandle, ExecStream, LogChunk, LogStream, MountInfo, VolumeUsageSummary};
+
+/// Applied to every volume `create_volume` creates, so `prune_volumes`'s
+/// default (label-scoped) mode and `run_diagnostic`'s owned-volume report
+/// can find server-created volumes without risking unrelated ones already
+/// on the host — the same "labels are the source of truth" approach
+/// `compose::native`'s `PROJECT_LABEL`/`SERVICE_LABEL` use for containers.
+pub(crate) const VOLUME_OWNER_LABEL: &str = "mcp.volume.owner";
+pub(crate) const VOLUME_OWNER_VALUE: &str = "mcp-server";
+
+mod compose;
+mod readonly;
+
+// Both backends always compile in now, since which one runs is a runtime
+// choice (`DockerSettings::backend`) made by `DockerBackend::new`, not a
+// build-time one. `cli`/`socket` stay private — callers go through
+// `DockerBackend`, never `cli::DockerClientImpl`/`socket::DockerClientImpl`
+// directly, so there's only ever one Docker-facing type in scope.
+pub mod exec;
+pub mod paths;
+pub mod wait;
+mod socket;
+mod cli;
+
+mod backend;
+pub use backend::DockerBackend;
+
+/// Parses a `since`/`until` timestamp argument as either an RFC3339
+/// instant or a relative offset from now (`"42m"`, `"3h"`), the same
+/// vocabulary `container_logs` accepts. Returns `None` if `s` matches
+/// neither form, in which case callers should leave the bound unset
+/// rather than guess.
+pub(crate) fn parse_time_arg(s: &str) -> Option<i64> {
+    if let Some(minutes) = s.strip_suffix('m') {
+        let minutes: i64 = minutes.parse().ok()?;
+        return Some((chrono::Utc::now() - chrono::Duration::minutes(minutes)).timestamp());
+    }
+    if let Some(hours) = s.strip_suffix('h') {
+        let hours: i64 = hours.parse().ok()?;
+        return Some((chrono::Utc::now() - chrono::Duration::hours(hours)).timestamp());
+    }
+    chrono::DateTime::parse_from_rfc3339(s).ok().map(|t| t.timestamp())
+}
 
+/// Builds the tar archive `image_build` hands to the daemon as its build
+/// context: either the caller's own `context_tar` (base64-decoded as-is),
+/// or a single-entry archive wrapping an inline `dockerfile` string, the
+/// same `Dockerfile`-at-the-root layout `docker build -` expects. Shared by
+/// both backends so the two accept exactly the same request shape.
+pub(crate) fn build_context_tar(args: &Value) -> Result<Vec<u8>, McpError> {
+    if let Some(context_tar) = args.get("context_tar").and_then(|v| v.as_str()) {
+        return base64::engine::general_purpose::STANDARD
+            .decode(context_tar)
+            .map_err(|e| McpError::InvalidParams(format!("Invalid base64 build context: {}", e)));
+    }
+
+    let dockerfile = args
+        .get("dockerfile")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| McpError::InvalidParams("Must provide either dockerfile or context_tar".to_string()))?;
+
+    let mut builder = tar::Builder::new(Vec::new());
+    let mut header = tar::Header::new_gnu();
+    header.set_path("Dockerfile").map_err(|e| McpError::InternalError(format!("Failed to build tar context: {}", e)))?;
+    header.set_size(dockerfile.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    builder
+        .append(&header, dockerfile.as_bytes())
+        .map_err(|e| McpError::InternalError(format!("Failed to build tar context: {}", e)))?;
+    builder
+        .into_inner()
+        .map_err(|e| McpError::InternalError(format!("Failed to build tar context: {}", e)))
+}
 
 pub trait DockerClient {
     // Container operations
@@ -17,576 +86,41 @@ pub trait DockerClient {
     async fn container_start(&self, args: Value) -> Result<CallToolResult, McpError>;
     async fn container_stop(&self, args: Value) -> Result<CallToolResult, McpError>;
     async fn container_logs(&self, args: Value) -> Result<CallToolResult, McpError>;
-    
+
     // Image operations
     async fn list_images(&self, args: Value) -> Result<CallToolResult, McpError>;
-    
+    async fn image_build(&self, args: Value) -> Result<CallToolResult, McpError>;
+
+    // Event operations
+    async fn docker_events(&self, args: Value) -> Result<CallToolResult, McpError>;
+
+    // Stats operations
+    async fn container_stats(&self, args: Value) -> Result<CallToolResult, McpError>;
+
+    // Archive operations
+    async fn container_copy_in(&self, args: Value) -> Result<CallToolResult, McpError>;
+    async fn container_copy_out(&self, args: Value) -> Result<CallToolResult, McpError>;
+
     // Compose operations
     async fn compose_up(&self, args: Value) -> Result<CallToolResult, McpError>;
     async fn compose_down(&self, args: Value) -> Result<CallToolResult, McpError>;
     async fn validate_compose(&self, args: Value) -> Result<CallToolResult, McpError>;
-    
+
+    // Volume operations
+    async fn list_volumes(&self, args: Value) -> Result<CallToolResult, McpError>;
+    async fn create_volume(&self, args: Value) -> Result<CallToolResult, McpError>;
+    async fn remove_volume(&self, args: Value) -> Result<CallToolResult, McpError>;
+    async fn prune_volumes(&self, args: Value) -> Result<CallToolResult, McpError>;
+
     // Resource operations
     async fn get_docker_info(&self) -> Result<String, McpError>;
     async fn get_docker_version(&self) -> Result<String, McpError>;
     async fn get_container_details(&self, container_id: &str) -> Result<String, McpError>;
     async fn get_image_details(&self, image_id: &str) -> Result<String, McpError>;
+    async fn get_volume_details(&self, volume_name: &str) -> Result<String, McpError>;
     async fn get_compose_status(&self, project_directory: &str) -> Result<String, McpError>;
+    /// Names of every Docker network visible to this connection, for
+    /// `config::validate` to check `NetworkSettings::allowed_networks`
+    /// against what actually exists.
+    async fn list_network_names(&self) -> Result<Vec<String>, McpError>;
 }
-
-pub struct DockerClientImpl {
-    client: Docker,
-    settings: DockerSettings,
-}
-
-impl DockerClientImpl {
-    // Add getter for compose path
-    pub fn get_compose_path(&self) -> &std::path::Path {
-        &self.settings.compose_path
-    }
-    // Enhance the Docker client connection handling
-    pub fn new(settings: &DockerSettings) -> Result<Self, McpError> {
-        let client = match settings.host.as_str() {
-            host if host.starts_with("unix://") => {
-                match Docker::connect_with_unix_defaults() {
-                    Ok(client) => client,
-                    Err(e) => return Err(McpError::DockerError(format!(
-                        "Failed to connect to Docker daemon at {}: {}", host, e
-                    ))),
-                }
-            }
-            host if host.starts_with("npipe://") => {
-                match Docker::connect_with_local_defaults() {
-                    Ok(client) => client,
-                    Err(e) => return Err(McpError::DockerError(format!(
-                        "Failed to connect to Docker daemon at {}: {}", host, e
-                    ))),
-                }
-            }
-            host => {
-                match Docker::connect_with_http_defaults() {
-                    Ok(client) => client,
-                    Err(e) => return Err(McpError::DockerError(format!(
-                        "Failed to connect to Docker daemon at {}: {}", host, e
-                    ))),
-                }
-            },
-        };
-    
-        Ok(Self {
-            client,
-            settings: settings.clone(),
-        })
-    }
-
-    fn is_read_only_operation(&self, operation: &str) -> bool {
-        match operation {
-            "list_containers" | "container_logs" | "list_images" |
-            "get_docker_info" | "get_docker_version" | "get_container_details" |
-            "get_image_details" | "get_compose_status" | "validate_compose" => true,
-            _ => false,
-        }
-    }
-
-    fn check_read_only(&self, operation: &str) -> Result<(), McpError> {
-        if self.settings.read_only && !self.is_read_only_operation(operation) {
-            return Err(McpError::OperationNotPermitted(
-                "Server is in read-only mode".to_string(),
-            ));
-        }
-        Ok(())
-    }
-}
-
-// Improve Docker operation with timeouts
-impl DockerClient for DockerClientImpl {
-    async fn list_containers(&self, args: Value) -> Result<CallToolResult, McpError> {
-        self.check_read_only("list_containers")?;
-
-        let all = args.get("all").and_then(|v| v.as_bool()).unwrap_or(false);
-        let limit = args.get("limit").and_then(|v| v.as_u64()).unwrap_or(0);
-        let filter = args.get("filter").and_then(|v| v.as_str());
-
-        let mut options = ListContainersOptions::<String>::default();
-        options.all = all;
-        if limit > 0 {
-            options.limit = Some(limit as isize);
-        }
-        
-        if let Some(filter_str) = filter {
-            let mut filters = HashMap::new();
-            // Parse filter string like "status=running"
-            let parts: Vec<&str> = filter_str.split('=').collect();
-            if parts.len() == 2 {
-                filters.insert(parts[0].to_string(), vec![parts[1].to_string()]);
-                options.filters = filters;
-            }
-        }
-
-        // Add timeout to Docker API call
-        match tokio::time::timeout(
-            self.settings.operation_timeout,
-            self.client.list_containers(Some(options))
-        ).await {
-            Ok(result) => {
-                match result {
-                    Ok(containers) => {
-                        let json_result = serde_json::to_string_pretty(&containers)?;
-                        
-                        Ok(CallToolResult {
-                            content: vec![Content::Text(TextContent {
-                                r#type: "text".to_string(),
-                                text: json_result,
-                            })],
-                            is_error: false,
-                        })
-                    },
-                    Err(e) => Err(McpError::DockerError(format!("Failed to list containers: {}", e))),
-                }
-            },
-            Err(_) => Err(McpError::OperationTimeout),
-        }
-    }
-
-    async fn container_start(&self, args: Value) -> Result<CallToolResult, McpError> {
-        self.check_read_only("container_start")?;
-
-        let container_id = args
-            .get("container_id")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| McpError::InvalidParams("Missing container_id parameter".to_string()))?;
-
-        let options = StartContainerOptions::<String>::default();
-        self.client.start_container(container_id, Some(options)).await?;
-
-        Ok(CallToolResult {
-            content: vec![Content::Text(TextContent {
-                r#type: "text".to_string(),
-                text: format!("Container {} started successfully", container_id),
-            })],
-            is_error: false,
-        })
-    }
-
-    async fn container_stop(&self, args: Value) -> Result<CallToolResult, McpError> {
-        self.check_read_only("container_stop")?;
-
-        let container_id = args
-            .get("container_id")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| McpError::InvalidParams("Missing container_id parameter".to_string()))?;
-
-        let timeout = args.get("timeout").and_then(|v| v.as_u64()).unwrap_or(10);
-        
-        let options = StopContainerOptions {
-            t: timeout as i64,
-        };
-
-        self.client.stop_container(container_id, Some(options)).await?;
-
-        Ok(CallToolResult {
-            content: vec![Content::Text(TextContent {
-                r#type: "text".to_string(),
-                text: format!("Container {} stopped successfully", container_id),
-            })],
-            is_error: false,
-        })
-    }
-
-    async fn container_logs(&self, args: Value) -> Result<CallToolResult, McpError> {
-        self.check_read_only("container_logs")?;
-
-        let container_id = args
-            .get("container_id")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| McpError::InvalidParams("Missing container_id parameter".to_string()))?;
-
-        let tail = args.get("tail").and_then(|v| v.as_str()).unwrap_or("all");
-        let since = args.get("since").and_then(|v| v.as_str());
-
-        let mut options = LogsOptions::<String> {
-            stdout: true,
-            stderr: true,
-            ..Default::default()
-        };
-
-        if tail != "all" {
-            options.tail = tail.to_string();
-        }
-
-        if let Some(since_str) = since {
-            // Handle relative time (e.g., "42m" for 42 minutes)
-            if since_str.ends_with('m') {
-                if let Ok(minutes) = since_str.trim_end_matches('m').parse::<i64>() {
-                    let since_timestamp = chrono::Utc::now() - chrono::Duration::minutes(minutes);
-                    options.since = since_timestamp.timestamp();
-                }
-            } else if since_str.ends_with('h') {
-                if let Ok(hours) = since_str.trim_end_matches('h').parse::<i64>() {
-                    let since_timestamp = chrono::Utc::now() - chrono::Duration::hours(hours);
-                    options.since = since_timestamp.timestamp();
-                }
-            } else if let Ok(timestamp) = chrono::DateTime::parse_from_rfc3339(since_str) {
-                options.since = timestamp.timestamp();
-            }
-        }
-
-        let max_log_size = self.settings.max_log_size;
-        
-        // Use timeout for logs collection
-        match tokio::time::timeout(
-            self.settings.operation_timeout,
-            self.client.logs(container_id, Some(options)).try_collect::<Vec<_>>()
-        ).await {
-            Ok(result) => {
-                match result {
-                    Ok(logs) => {
-                        let mut log_text = String::new();
-                        for log in logs {
-                            match log {
-                                bollard::container::LogOutput::StdOut { message } => {
-                                    if let Ok(text) = String::from_utf8(message.to_vec()) {
-                                        log_text.push_str(&format!("[STDOUT] {}\n", text));
-                                    }
-                                }
-                                bollard::container::LogOutput::StdErr { message } => {
-                                    if let Ok(text) = String::from_utf8(message.to_vec()) {
-                                        log_text.push_str(&format!("[STDERR] {}\n", text));
-                                    }
-                                }
-                                _ => {}
-                            }
-                            
-                            // Check if we've exceeded the maximum log size
-                            if log_text.len() > max_log_size {
-                                log_text.truncate(max_log_size);
-                                log_text.push_str("\n... (log truncated due to size limit)");
-                                break;
-                            }
-                        }
-
-                        Ok(CallToolResult {
-                            content: vec![Content::Text(TextContent {
-                                r#type: "text".to_string(),
-                                text: log_text,
-                            })],
-                            is_error: false,
-                        })
-                    },
-                    Err(e) => Err(McpError::DockerError(format!("Failed to get container logs: {}", e))),
-                }
-            },
-            Err(_) => Err(McpError::OperationTimeout),
-        }
-    }
-
-    async fn list_images(&self, args: Value) -> Result<CallToolResult, McpError> {
-        self.check_read_only("list_images")?;
-
-        let all = args.get("all").and_then(|v| v.as_bool()).unwrap_or(false);
-        let filter = args.get("filter").and_then(|v| v.as_str());
-
-        let mut options = ListImagesOptions::<String>::default();
-        options.all = all;
-        
-        if let Some(filter_str) = filter {
-            let mut filters = HashMap::new();
-            // Parse filter string like "reference=alpine"
-            let parts: Vec<&str> = filter_str.split('=').collect();
-            if parts.len() == 2 {
-                filters.insert(parts[0].to_string(), vec![parts[1].to_string()]);
-                options.filters = filters;
-            }
-        }
-
-        let images = self.client.list_images(Some(options)).await?;
-        
-        let json_result = serde_json::to_string_pretty(&images)?;
-        
-        Ok(CallToolResult {
-            content: vec![Content::Text(TextContent {
-                r#type: "text".to_string(),
-                text: json_result,
-            })],
-            is_error: false,
-        })
-    }
-
-    async fn compose_up(&self, args: Value) -> Result<CallToolResult, McpError> {
-        self.check_read_only("compose_up")?;
-
-        let project_directory = args
-            .get("project_directory")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| McpError::InvalidParams("Missing project_directory parameter".to_string()))?;
-
-        // Security check for project directory
-        if let Some(allowed_projects) = &self.settings.allowed_compose_projects {
-            if !allowed_projects.contains(project_directory) {
-                return Err(McpError::OperationNotPermitted(format!(
-                    "Project directory '{}' is not in the allowed list",
-                    project_directory
-                )));
-            }
-        }
-
-        let detach = args.get("detach").and_then(|v| v.as_bool()).unwrap_or(true);
-        let services: Vec<String> = args
-            .get("services")
-            .and_then(|v| v.as_array())
-            .map(|arr| {
-                arr.iter()
-                    .filter_map(|s| s.as_str().map(String::from))
-                    .collect()
-            })
-            .unwrap_or_default();
-
-        let mut command = Command::new(&self.settings.compose_path);
-        command.current_dir(project_directory);
-        command.arg("up");
-        
-        if detach {
-            command.arg("-d");
-        }
-        
-        for service in services {
-            command.arg(&service);
-        }
-
-        let output = tokio::process::Command::from(command)
-            .output()
-            .await
-            .map_err(|e| McpError::DockerError(format!("Failed to execute docker-compose: {}", e)))?;
-
-        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-
-        let mut result = String::new();
-        if !stdout.is_empty() {
-            result.push_str(&format!("STDOUT:\n{}", stdout));
-        }
-        if !stderr.is_empty() {
-            if !result.is_empty() {
-                result.push_str("\n");
-            }
-            result.push_str(&format!("STDERR:\n{}", stderr));
-        }
-
-        if output.status.success() {
-            Ok(CallToolResult {
-                content: vec![Content::Text(TextContent {
-                    r#type: "text".to_string(),
-                    text: format!("Docker Compose up successful for {}:\n{}", project_directory, result),
-                })],
-                is_error: false,
-            })
-        } else {
-            Ok(CallToolResult {
-                content: vec![Content::Text(TextContent {
-                    r#type: "text".to_string(),
-                    text: format!("Docker Compose up failed for {}:\n{}", project_directory, result),
-                })],
-                is_error: true,
-            })
-        }
-    }
-
-    async fn compose_down(&self, args: Value) -> Result<CallToolResult, McpError> {
-        self.check_read_only("compose_down")?;
-
-        let project_directory = args
-            .get("project_directory")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| McpError::InvalidParams("Missing project_directory parameter".to_string()))?;
-
-        // Security check for project directory
-        if let Some(allowed_projects) = &self.settings.allowed_compose_projects {
-            if !allowed_projects.contains(project_directory) {
-                return Err(McpError::OperationNotPermitted(format!(
-                    "Project directory '{}' is not in the allowed list",
-                    project_directory
-                )));
-            }
-        }
-
-        let volumes = args.get("volumes").and_then(|v| v.as_bool()).unwrap_or(false);
-        let remove_images = args.get("remove_images").and_then(|v| v.as_str());
-
-        let mut command = Command::new(&self.settings.compose_path);
-        command.current_dir(project_directory);
-        command.arg("down");
-        
-        if volumes {
-            command.arg("-v");
-        }
-        
-        if let Some(images) = remove_images {
-            match images {
-                "all" => {
-                    command.arg("--rmi").arg("all");
-                }
-                "local" => {
-                    command.arg("--rmi").arg("local");
-                }
-                _ => {}
-            }
-        }
-
-        let output = tokio::process::Command::from(command)
-            .output()
-            .await
-            .map_err(|e| McpError::DockerError(format!("Failed to execute docker-compose: {}", e)))?;
-
-        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-
-        let mut result = String::new();
-        if !stdout.is_empty() {
-            result.push_str(&format!("STDOUT:\n{}", stdout));
-        }
-        if !stderr.is_empty() {
-            if !result.is_empty() {
-                result.push_str("\n");
-            }
-            result.push_str(&format!("STDERR:\n{}", stderr));
-        }
-
-        if output.status.success() {
-            Ok(CallToolResult {
-                content: vec![Content::Text(TextContent {
-                    r#type: "text".to_string(),
-                    text: format!("Docker Compose down successful for {}:\n{}", project_directory, result),
-                })],
-                is_error: false,
-            })
-        } else {
-            Ok(CallToolResult {
-                content: vec![Content::Text(TextContent {
-                    r#type: "text".to_string(),
-                    text: format!("Docker Compose down failed for {}:\n{}", project_directory, result),
-                })],
-                is_error: true,
-            })
-        }
-    }
-
-    async fn validate_compose(&self, args: Value) -> Result<CallToolResult, McpError> {
-        self.check_read_only("validate_compose")?;
-
-        let compose_content = args
-            .get("compose_content")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| McpError::InvalidParams("Missing compose_content parameter".to_string()))?;
-
-        // Create a temporary file with the compose content
-        let temp_dir = tempfile::tempdir()
-            .map_err(|e| McpError::InternalError(format!("Failed to create temporary directory: {}", e)))?;
-        
-        let temp_file_path = temp_dir.path().join("docker-compose.yml");
-        
-        tokio::fs::write(&temp_file_path, compose_content)
-            .await
-            .map_err(|e| McpError::InternalError(format!("Failed to write temporary file: {}", e)))?;
-
-        let mut command = Command::new(&self.settings.compose_path);
-        command.current_dir(temp_dir.path());
-        command.arg("config");
-
-        let output = tokio::process::Command::from(command)
-            .output()
-            .await
-            .map_err(|e| McpError::DockerError(format!("Failed to execute docker-compose: {}", e)))?;
-
-        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-
-        let mut result = String::new();
-        if !stdout.is_empty() {
-            result.push_str(&format!("STDOUT:\n{}", stdout));
-        }
-        if !stderr.is_empty() {
-            if !result.is_empty() {
-                result.push_str("\n");
-            }
-            result.push_str(&format!("STDERR:\n{}", stderr));
-        }
-
-        if output.status.success() {
-            Ok(CallToolResult {
-                content: vec![Content::Text(TextContent {
-                    r#type: "text".to_string(),
-                    text: format!("Docker Compose configuration is valid.\n{}", result),
-                })],
-                is_error: false,
-            })
-        } else {
-            Ok(CallToolResult {
-                content: vec![Content::Text(TextContent {
-                    r#type: "text".to_string(),
-                    text: format!("Docker Compose configuration is invalid.\n{}", result),
-                })],
-                is_error: true,
-            })
-        }
-    }
-
-    async fn get_docker_info(&self) -> Result<String, McpError> {
-        self.check_read_only("get_docker_info")?;
-
-        let info = self.client.info().await?;
-        Ok(serde_json::to_string_pretty(&info)?)
-    }
-
-    async fn get_docker_version(&self) -> Result<String, McpError> {
-        self.check_read_only("get_docker_version")?;
-
-        let version = self.client.version().await?;
-        Ok(serde_json::to_string_pretty(&version)?)
-    }
-
-    async fn get_container_details(&self, container_id: &str) -> Result<String, McpError> {
-        self.check_read_only("get_container_details")?;
-
-        let details = self.client.inspect_container(container_id, None).await?;
-        Ok(serde_json::to_string_pretty(&details)?)
-    }
-
-    async fn get_image_details(&self, image_id: &str) -> Result<String, McpError> {
-        self.check_read_only("get_image_details")?;
-
-        let details = self.client.inspect_image(image_id).await?;
-        Ok(serde_json::to_string_pretty(&details)?)
-    }
-
-    async fn get_compose_status(&self, project_directory: &str) -> Result<String, McpError> {
-        self.check_read_only("get_compose_status")?;
-
-        // Security check for project directory
-        if let Some(allowed_projects) = &self.settings.allowed_compose_projects {
-            if !allowed_projects.contains(project_directory) {
-                return Err(McpError::OperationNotPermitted(format!(
-                    "Project directory '{}' is not in the allowed list",
-                    project_directory
-                )));
-            }
-        }
-
-        let mut command = Command::new(&self.settings.compose_path);
-        command.current_dir(project_directory);
-        command.arg("ps");
-        command.arg("--format").arg("json");
-
-        let output = tokio::process::Command::from(command)
-            .output()
-            .await
-            .map_err(|e| McpError::DockerError(format!("Failed to execute docker-compose: {}", e)))?;
-
-        if output.status.success() {
-            let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-            Ok(stdout)
-        } else {
-            let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-            Err(McpError::DockerError(format!("Failed to get compose status: {}", stderr)))
-        }
-    }
-}
\ No newline at end of file
@@ -0,0 +1,63 @@
+//! Host↔container path translation built from a container's mount table —
+//! the same longest-prefix-match technique `unitctl` uses so tools can be
+//! handed whichever side of a mount is convenient and still reach the
+//! right file. A container path under a mount's `destination` maps to that
+//! mount's host `source` (and vice versa); nested/overlapping mounts are
+//! resolved by picking the longest matching prefix, same as the kernel
+//! does when choosing which mount a path resolves through.
+
+use super::types::MountInfo;
+
+/// Outcome of translating a path through a container's mount table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResolvedPath {
+    /// `path` fell under a mount and was rewritten to the other side.
+    Translated { path: String, read_only: bool },
+    /// No mount covers `path`; it's only meaningful on the side it was
+    /// given on (e.g. a container path with no bind/volume backing it).
+    ContainerOnly(String),
+}
+
+/// Rewrites a container-internal path to its host equivalent, matching the
+/// mount with the longest `destination` prefix.
+pub fn to_host(mounts: &[MountInfo], container_path: &str) -> ResolvedPath {
+    resolve(mounts, container_path, |m| &m.destination, |m| &m.source)
+}
+
+/// Rewrites a host path to its container-internal equivalent, matching the
+/// mount with the longest `source` prefix.
+pub fn to_container(mounts: &[MountInfo], host_path: &str) -> ResolvedPath {
+    resolve(mounts, host_path, |m| &m.source, |m| &m.destination)
+}
+
+fn resolve<'a>(
+    mounts: &'a [MountInfo],
+    path: &str,
+    from: impl Fn(&'a MountInfo) -> &'a String,
+    to: impl Fn(&'a MountInfo) -> &'a String,
+) -> ResolvedPath {
+    mounts
+        .iter()
+        .filter(|m| is_under(path, from(m)))
+        .max_by_key(|m| from(m).len())
+        .map(|m| ResolvedPath::Translated {
+            path: rebase(path, from(m), to(m)),
+            read_only: m.read_only,
+        })
+        .unwrap_or_else(|| ResolvedPath::ContainerOnly(path.to_string()))
+}
+
+fn is_under(path: &str, prefix: &str) -> bool {
+    let prefix = prefix.trim_end_matches('/');
+    if prefix.is_empty() {
+        return true;
+    }
+    path == prefix || path.starts_with(&format!("{}/", prefix))
+}
+
+fn rebase(path: &str, from_prefix: &str, to_prefix: &str) -> String {
+    let from_prefix = from_prefix.trim_end_matches('/');
+    let to_prefix = to_prefix.trim_end_matches('/');
+    let suffix = path.strip_prefix(from_prefix).unwrap_or("");
+    format!("{}{}", to_prefix, suffix)
+}
@@ -0,0 +1,36 @@
+//! The read-only allow-list, shared by both the `socket` and `cli` backends
+//! so enabling `docker.read_only` has the same effect regardless of which
+//! one `DockerBackend` selects at runtime.
+
+use crate::config::types::DockerSettings;
+use crate::protocol::error::McpError;
+
+fn is_read_only_operation(operation: &str) -> bool {
+    matches!(
+        operation,
+        "list_containers"
+            | "container_logs"
+            | "list_images"
+            | "docker_events"
+            | "container_stats"
+            | "container_copy_out"
+            | "get_docker_info"
+            | "get_docker_version"
+            | "get_container_details"
+            | "get_image_details"
+            | "get_volume_details"
+            | "get_compose_status"
+            | "validate_compose"
+            | "list_network_names"
+            | "list_volumes"
+    )
+}
+
+pub(crate) fn check_read_only(settings: &DockerSettings, operation: &str) -> Result<(), McpError> {
+    if settings.read_only && !is_read_only_operation(operation) {
+        return Err(McpError::OperationNotPermitted(
+            "Server is in read-only mode".to_string(),
+        ));
+    }
+    Ok(())
+}
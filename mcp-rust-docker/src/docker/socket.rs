@@ -0,0 +1,902 @@
+//! Docker Engine API client backend: talks directly to the daemon over
+//! `/var/run/docker.sock` (or a configured TCP/npipe endpoint) via bollard
+//! instead of shelling out to the `docker` CLI. Selected at runtime by
+//! `DockerBackend` when `DockerSettings::backend` is `api` (the default);
+//! see `backend.rs` for the CLI fallback.
+
+use base64::Engine;
+use bollard::container::{
+    DownloadFromContainerOptions, ListContainersOptions, LogsOptions, StartContainerOptions, StopContainerOptions,
+    UploadToContainerOptions,
+};
+use bollard::image::{BuildImageOptions, ListImagesOptions};
+use bollard::system::EventsOptions;
+use bollard::volume::{CreateVolumeOptions, ListVolumesOptions, PruneVolumesOptions, RemoveVolumeOptions};
+use bollard::Docker;
+use futures::stream::{BoxStream, StreamExt};
+use serde_json::Value;
+use std::collections::HashMap;
+
+use crate::config::types::DockerSettings;
+use crate::protocol::error::McpError;
+use crate::protocol::types::{CallToolResult, Content, TextContent};
+use futures::stream::TryStreamExt;
+
+use super::compose;
+use super::exec;
+use super::readonly::check_read_only;
+use super::types::{
+    classify_container_state, compute_cpu_percent, ContainerStateSummary, ContainerStatsSample, ExecHandle, LogChunk, LogStream,
+    MountInfo, VolumeUsageSummary,
+};
+use super::DockerClient;
+
+pub struct DockerClientImpl {
+    client: Docker,
+    settings: DockerSettings,
+}
+
+impl DockerClientImpl {
+    pub fn get_compose_path(&self) -> &std::path::Path {
+        &self.settings.compose_path
+    }
+
+    pub fn new(settings: &DockerSettings) -> Result<Self, McpError> {
+        let client = match settings.host.as_str() {
+            host if host.starts_with("unix://") => match Docker::connect_with_unix(host, 120, bollard::API_DEFAULT_VERSION) {
+                Ok(client) => client,
+                Err(e) => {
+                    return Err(McpError::DockerError(format!(
+                        "Failed to connect to Docker daemon at {}: {}",
+                        host, e
+                    )))
+                }
+            },
+            host if host.starts_with("npipe://") => match Docker::connect_with_local_defaults() {
+                Ok(client) => client,
+                Err(e) => {
+                    return Err(McpError::DockerError(format!(
+                        "Failed to connect to Docker daemon at {}: {}",
+                        host, e
+                    )))
+                }
+            },
+            host => {
+                let connected = match &settings.tls {
+                    Some(tls) => Docker::connect_with_ssl(
+                        host,
+                        &tls.key_path,
+                        &tls.cert_path,
+                        &tls.ca_path,
+                        120,
+                        bollard::API_DEFAULT_VERSION,
+                    ),
+                    None => Docker::connect_with_http(host, 120, bollard::API_DEFAULT_VERSION),
+                };
+
+                match connected {
+                    Ok(client) => client,
+                    Err(e) => {
+                        return Err(McpError::DockerError(format!(
+                            "Failed to connect to Docker daemon at {}: {}",
+                            host, e
+                        )))
+                    }
+                }
+            }
+        };
+
+        Ok(Self {
+            client,
+            settings: settings.clone(),
+        })
+    }
+
+    /// Opens a live (`follow = true`) log stream for a container, for
+    /// callers that want to push lines out incrementally (e.g. as JSON-RPC
+    /// notifications) instead of collecting one capped blob like
+    /// `container_logs` does.
+    pub fn follow_logs(&self, container_id: &str) -> BoxStream<'_, Result<LogChunk, McpError>> {
+        let options = LogsOptions::<String> {
+            stdout: true,
+            stderr: true,
+            follow: true,
+            tail: "0".to_string(),
+            ..Default::default()
+        };
+
+        Box::pin(self.client.logs(container_id, Some(options)).filter_map(|chunk| async move {
+            match chunk {
+                Ok(bollard::container::LogOutput::StdOut { message }) => Some(Ok(LogChunk {
+                    stream: LogStream::Stdout,
+                    text: String::from_utf8_lossy(&message).to_string(),
+                })),
+                Ok(bollard::container::LogOutput::StdErr { message }) => Some(Ok(LogChunk {
+                    stream: LogStream::Stderr,
+                    text: String::from_utf8_lossy(&message).to_string(),
+                })),
+                Ok(_) => None,
+                Err(e) => Some(Err(McpError::from(e))),
+            }
+        }))
+    }
+
+    /// Opens the daemon's event stream, optionally scoped by the same kind
+    /// of filter map `list_containers`/`list_images` already accept.
+    pub fn stream_events(
+        &self,
+        since: Option<i64>,
+        until: Option<i64>,
+        filters: HashMap<String, Vec<String>>,
+    ) -> BoxStream<'_, Result<Value, McpError>> {
+        // EventsOptions takes `since`/`until` as the string form the Docker
+        // API itself accepts (a unix timestamp, same as the CLI's
+        // --since/--until), not the `i64` this method takes from callers.
+        let options = EventsOptions {
+            since: since.map(|s| s.to_string()),
+            until: until.map(|u| u.to_string()),
+            filters,
+        };
+
+        Box::pin(self.client.events(Some(options)).map(|event| {
+            event
+                .map_err(McpError::from)
+                .map(|event| serde_json::to_value(event).unwrap_or(Value::Null))
+        }))
+    }
+
+    /// Starts an interactive command in `container_id` and hands back a
+    /// handle the caller can use to feed stdin and drain demultiplexed
+    /// stdout/stderr chunks as they arrive. `Docker` is cheap to clone (it's
+    /// just a handle to the connection), so this doesn't need `&self` to
+    /// outlive the returned stream.
+    pub async fn start_exec(
+        &self,
+        container_id: &str,
+        cmd: Vec<String>,
+        working_dir: Option<String>,
+        env: Option<Vec<String>>,
+        tty: bool,
+    ) -> Result<(String, ExecHandle), McpError> {
+        exec::start_exec(self.client.clone(), container_id, cmd, working_dir, env, tty).await
+    }
+
+    pub async fn exec_exit_code(&self, exec_id: &str) -> Result<Option<i64>, McpError> {
+        exec::inspect_exec_exit_code(&self.client, exec_id).await
+    }
+
+    /// One resource-usage sample for `container_id`. The Engine API's
+    /// stats response already carries both the current and previous CPU
+    /// counters (`cpu_stats`/`precpu_stats`), so a single non-streaming
+    /// call is enough to compute a CPU percentage — no need to keep our
+    /// own previous-sample state between polls.
+    pub async fn get_container_stats(&self, container_id: &str) -> Result<ContainerStatsSample, McpError> {
+        use bollard::container::StatsOptions;
+
+        let mut stream = self.client.stats(
+            container_id,
+            Some(StatsOptions { stream: false, one_shot: false }),
+        );
+
+        let stats = stream
+            .next()
+            .await
+            .ok_or_else(|| McpError::DockerError(format!("No stats returned for container {}", container_id)))?
+            .map_err(McpError::from)?;
+
+        let per_cpu_usage = stats.cpu_stats.cpu_usage.percpu_usage.clone().unwrap_or_default();
+        let online_cpus = stats
+            .cpu_stats
+            .online_cpus
+            .unwrap_or_else(|| per_cpu_usage.len().max(1) as u64);
+
+        let cpu_percent = compute_cpu_percent(
+            stats.cpu_stats.cpu_usage.total_usage,
+            stats.precpu_stats.cpu_usage.total_usage,
+            stats.cpu_stats.system_cpu_usage.unwrap_or(0),
+            stats.precpu_stats.system_cpu_usage.unwrap_or(0),
+            online_cpus,
+        );
+
+        let blkio = stats.blkio_stats.io_service_bytes_recursive.unwrap_or_default();
+        let blk_read = blkio.iter().filter(|e| e.op.eq_ignore_ascii_case("read")).map(|e| e.value).sum();
+        let blk_write = blkio.iter().filter(|e| e.op.eq_ignore_ascii_case("write")).map(|e| e.value).sum();
+
+        let networks = stats.networks.unwrap_or_default();
+        let network_rx = networks.values().map(|n| n.rx_bytes).sum();
+        let network_tx = networks.values().map(|n| n.tx_bytes).sum();
+
+        Ok(ContainerStatsSample {
+            cpu_percent,
+            cpu_total_usage: stats.cpu_stats.cpu_usage.total_usage,
+            per_cpu_usage,
+            memory_usage: stats.memory_stats.usage.unwrap_or(0),
+            memory_limit: stats.memory_stats.limit.unwrap_or(0),
+            memory_cache: stats.memory_stats.stats.map(memory_cache_from_stats).unwrap_or(0),
+            pids_current: stats.pids_stats.current.unwrap_or(0),
+            pids_limit: stats.pids_stats.limit,
+            blk_read,
+            blk_write,
+            network_rx,
+            network_tx,
+        })
+    }
+
+    /// The container's bind mounts and volumes, for [`super::paths`]'s
+    /// host↔container path translation.
+    pub async fn get_container_mounts(&self, container_id: &str) -> Result<Vec<MountInfo>, McpError> {
+        let details = self.client.inspect_container(container_id, None).await?;
+        Ok(details
+            .mounts
+            .unwrap_or_default()
+            .into_iter()
+            .map(|m| MountInfo {
+                source: m.source.unwrap_or_default(),
+                destination: m.destination.unwrap_or_default(),
+                read_only: !m.rw.unwrap_or(true),
+            })
+            .collect())
+    }
+
+    /// `ContainerInspectResponse::state`, folded into a
+    /// [`super::types::ContainerState`] for `wait_for_container`'s polling
+    /// loop.
+    pub async fn inspect_state(&self, container_id: &str) -> Result<ContainerStateSummary, McpError> {
+        let details = self.client.inspect_container(container_id, None).await?;
+        let state = details
+            .state
+            .ok_or_else(|| McpError::DockerError(format!("No state reported for container {}", container_id)))?;
+
+        let status = state.status.map(|s| s.to_string()).unwrap_or_default();
+        let health_status = state.health.and_then(|h| h.status).map(|s| s.to_string());
+        let exit_code = state.exit_code.unwrap_or(0);
+        let restarting = state.restarting.unwrap_or(false);
+
+        Ok(ContainerStateSummary {
+            state: classify_container_state(&status, health_status.as_deref(), exit_code),
+            health_status,
+            restarting,
+        })
+    }
+
+    /// Resizes a PTY-allocated exec session. Only the `api` backend can do
+    /// this — the Engine API exposes `resize_exec` directly, while the
+    /// `cli` backend has no equivalent `docker exec` subcommand.
+    pub async fn resize_exec(&self, exec_id: &str, rows: u16, cols: u16) -> Result<(), McpError> {
+        exec::resize_exec(&self.client, exec_id, rows, cols).await
+    }
+
+    pub(crate) fn check_read_only(&self, operation: &str) -> Result<(), McpError> {
+        check_read_only(&self.settings, operation)
+    }
+
+    pub async fn list_unhealthy_containers(&self, label: &str) -> Result<Vec<String>, McpError> {
+        let mut filters = HashMap::new();
+        filters.insert("label".to_string(), vec![label.to_string()]);
+        filters.insert("health".to_string(), vec!["unhealthy".to_string()]);
+
+        let containers = self
+            .client
+            .list_containers(Some(ListContainersOptions { all: true, filters, ..Default::default() }))
+            .await?;
+
+        Ok(containers.into_iter().filter_map(|c| c.id).collect())
+    }
+
+    pub async fn restart_container(&self, container_id: &str) -> Result<(), McpError> {
+        self.client.restart_container(container_id, None).await?;
+        Ok(())
+    }
+
+    /// Sums `UsageData.size` across every volume carrying
+    /// `VOLUME_OWNER_LABEL`, for `run_diagnostic`'s owned-volume report.
+    /// The daemon only computes `UsageData` when asked, so a volume
+    /// without it contributes `0` rather than making the whole total
+    /// `None` - only an empty owned set does that.
+    pub async fn owned_volumes_usage(&self) -> Result<VolumeUsageSummary, McpError> {
+        let mut options = ListVolumesOptions::<String>::default();
+        options.filters.insert("label".to_string(), vec![format!("{}={}", super::VOLUME_OWNER_LABEL, super::VOLUME_OWNER_VALUE)]);
+
+        let response = self.client.list_volumes(Some(options)).await?;
+        let volumes = response.volumes.unwrap_or_default();
+
+        if volumes.is_empty() {
+            return Ok(VolumeUsageSummary { owned_count: 0, total_reclaimable_bytes: None });
+        }
+
+        let total = volumes.iter().filter_map(|v| v.usage_data.as_ref()).map(|u| u.size).sum();
+
+        Ok(VolumeUsageSummary { owned_count: volumes.len(), total_reclaimable_bytes: Some(total) })
+    }
+}
+
+/// Page cache usage out of a container's `memory_stats.stats`, which bollard
+/// models as a cgroup-version-specific enum rather than a flat struct: v1
+/// reports it directly as `cache`, while v2 has no equivalent field and
+/// approximates it with `inactive_file`.
+fn memory_cache_from_stats(stats: bollard::container::MemoryStatsStats) -> u64 {
+    use bollard::container::MemoryStatsStats;
+
+    match stats {
+        MemoryStatsStats::V1(v1) => v1.cache,
+        MemoryStatsStats::V2(v2) => v2.inactive_file,
+    }
+}
+
+impl DockerClient for DockerClientImpl {
+    async fn list_containers(&self, args: Value) -> Result<CallToolResult, McpError> {
+        self.check_read_only("list_containers")?;
+
+        let all = args.get("all").and_then(|v| v.as_bool()).unwrap_or(false);
+        let limit = args.get("limit").and_then(|v| v.as_u64()).unwrap_or(0);
+        let filter = args.get("filter").and_then(|v| v.as_str());
+
+        let mut options = ListContainersOptions::<String>::default();
+        options.all = all;
+        if limit > 0 {
+            options.limit = Some(limit as isize);
+        }
+
+        if let Some(filter_str) = filter {
+            let mut filters = HashMap::new();
+            // Parse filter string like "status=running"
+            let parts: Vec<&str> = filter_str.split('=').collect();
+            if parts.len() == 2 {
+                filters.insert(parts[0].to_string(), vec![parts[1].to_string()]);
+                options.filters = filters;
+            }
+        }
+
+        // Add timeout to Docker API call
+        match tokio::time::timeout(self.settings.operation_timeout, self.client.list_containers(Some(options))).await {
+            Ok(result) => match result {
+                Ok(containers) => {
+                    let json_result = serde_json::to_string_pretty(&containers)?;
+
+                    Ok(CallToolResult {
+                        content: vec![Content::Text(TextContent {
+                            r#type: "text".to_string(),
+                            text: json_result,
+                        })],
+                        is_error: false,
+                    })
+                }
+                Err(e) => Err(McpError::DockerError(format!("Failed to list containers: {}", e))),
+            },
+            Err(_) => Err(McpError::OperationTimeout),
+        }
+    }
+
+    async fn container_start(&self, args: Value) -> Result<CallToolResult, McpError> {
+        self.check_read_only("container_start")?;
+
+        let container_id = args
+            .get("container_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| McpError::InvalidParams("Missing container_id parameter".to_string()))?;
+
+        let options = StartContainerOptions::<String>::default();
+        self.client.start_container(container_id, Some(options)).await?;
+
+        Ok(CallToolResult {
+            content: vec![Content::Text(TextContent {
+                r#type: "text".to_string(),
+                text: format!("Container {} started successfully", container_id),
+            })],
+            is_error: false,
+        })
+    }
+
+    async fn container_stop(&self, args: Value) -> Result<CallToolResult, McpError> {
+        self.check_read_only("container_stop")?;
+
+        let container_id = args
+            .get("container_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| McpError::InvalidParams("Missing container_id parameter".to_string()))?;
+
+        let timeout = args.get("timeout").and_then(|v| v.as_u64()).unwrap_or(10);
+
+        let options = StopContainerOptions { t: timeout as i64 };
+
+        self.client.stop_container(container_id, Some(options)).await?;
+
+        Ok(CallToolResult {
+            content: vec![Content::Text(TextContent {
+                r#type: "text".to_string(),
+                text: format!("Container {} stopped successfully", container_id),
+            })],
+            is_error: false,
+        })
+    }
+
+    async fn container_logs(&self, args: Value) -> Result<CallToolResult, McpError> {
+        self.check_read_only("container_logs")?;
+
+        let container_id = args
+            .get("container_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| McpError::InvalidParams("Missing container_id parameter".to_string()))?;
+
+        let tail = args.get("tail").and_then(|v| v.as_str()).unwrap_or("all");
+        let since = args.get("since").and_then(|v| v.as_str());
+
+        let mut options = LogsOptions::<String> {
+            stdout: true,
+            stderr: true,
+            ..Default::default()
+        };
+
+        if tail != "all" {
+            options.tail = tail.to_string();
+        }
+
+        if let Some(since_str) = since {
+            if let Some(timestamp) = super::parse_time_arg(since_str) {
+                options.since = timestamp;
+            }
+        }
+
+        let max_log_size = self.settings.max_log_size;
+
+        // Use timeout for logs collection
+        match tokio::time::timeout(
+            self.settings.operation_timeout,
+            self.client.logs(container_id, Some(options)).try_collect::<Vec<_>>(),
+        )
+        .await
+        {
+            Ok(result) => match result {
+                Ok(logs) => {
+                    let mut log_text = String::new();
+                    for log in logs {
+                        match log {
+                            bollard::container::LogOutput::StdOut { message } => {
+                                if let Ok(text) = String::from_utf8(message.to_vec()) {
+                                    log_text.push_str(&format!("[STDOUT] {}\n", text));
+                                }
+                            }
+                            bollard::container::LogOutput::StdErr { message } => {
+                                if let Ok(text) = String::from_utf8(message.to_vec()) {
+                                    log_text.push_str(&format!("[STDERR] {}\n", text));
+                                }
+                            }
+                            _ => {}
+                        }
+
+                        // Check if we've exceeded the maximum log size
+                        if log_text.len() > max_log_size {
+                            log_text.truncate(max_log_size);
+                            log_text.push_str("\n... (log truncated due to size limit)");
+                            break;
+                        }
+                    }
+
+                    Ok(CallToolResult {
+                        content: vec![Content::Text(TextContent {
+                            r#type: "text".to_string(),
+                            text: log_text,
+                        })],
+                        is_error: false,
+                    })
+                }
+                Err(e) => Err(McpError::DockerError(format!("Failed to get container logs: {}", e))),
+            },
+            Err(_) => Err(McpError::OperationTimeout),
+        }
+    }
+
+    async fn list_images(&self, args: Value) -> Result<CallToolResult, McpError> {
+        self.check_read_only("list_images")?;
+
+        let all = args.get("all").and_then(|v| v.as_bool()).unwrap_or(false);
+        let filter = args.get("filter").and_then(|v| v.as_str());
+
+        let mut options = ListImagesOptions::<String>::default();
+        options.all = all;
+
+        if let Some(filter_str) = filter {
+            let mut filters = HashMap::new();
+            // Parse filter string like "reference=alpine"
+            let parts: Vec<&str> = filter_str.split('=').collect();
+            if parts.len() == 2 {
+                filters.insert(parts[0].to_string(), vec![parts[1].to_string()]);
+                options.filters = filters;
+            }
+        }
+
+        let images = self.client.list_images(Some(options)).await?;
+
+        let json_result = serde_json::to_string_pretty(&images)?;
+
+        Ok(CallToolResult {
+            content: vec![Content::Text(TextContent {
+                r#type: "text".to_string(),
+                text: json_result,
+            })],
+            is_error: false,
+        })
+    }
+
+    async fn image_build(&self, args: Value) -> Result<CallToolResult, McpError> {
+        self.check_read_only("image_build")?;
+
+        let tag = args
+            .get("tag")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| McpError::InvalidParams("Missing tag parameter".to_string()))?;
+
+        let buildargs: HashMap<String, String> = args
+            .get("build_args")
+            .and_then(|v| v.as_object())
+            .map(|obj| obj.iter().filter_map(|(k, v)| v.as_str().map(|s| (k.clone(), s.to_string()))).collect())
+            .unwrap_or_default();
+
+        let context_tar = super::build_context_tar(&args)?;
+
+        let options = BuildImageOptions { t: tag.to_string(), buildargs, ..Default::default() };
+
+        let run = async {
+            let mut stream = self.client.build_image(options, None, Some(context_tar.into()));
+            let mut log_text = String::new();
+            let mut is_error = false;
+
+            while let Some(chunk) = stream.next().await {
+                match chunk {
+                    Ok(info) => {
+                        if let Some(stream_line) = info.stream {
+                            log_text.push_str(&stream_line);
+                        }
+                        if let Some(status) = info.status {
+                            log_text.push_str(&status);
+                            log_text.push('\n');
+                        }
+                        if let Some(error) = info.error {
+                            log_text.push_str(&error);
+                            log_text.push('\n');
+                            is_error = true;
+                        }
+                    }
+                    Err(e) => {
+                        log_text.push_str(&format!("{}\n", e));
+                        is_error = true;
+                    }
+                }
+            }
+
+            (log_text, is_error)
+        };
+
+        let (mut log_text, is_error) = tokio::time::timeout(self.settings.operation_timeout, run)
+            .await
+            .map_err(|_| McpError::OperationTimeout)?;
+
+        if log_text.len() > self.settings.max_log_size {
+            log_text.truncate(self.settings.max_log_size);
+        }
+
+        Ok(CallToolResult {
+            content: vec![Content::Text(TextContent { r#type: "text".to_string(), text: log_text })],
+            is_error,
+        })
+    }
+
+    async fn docker_events(&self, args: Value) -> Result<CallToolResult, McpError> {
+        self.check_read_only("docker_events")?;
+
+        let since = args.get("since").and_then(|v| v.as_str()).and_then(super::parse_time_arg);
+        let until = args.get("until").and_then(|v| v.as_str()).and_then(super::parse_time_arg);
+
+        let filters: HashMap<String, Vec<String>> = args
+            .get("filters")
+            .and_then(|v| v.as_object())
+            .map(|obj| {
+                obj.iter()
+                    .map(|(k, v)| {
+                        let values = v
+                            .as_array()
+                            .map(|arr| arr.iter().filter_map(|s| s.as_str().map(String::from)).collect())
+                            .unwrap_or_default();
+                        (k.clone(), values)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let events = tokio::time::timeout(
+            self.settings.operation_timeout,
+            self.stream_events(since, until, filters).try_collect::<Vec<_>>(),
+        )
+        .await
+        .map_err(|_| McpError::OperationTimeout)?
+        .map_err(|e| McpError::DockerError(format!("Failed to collect Docker events: {}", e)))?;
+
+        let json_result = serde_json::to_string_pretty(&events)?;
+
+        Ok(CallToolResult {
+            content: vec![Content::Text(TextContent { r#type: "text".to_string(), text: json_result })],
+            is_error: false,
+        })
+    }
+
+    async fn container_stats(&self, args: Value) -> Result<CallToolResult, McpError> {
+        self.check_read_only("container_stats")?;
+
+        let container_id = args
+            .get("container_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| McpError::InvalidParams("Missing container_id parameter".to_string()))?;
+
+        if args.get("stream").and_then(|v| v.as_bool()).unwrap_or(false) {
+            return Err(McpError::InvalidParams(
+                "container_stats only returns a one-shot sample; use docker/stats/subscribe for continuous streaming"
+                    .to_string(),
+            ));
+        }
+
+        let sample = tokio::time::timeout(self.settings.operation_timeout, self.get_container_stats(container_id))
+            .await
+            .map_err(|_| McpError::OperationTimeout)??;
+
+        Ok(CallToolResult {
+            content: vec![Content::Text(TextContent {
+                r#type: "text".to_string(),
+                text: serde_json::to_string_pretty(&super::types::container_stats_to_json(&sample))?,
+            })],
+            is_error: false,
+        })
+    }
+
+    async fn container_copy_in(&self, args: Value) -> Result<CallToolResult, McpError> {
+        self.check_read_only("container_copy_in")?;
+
+        let container_id = args
+            .get("container_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| McpError::InvalidParams("Missing container_id parameter".to_string()))?;
+        let path = args
+            .get("path")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| McpError::InvalidParams("Missing path parameter".to_string()))?;
+        let tar_base64 = args
+            .get("tar_base64")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| McpError::InvalidParams("Missing tar_base64 parameter".to_string()))?;
+
+        let tar_bytes = base64::engine::general_purpose::STANDARD
+            .decode(tar_base64)
+            .map_err(|e| McpError::InvalidParams(format!("Invalid base64 tar payload: {}", e)))?;
+
+        let options = UploadToContainerOptions { path: path.to_string(), ..Default::default() };
+
+        tokio::time::timeout(
+            self.settings.operation_timeout,
+            self.client.upload_to_container(container_id, Some(options), tar_bytes.into()),
+        )
+        .await
+        .map_err(|_| McpError::OperationTimeout)?
+        .map_err(|e| McpError::DockerError(format!("Failed to upload to container: {}", e)))?;
+
+        Ok(CallToolResult {
+            content: vec![Content::Text(TextContent {
+                r#type: "text".to_string(),
+                text: format!("Copied archive into {}:{}", container_id, path),
+            })],
+            is_error: false,
+        })
+    }
+
+    async fn container_copy_out(&self, args: Value) -> Result<CallToolResult, McpError> {
+        self.check_read_only("container_copy_out")?;
+
+        let container_id = args
+            .get("container_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| McpError::InvalidParams("Missing container_id parameter".to_string()))?;
+        let path = args
+            .get("path")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| McpError::InvalidParams("Missing path parameter".to_string()))?;
+
+        let options = DownloadFromContainerOptions { path: path.to_string() };
+
+        let chunks = tokio::time::timeout(
+            self.settings.operation_timeout,
+            self.client.download_from_container(container_id, Some(options)).try_collect::<Vec<_>>(),
+        )
+        .await
+        .map_err(|_| McpError::OperationTimeout)?
+        .map_err(|e| McpError::DockerError(format!("Failed to download from container: {}", e)))?;
+
+        let mut tar_bytes: Vec<u8> = Vec::new();
+        for chunk in chunks {
+            tar_bytes.extend_from_slice(&chunk);
+        }
+
+        let truncated = tar_bytes.len() > self.settings.max_log_size;
+        if truncated {
+            tar_bytes.truncate(self.settings.max_log_size);
+        }
+
+        let tar_base64 = base64::engine::general_purpose::STANDARD.encode(&tar_bytes);
+
+        Ok(CallToolResult {
+            content: vec![Content::Text(TextContent {
+                r#type: "text".to_string(),
+                text: serde_json::json!({ "tar_base64": tar_base64, "truncated": truncated }).to_string(),
+            })],
+            is_error: false,
+        })
+    }
+
+    async fn compose_up(&self, args: Value) -> Result<CallToolResult, McpError> {
+        self.check_read_only("compose_up")?;
+        compose::native::compose_up(&self.client, &self.settings, args).await
+    }
+
+    async fn compose_down(&self, args: Value) -> Result<CallToolResult, McpError> {
+        self.check_read_only("compose_down")?;
+        compose::native::compose_down(&self.client, &self.settings, args).await
+    }
+
+    async fn validate_compose(&self, args: Value) -> Result<CallToolResult, McpError> {
+        self.check_read_only("validate_compose")?;
+        compose::validate_compose(&self.settings, args).await
+    }
+
+    async fn list_volumes(&self, args: Value) -> Result<CallToolResult, McpError> {
+        self.check_read_only("list_volumes")?;
+
+        let filter = args.get("filter").and_then(|v| v.as_str());
+
+        let mut options = ListVolumesOptions::<String>::default();
+        if let Some(filter_str) = filter {
+            let parts: Vec<&str> = filter_str.split('=').collect();
+            if parts.len() == 2 {
+                let mut filters = HashMap::new();
+                filters.insert(parts[0].to_string(), vec![parts[1].to_string()]);
+                options.filters = filters;
+            }
+        }
+
+        let response = self.client.list_volumes(Some(options)).await?;
+        let json_result = serde_json::to_string_pretty(&response.volumes.unwrap_or_default())?;
+
+        Ok(CallToolResult {
+            content: vec![Content::Text(TextContent { r#type: "text".to_string(), text: json_result })],
+            is_error: false,
+        })
+    }
+
+    async fn create_volume(&self, args: Value) -> Result<CallToolResult, McpError> {
+        self.check_read_only("create_volume")?;
+
+        let name = args.get("name").and_then(|v| v.as_str()).unwrap_or_default();
+        let driver = args.get("driver").and_then(|v| v.as_str()).unwrap_or_default();
+
+        let mut owned_labels: HashMap<String, String> = args
+            .get("labels")
+            .and_then(|v| v.as_object())
+            .map(|obj| obj.iter().filter_map(|(k, v)| v.as_str().map(|s| (k.clone(), s.to_string()))).collect())
+            .unwrap_or_default();
+        owned_labels.insert(super::VOLUME_OWNER_LABEL.to_string(), super::VOLUME_OWNER_VALUE.to_string());
+
+        // CreateVolumeOptions<T> infers T = &str from `name`/`driver`, so
+        // `labels` needs borrowed &str pairs too; owned_labels is kept alive
+        // across the call to back them.
+        let labels: HashMap<&str, &str> =
+            owned_labels.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect();
+
+        let volume = self
+            .client
+            .create_volume(CreateVolumeOptions { name, driver, labels, ..Default::default() })
+            .await?;
+
+        Ok(CallToolResult {
+            content: vec![Content::Text(TextContent {
+                r#type: "text".to_string(),
+                text: serde_json::to_string_pretty(&volume)?,
+            })],
+            is_error: false,
+        })
+    }
+
+    async fn remove_volume(&self, args: Value) -> Result<CallToolResult, McpError> {
+        self.check_read_only("remove_volume")?;
+
+        let name = args
+            .get("name")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| McpError::InvalidParams("Missing name parameter".to_string()))?;
+        let force = args.get("force").and_then(|v| v.as_bool()).unwrap_or(false);
+
+        self.client.remove_volume(name, Some(RemoveVolumeOptions { force })).await?;
+
+        Ok(CallToolResult {
+            content: vec![Content::Text(TextContent {
+                r#type: "text".to_string(),
+                text: format!("Removed volume {}", name),
+            })],
+            is_error: false,
+        })
+    }
+
+    /// Prunes dangling (unattached) volumes. Defaults to only those carrying
+    /// `VOLUME_OWNER_LABEL` — volumes this server itself created — so a
+    /// careless prune doesn't take out unrelated data the host's other
+    /// workloads still expect to find by name later; `all: true` opts into
+    /// removing every dangling volume regardless of origin.
+    async fn prune_volumes(&self, args: Value) -> Result<CallToolResult, McpError> {
+        self.check_read_only("prune_volumes")?;
+
+        let prune_all = args.get("all").and_then(|v| v.as_bool()).unwrap_or(false);
+
+        let mut options = PruneVolumesOptions::<String>::default();
+        if !prune_all {
+            let mut filters = HashMap::new();
+            filters.insert("label".to_string(), vec![format!("{}={}", super::VOLUME_OWNER_LABEL, super::VOLUME_OWNER_VALUE)]);
+            options.filters = filters;
+        }
+
+        let response = self.client.prune_volumes(Some(options)).await?;
+
+        Ok(CallToolResult {
+            content: vec![Content::Text(TextContent {
+                r#type: "text".to_string(),
+                text: serde_json::json!({
+                    "volumes_deleted": response.volumes_deleted.unwrap_or_default(),
+                    "space_reclaimed": response.space_reclaimed.unwrap_or(0),
+                })
+                .to_string(),
+            })],
+            is_error: false,
+        })
+    }
+
+    async fn get_docker_info(&self) -> Result<String, McpError> {
+        self.check_read_only("get_docker_info")?;
+
+        let info = self.client.info().await?;
+        Ok(serde_json::to_string_pretty(&info)?)
+    }
+
+    async fn get_docker_version(&self) -> Result<String, McpError> {
+        self.check_read_only("get_docker_version")?;
+
+        let version = self.client.version().await?;
+        Ok(serde_json::to_string_pretty(&version)?)
+    }
+
+    async fn get_container_details(&self, container_id: &str) -> Result<String, McpError> {
+        self.check_read_only("get_container_details")?;
+
+        let details = self.client.inspect_container(container_id, None).await?;
+        Ok(serde_json::to_string_pretty(&details)?)
+    }
+
+    async fn get_image_details(&self, image_id: &str) -> Result<String, McpError> {
+        self.check_read_only("get_image_details")?;
+
+        let details = self.client.inspect_image(image_id).await?;
+        Ok(serde_json::to_string_pretty(&details)?)
+    }
+
+    async fn get_volume_details(&self, volume_name: &str) -> Result<String, McpError> {
+        self.check_read_only("get_volume_details")?;
+
+        let details = self.client.inspect_volume(volume_name).await?;
+        Ok(serde_json::to_string_pretty(&details)?)
+    }
+
+    async fn get_compose_status(&self, project_directory: &str) -> Result<String, McpError> {
+        self.check_read_only("get_compose_status")?;
+        compose::native::get_compose_status(&self.client, &self.settings, project_directory).await
+    }
+
+    async fn list_network_names(&self) -> Result<Vec<String>, McpError> {
+        self.check_read_only("list_network_names")?;
+
+        let networks = self.client.list_networks::<String>(None).await?;
+        Ok(networks.into_iter().filter_map(|n| n.name).collect())
+    }
+}
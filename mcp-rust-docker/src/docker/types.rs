@@ -0,0 +1,209 @@
+//! Shapes shared between the Docker Engine API client (`socket`, built on
+//! bollard and a direct connection to the daemon socket) and the CLI
+//! fallback (`cli`, selected by `DockerBackend` at runtime), so the rest of
+//! the server doesn't need to know which backend produced a log line or
+//! exec chunk.
+
+use futures::stream::BoxStream;
+use tokio::sync::mpsc;
+
+use crate::protocol::error::McpError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogStream {
+    Stdout,
+    Stderr,
+}
+
+#[derive(Debug, Clone)]
+pub struct LogChunk {
+    pub stream: LogStream,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecStream {
+    Stdin,
+    Stdout,
+    Stderr,
+}
+
+#[derive(Debug, Clone)]
+pub struct ExecChunk {
+    pub stream: ExecStream,
+    pub data: Vec<u8>,
+}
+
+/// A running exec session: a channel to feed it stdin, plus a stream of
+/// demultiplexed stdout/stderr chunks. The caller is responsible for
+/// draining `output` to completion (or dropping it, which aborts the
+/// underlying task) and for fetching the exit code afterwards via
+/// `DockerBackend::exec_exit_code`.
+pub struct ExecHandle {
+    pub stdin_tx: mpsc::Sender<Vec<u8>>,
+    pub output: BoxStream<'static, Result<ExecChunk, McpError>>,
+}
+
+/// One `container_stats` sample. `cpu_percent` is always a snapshot
+/// computed from two successive CPU counters (the Engine API reports both
+/// the current and previous sample in one response, so the `api` backend
+/// needs only one call per tick; the `cli` backend takes dockerd's own
+/// precomputed `CPUPerc` instead, since `docker stats` doesn't expose raw
+/// counters). `per_cpu_usage` is only populated by the `api` backend.
+#[derive(Debug, Clone, Default)]
+pub struct ContainerStatsSample {
+    pub cpu_percent: f64,
+    pub cpu_total_usage: u64,
+    pub per_cpu_usage: Vec<u64>,
+    pub memory_usage: u64,
+    pub memory_limit: u64,
+    pub memory_cache: u64,
+    pub pids_current: u64,
+    pub pids_limit: Option<u64>,
+    pub blk_read: u64,
+    pub blk_write: u64,
+    pub network_rx: u64,
+    pub network_tx: u64,
+}
+
+/// `(cpu_delta / system_delta) * online_cpus * 100`, the same formula the
+/// `docker stats` CLI uses to turn two successive CPU counter samples into
+/// a percentage. Returns `0.0` if either delta is non-positive (first
+/// sample after a counter reset, clock skew, etc.) rather than dividing by
+/// zero or going negative.
+pub fn compute_cpu_percent(cpu_total: u64, precpu_total: u64, system_usage: u64, presystem_usage: u64, online_cpus: u64) -> f64 {
+    let cpu_delta = cpu_total.saturating_sub(precpu_total);
+    let system_delta = system_usage.saturating_sub(presystem_usage);
+
+    if cpu_delta == 0 || system_delta == 0 {
+        0.0
+    } else {
+        (cpu_delta as f64 / system_delta as f64) * online_cpus.max(1) as f64 * 100.0
+    }
+}
+
+/// Formats a byte count as `docker stats` would (`"1.94 GiB"`, `"0 B"`),
+/// using binary (1024-based) units.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: &[&str] = &["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = UNITS[0];
+    for candidate in &UNITS[1..] {
+        if value < 1024.0 {
+            break;
+        }
+        value /= 1024.0;
+        unit = candidate;
+    }
+
+    format!("{:.2} {}", value, unit)
+}
+
+/// Renders a [`ContainerStatsSample`] the way the `container_stats` tool
+/// returns it: raw byte counts alongside `format_bytes` strings, plus the
+/// derived memory usage (`usage - cache`, matching what `docker stats`
+/// itself reports) and its percent of `memory_limit`.
+pub fn container_stats_to_json(sample: &ContainerStatsSample) -> serde_json::Value {
+    let effective_memory_usage = sample.memory_usage.saturating_sub(sample.memory_cache);
+    let memory_percent = if sample.memory_limit > 0 {
+        (effective_memory_usage as f64 / sample.memory_limit as f64) * 100.0
+    } else {
+        0.0
+    };
+
+    serde_json::json!({
+        "cpu_percent": sample.cpu_percent,
+        "memory": {
+            "usage_bytes": effective_memory_usage,
+            "usage_human": format_bytes(effective_memory_usage),
+            "limit_bytes": sample.memory_limit,
+            "limit_human": format_bytes(sample.memory_limit),
+            "percent": memory_percent,
+        },
+        "pids": {
+            "current": sample.pids_current,
+            "limit": sample.pids_limit,
+        },
+        "block_io": {
+            "read_bytes": sample.blk_read,
+            "read_human": format_bytes(sample.blk_read),
+            "write_bytes": sample.blk_write,
+            "write_human": format_bytes(sample.blk_write),
+        },
+        "network": {
+            "rx_bytes": sample.network_rx,
+            "rx_human": format_bytes(sample.network_rx),
+            "tx_bytes": sample.network_tx,
+            "tx_human": format_bytes(sample.network_tx),
+        },
+    })
+}
+
+/// One entry from a container's `Mounts` (bind mount or named volume),
+/// reduced to the fields [`super::paths`] needs to build its prefix table.
+#[derive(Debug, Clone)]
+pub struct MountInfo {
+    /// Path on the host (bind mount source, or the volume's storage path).
+    pub source: String,
+    /// Path inside the container this mount is attached at.
+    pub destination: String,
+    pub read_only: bool,
+}
+
+/// A container's lifecycle state, folded from `docker inspect`'s
+/// `State.Status`/`State.Health.Status` for [`super::wait::wait_for_container`]
+/// to evaluate conditions (and short-circuit) against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContainerState {
+    Created,
+    Running,
+    Healthy,
+    Exited(i64),
+    Dead,
+}
+
+/// `docker inspect`'s `.State`, reduced to what
+/// [`super::wait::wait_for_container`] evaluates conditions against.
+/// `health_status` keeps the raw string (`"starting"`/`"unhealthy"`/
+/// `"none"`, or absent for a container with no `HEALTHCHECK`) alongside the
+/// derived `state`, since a caller with no `healthcheck` condition still
+/// benefits from seeing it.
+#[derive(Debug, Clone)]
+pub struct ContainerStateSummary {
+    pub state: ContainerState,
+    pub health_status: Option<String>,
+    pub restarting: bool,
+}
+
+/// Folds a raw `docker inspect` status (`"running"`, `"exited"`, ...) and
+/// health status into one [`ContainerState`]. A container only reaches
+/// [`ContainerState::Healthy`] once its status is `"running"` *and* its
+/// healthcheck reports `"healthy"`; any other `"running"`-family status
+/// (including `"restarting"`, `"paused"`) falls back to
+/// [`ContainerState::Running`] — [`ContainerStateSummary::restarting`]
+/// distinguishes the former for callers that care.
+pub fn classify_container_state(status: &str, health_status: Option<&str>, exit_code: i64) -> ContainerState {
+    match status {
+        "exited" => ContainerState::Exited(exit_code),
+        "dead" => ContainerState::Dead,
+        "created" => ContainerState::Created,
+        "running" if health_status == Some("healthy") => ContainerState::Healthy,
+        _ => ContainerState::Running,
+    }
+}
+
+/// Owned-volume accounting for `run_diagnostic`: how many volumes carry
+/// [`super::VOLUME_OWNER_LABEL`] and how much space they'd reclaim if
+/// pruned. `total_reclaimable_bytes` is `None` on the `cli` backend, which
+/// has no equivalent of the Engine API's per-volume `UsageData` short of
+/// parsing `docker system df`'s human-formatted output.
+#[derive(Debug, Clone, Default)]
+pub struct VolumeUsageSummary {
+    pub owned_count: usize,
+    pub total_reclaimable_bytes: Option<i64>,
+}
@@ -0,0 +1,115 @@
+//! Polling engine behind the `wait-for-container` tool: evaluates a
+//! caller-supplied list of [`WaitCondition`]s against `container_id`'s
+//! inspect state every tick until all of them hold, the container reaches
+//! a terminal state it can never satisfy from (`Exited`/`Dead`), or
+//! `timeout` elapses.
+
+use std::time::{Duration, Instant};
+
+use futures::stream::StreamExt;
+use regex::Regex;
+
+use super::backend::DockerBackend;
+use super::types::ContainerState;
+use crate::protocol::error::McpError;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+const PORT_CONNECT_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// One readiness check `wait_for_container` can block on.
+pub enum WaitCondition {
+    /// `State.Health.Status == "healthy"`.
+    Healthcheck,
+    /// `State.Running` and not `State.Restarting`.
+    Running,
+    /// `pattern` appears somewhere in stdout/stderr emitted since the wait
+    /// started.
+    LogMatch(Regex),
+    /// A TCP connection to `host:port` succeeds — typically a port the
+    /// container publishes on the Docker host.
+    PortOpen { host: String, port: u16 },
+}
+
+/// Terminal outcome of a successful wait: the container's state the moment
+/// every condition was satisfied, and how long that took.
+pub struct WaitOutcome {
+    pub state: ContainerState,
+    pub elapsed: Duration,
+}
+
+/// Polls `container_id` until every condition in `conditions` holds or
+/// `timeout` elapses. `Exited`/`Dead` short-circuit immediately with an
+/// error rather than waiting out the timeout, since no amount of further
+/// polling turns a dead container into a healthy one.
+pub async fn wait_for_container(
+    docker: &DockerBackend,
+    container_id: &str,
+    conditions: &[WaitCondition],
+    timeout: Duration,
+) -> Result<WaitOutcome, McpError> {
+    let start = Instant::now();
+
+    // Opened once up front rather than re-fetched on every tick, so a
+    // `log_match` condition sees everything the container has written
+    // since the wait started rather than just what's new since the last
+    // poll.
+    let mut log_stream = conditions
+        .iter()
+        .any(|c| matches!(c, WaitCondition::LogMatch(_)))
+        .then(|| docker.follow_logs(container_id));
+    let mut log_buffer = String::new();
+
+    loop {
+        let summary = docker.inspect_state(container_id).await?;
+
+        match summary.state {
+            ContainerState::Exited(code) => {
+                return Err(McpError::DockerError(format!(
+                    "Container '{}' exited with code {} while waiting",
+                    container_id, code
+                )));
+            }
+            ContainerState::Dead => {
+                return Err(McpError::DockerError(format!("Container '{}' is dead", container_id)));
+            }
+            _ => {}
+        }
+
+        if let Some(stream) = log_stream.as_mut() {
+            while let Ok(Some(chunk)) = tokio::time::timeout(Duration::from_millis(1), stream.next()).await {
+                log_buffer.push_str(&chunk?.text);
+            }
+        }
+
+        let mut all_satisfied = true;
+        for condition in conditions {
+            let satisfied = match condition {
+                WaitCondition::Healthcheck => summary.state == ContainerState::Healthy,
+                WaitCondition::Running => {
+                    !summary.restarting && matches!(summary.state, ContainerState::Running | ContainerState::Healthy)
+                }
+                WaitCondition::LogMatch(pattern) => pattern.is_match(&log_buffer),
+                WaitCondition::PortOpen { host, port } => {
+                    tokio::time::timeout(PORT_CONNECT_TIMEOUT, tokio::net::TcpStream::connect((host.as_str(), *port)))
+                        .await
+                        .map(|r| r.is_ok())
+                        .unwrap_or(false)
+                }
+            };
+            if !satisfied {
+                all_satisfied = false;
+                break;
+            }
+        }
+
+        if all_satisfied {
+            return Ok(WaitOutcome { state: summary.state, elapsed: start.elapsed() });
+        }
+
+        if start.elapsed() >= timeout {
+            return Err(McpError::OperationTimeout);
+        }
+
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
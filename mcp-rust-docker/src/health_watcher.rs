@@ -0,0 +1,92 @@
+//! Background subsystem that restarts containers the daemon has marked
+//! `unhealthy` for longer than a configurable grace period. Purely opt-in
+//! via `DockerSettings::health_watcher`; most deployments leave it
+//! disabled and handle restarts through their own orchestrator instead.
+//! Only containers carrying the configured label are ever touched.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Instant;
+
+use tokio::task::AbortHandle;
+
+use crate::audit::{self, AuditDecision, AuditEvent};
+use crate::config::types::HealthWatcherSettings;
+use crate::docker::DockerBackend;
+
+/// Owns the background polling task; dropping or calling `stop` tears it
+/// down.
+pub struct HealthWatcher {
+    abort: AbortHandle,
+}
+
+impl HealthWatcher {
+    /// Spawns the watcher loop against `docker`, polling every
+    /// `settings.interval` for containers marked `unhealthy` and
+    /// restarting any that have stayed that way past
+    /// `settings.unhealthy_timeout`. Returns immediately; the loop runs
+    /// until `stop` is called or the returned handle is dropped.
+    pub fn start(docker: Arc<DockerBackend>, settings: HealthWatcherSettings) -> Self {
+        let handle = tokio::spawn(async move {
+            let mut first_seen: HashMap<String, Instant> = HashMap::new();
+            let mut ticker = tokio::time::interval(settings.interval);
+
+            loop {
+                ticker.tick().await;
+
+                let unhealthy = match docker.list_unhealthy_containers(&settings.label).await {
+                    Ok(ids) => ids,
+                    Err(e) => {
+                        log::warn!("health watcher: failed to list unhealthy containers: {}", e);
+                        continue;
+                    }
+                };
+
+                // Drop tracking for anything that recovered or disappeared
+                // since the last poll, so a container that flaps doesn't
+                // carry over a stale first-seen time.
+                let still_unhealthy: HashSet<&str> = unhealthy.iter().map(String::as_str).collect();
+                first_seen.retain(|id, _| still_unhealthy.contains(id.as_str()));
+
+                let now = Instant::now();
+                for id in unhealthy {
+                    let first_seen_at = *first_seen.entry(id.clone()).or_insert(now);
+                    if now.duration_since(first_seen_at) < settings.unhealthy_timeout {
+                        continue;
+                    }
+
+                    match docker.restart_container(&id).await {
+                        Ok(()) => {
+                            log::info!("health watcher: restarted unhealthy container {}", id);
+                            audit::record(
+                                AuditEvent::new("health_watcher.restart", AuditDecision::Allow)
+                                    .with_target(id.clone())
+                                    .with_matched_rule(format!(
+                                        "unhealthy for at least {:?}",
+                                        settings.unhealthy_timeout
+                                    )),
+                            );
+                        }
+                        Err(e) => {
+                            log::warn!("health watcher: failed to restart container {}: {}", id, e);
+                        }
+                    }
+                    first_seen.remove(&id);
+                }
+            }
+        });
+
+        Self { abort: handle.abort_handle() }
+    }
+
+    /// Stops the watcher loop. Safe to call more than once.
+    pub fn stop(&self) {
+        self.abort.abort();
+    }
+}
+
+impl Drop for HealthWatcher {
+    fn drop(&mut self) {
+        self.abort.abort();
+    }
+}
@@ -1,5 +1,6 @@
 use chrono::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs::{File, OpenOptions};
 use std::io::Write;
 use std::path::Path;
@@ -7,6 +8,8 @@ use std::sync::Mutex;
 use std::time::Instant;
 use once_cell::sync::Lazy;
 
+use crate::metrics;
+
 // Define structured log entry
 #[derive(Debug, Serialize, Deserialize)]
 struct LogEntry {
@@ -17,6 +20,7 @@ struct LogEntry {
     method: Option<String>,
     details: Option<String>,
     error_code: Option<i32>,
+    error_class: Option<String>,
     duration_ms: Option<u64>,
 }
 
@@ -25,7 +29,9 @@ pub struct ErrorLogger {
     file: Option<Mutex<File>>,
     console_output: bool,
     log_requests: bool,
-    start_time: Instant,
+    // Start times of in-flight requests, keyed by request id, so
+    // `log_request_end` can report how long that specific request took.
+    in_flight: Mutex<HashMap<String, Instant>>,
 }
 
 // Global instance
@@ -34,7 +40,7 @@ static ERROR_LOGGER: Lazy<Mutex<ErrorLogger>> = Lazy::new(|| {
         file: None,
         console_output: true,
         log_requests: true,
-        start_time: Instant::now(),
+        in_flight: Mutex::new(HashMap::new()),
     })
 });
 
@@ -66,9 +72,22 @@ impl ErrorLogger {
         method: Option<&str>,
         details: Option<&str>,
         error_code: Option<i32>,
+    ) {
+        Self::log_error_with_duration(level, message, request_id, method, details, error_code, None, None);
+    }
+
+    fn log_error_with_duration(
+        level: &str,
+        message: &str,
+        request_id: Option<&str>,
+        method: Option<&str>,
+        details: Option<&str>,
+        error_code: Option<i32>,
+        error_class: Option<&str>,
+        duration_ms: Option<u64>,
     ) {
         let logger = ERROR_LOGGER.lock().unwrap();
-        
+
         let now = Local::now();
         let entry = LogEntry {
             timestamp: now.to_rfc3339(),
@@ -78,7 +97,8 @@ impl ErrorLogger {
             method: method.map(String::from),
             details: details.map(String::from),
             error_code,
-            duration_ms: Some(logger.start_time.elapsed().as_millis() as u64),
+            error_class: error_class.map(String::from),
+            duration_ms,
         };
         
         // Log to file if configured
@@ -119,21 +139,39 @@ impl ErrorLogger {
     }
     
     pub fn log_request_start(id: &str, method: &str) {
+        ERROR_LOGGER.lock().unwrap().in_flight.lock().unwrap().insert(id.to_string(), Instant::now());
+
+        metrics::record_request_start(method);
+
         if ERROR_LOGGER.lock().unwrap().log_requests {
             Self::log_error("INFO", &format!("Request started"), Some(id), Some(method), None, None);
         }
     }
-    
-    pub fn log_request_end(id: &str, method: &str, success: bool, error_code: Option<i32>, error_message: Option<&str>) {
+
+    pub fn log_request_end(
+        id: &str,
+        method: &str,
+        success: bool,
+        error_code: Option<i32>,
+        error_class: Option<&str>,
+        error_message: Option<&str>,
+    ) {
+        let start = ERROR_LOGGER.lock().unwrap().in_flight.lock().unwrap().remove(id);
+        let duration_ms = start.map(|start| start.elapsed().as_millis() as u64).unwrap_or(0);
+
+        metrics::record_request_end(success, error_code, duration_ms);
+
         if ERROR_LOGGER.lock().unwrap().log_requests {
             let status = if success { "succeeded" } else { "failed" };
-            Self::log_error(
+            Self::log_error_with_duration(
                 if success { "INFO" } else { "ERROR" },
                 &format!("Request {}", status),
                 Some(id),
                 Some(method),
                 error_message,
-                error_code
+                error_code,
+                error_class,
+                Some(duration_ms),
             );
         }
     }
@@ -147,52 +185,3 @@ impl ErrorLogger {
     }
 }
 
-// Add this to your McpServer implementation
-impl McpServer {
-    // Add a method to improve error logging
-    fn log_request(&self, request: &JsonRpcRequest, response: &JsonRpcResponse) {
-        let id = match &request.id {
-            JsonRpcId::Null => "null".to_string(),
-            JsonRpcId::String(s) => s.clone(),
-            JsonRpcId::Number(n) => n.to_string(),
-        };
-        
-        let success = response.error.is_none();
-        let error_code = response.error.as_ref().map(|e| e.code);
-        let error_message = response.error.as_ref().map(|e| e.message.as_str());
-        
-        ErrorLogger::log_request_end(&id, &request.method, success, error_code, error_message);
-    }
-    
-    // Modify your process_request method to use the logger
-    pub async fn process_request(&self, request: JsonRpcRequest) -> JsonRpcResponse {
-        // Log request start
-        let id_str = match &request.id {
-            JsonRpcId::Null => "null".to_string(),
-            JsonRpcId::String(s) => s.clone(),
-            JsonRpcId::Number(n) => n.to_string(),
-        };
-        
-        ErrorLogger::log_request_start(&id_str, &request.method);
-        
-        // Apply rate limiting
-        if let Err(e) = self.rate_limiter.check() {
-            let response = self.error_response(request.id, e);
-            self.log_request(&request, &response);
-            return response;
-        }
-
-        let response = match request.method.as_str() {
-            // Existing method handlers...
-            _ => self.error_response(
-                request.id,
-                McpError::MethodNotFound(format!("Method '{}' not found", request.method)),
-            ),
-        };
-        
-        // Log request completion
-        self.log_request(&request, &response);
-        
-        response
-    }
-}
\ No newline at end of file
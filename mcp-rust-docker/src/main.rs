@@ -1,5 +1,10 @@
+mod audit;
 mod config;
 mod docker;
+mod health_watcher;
+mod logging;
+mod metrics;
+mod oci;
 mod protocol;
 mod security;
 mod server;
@@ -35,6 +40,28 @@ async fn main() -> anyhow::Result<()> {
                 .long("quiet")
                 .help("Suppresses all output except errors"),
         )
+        .arg(
+            clap::Arg::new("transport")
+                .short('t')
+                .long("transport")
+                .value_name("TRANSPORT")
+                .possible_values(["stdio", "http", "sse", "tcp", "websocket"])
+                .help("Overrides the configured transport (stdio, http, tcp, websocket; \"sse\" is accepted as an alias for \"http\")")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("test")
+                .long("test")
+                .help("Validates the loaded config against the live environment (daemon reachability, referenced networks/images, quota consistency) and exits without starting the server"),
+        )
+        .arg(
+            clap::Arg::new("profile")
+                .short('p')
+                .long("profile")
+                .value_name("NAME")
+                .help("Selects a named profile from the config file's `profiles` section to layer on top of the base config (overrides DOCKER_MCP_PROFILE)")
+                .takes_value(true),
+        )
         .get_matches();
 
     // Set up logging with better default configuration
@@ -60,13 +87,14 @@ async fn main() -> anyhow::Result<()> {
 
     // Load configuration
     let config_path = matches.value_of("config").map(std::path::PathBuf::from);
+    let profile = matches.value_of("profile").map(str::to_string);
     info!("Loading configuration{}", if config_path.is_some() {
         format!(" from {:?}", config_path.as_ref().unwrap())
     } else {
         " from default locations".to_string()
     });
-    
-    let config = match crate::config::loader::load_config(config_path) {
+
+    let mut config = match crate::config::loader::load_config(config_path.clone(), profile.as_deref()) {
         Ok(config) => config,
         Err(e) => {
             error!("Failed to load configuration: {}", e);
@@ -76,6 +104,19 @@ async fn main() -> anyhow::Result<()> {
         }
     };
 
+    if let Some(transport) = matches.value_of("transport") {
+        config.server.transport = match transport {
+            "stdio" => crate::config::types::TransportType::Stdio,
+            "http" | "sse" => crate::config::types::TransportType::Http,
+            "tcp" => crate::config::types::TransportType::Tcp,
+            "websocket" => crate::config::types::TransportType::WebSocket,
+            other => {
+                error!("Unknown transport '{}'", other);
+                return Err(anyhow::anyhow!("Unknown transport '{}'", other));
+            }
+        };
+    }
+
     // Log startup information
     info!(
         "Configuration loaded - Server: {} ({})",
@@ -84,9 +125,98 @@ async fn main() -> anyhow::Result<()> {
     info!("Docker host: {}", config.docker.host);
     info!("Read-only mode: {}", if config.docker.read_only { "ENABLED" } else { "DISABLED" });
 
+    if matches.is_present("test") {
+        let report = crate::config::validate::validate(&config).await;
+        for assertion in &report.assertions {
+            if assertion.in_desired_state {
+                println!("PASS  {} (expected: {})", assertion.resource, assertion.expected);
+            } else {
+                println!(
+                    "FAIL  {} (expected: {}, actual: {})",
+                    assertion.resource, assertion.expected, assertion.actual
+                );
+            }
+        }
+
+        if report.all_passed() {
+            info!("Config validation passed");
+            return Ok(());
+        } else {
+            error!("Config validation failed");
+            std::process::exit(1);
+        }
+    }
+
+    let preconditions = &config.docker.preconditions;
+    if preconditions.fail_on_unmet
+        && (preconditions.required_docker_version.is_some()
+            || preconditions.required_api_version.is_some()
+            || !preconditions.required_images.is_empty())
+    {
+        match crate::docker::DockerBackend::new(&config.docker).await {
+            Ok(docker) => {
+                let docker = std::sync::Arc::new(docker);
+                let assertions = crate::config::validate::check_preconditions(&docker, &config).await;
+                let unmet: Vec<_> = assertions.iter().filter(|a| !a.in_desired_state).collect();
+                if unmet.is_empty() {
+                    info!("All configured startup preconditions satisfied");
+                } else {
+                    for assertion in &unmet {
+                        error!(
+                            "Startup precondition unmet: {} (expected: {}, actual: {})",
+                            assertion.resource, assertion.expected, assertion.actual
+                        );
+                    }
+                    return Err(anyhow::anyhow!("{} startup precondition(s) unmet", unmet.len()));
+                }
+            }
+            Err(e) => {
+                error!("Failed to connect to Docker while checking startup preconditions: {}", e);
+                return Err(anyhow::anyhow!("Docker connection failed while checking preconditions: {}", e));
+            }
+        }
+    }
+
+    if config.logging.audit_logging {
+        if let Err(e) = crate::audit::init_audit_sink(config.logging.audit_file.as_deref()) {
+            error!("Failed to initialize audit log: {}", e);
+            return Err(anyhow::anyhow!("Audit log initialization failed: {}", e));
+        }
+    }
+
+    if config.reload.enabled {
+        let initial = std::sync::Arc::new(config.clone());
+        match crate::config::watcher::ConfigWatcher::start(config_path.clone(), profile.clone(), initial, config.reload.debounce) {
+            Ok((watcher, mut config_rx)) => {
+                tokio::spawn(async move {
+                    // Holding `watcher` here keeps its background reload
+                    // task and `notify` handle alive for as long as this
+                    // task runs, which is the server's lifetime.
+                    let _watcher = watcher;
+                    while config_rx.changed().await.is_ok() {
+                        info!("Configuration reloaded from disk");
+                    }
+                });
+            }
+            Err(e) => {
+                error!("Failed to start config file watcher: {}", e);
+            }
+        }
+    }
+
+    if config.metrics.enabled {
+        let bind_address = config.metrics.bind_address.clone();
+        let bind_port = config.metrics.bind_port;
+        tokio::spawn(async move {
+            if let Err(e) = crate::metrics::run_metrics_server(&bind_address, bind_port).await {
+                error!("Metrics server error: {}", e);
+            }
+        });
+    }
+
     // Create and initialize server
     info!("Initializing server...");
-    let server = match crate::server::McpServer::new(&config) {
+    let server = match crate::server::McpServer::new(&config).await {
         Ok(server) => server,
         Err(e) => {
             error!("Failed to initialize server: {}", e);
@@ -102,23 +232,87 @@ async fn main() -> anyhow::Result<()> {
         }
     }
 
+    // Taken before `server` is moved into a transport below, so a SIGTERM/
+    // SIGINT can flip the draining flag and race shutdown against the
+    // transport's `run()` future even though nothing else holds onto the
+    // server directly from here on.
+    let shutdown = server.shutdown_handle();
+    shutdown.clone().install_signal_handlers();
+
     // Set up transport based on configuration
     match server.get_transport_type() {
         crate::config::types::TransportType::Stdio => {
             info!("Using stdio transport for JSON-RPC communication");
-            let mut transport = crate::transport::stdio::StdioTransport::new(server);
-            
-            match transport.run().await {
-                Ok(_) => info!("Transport completed normally"),
-                Err(e) => {
-                    error!("Transport error: {}", e);
-                    return Err(anyhow::anyhow!("Transport error: {}", e));
-                }
+            let max_in_flight = server.get_max_in_flight();
+            let mut transport = crate::transport::stdio::StdioTransport::new(server)
+                .with_max_in_flight(max_in_flight);
+
+            tokio::select! {
+                result = transport.run() => match result {
+                    Ok(_) => info!("Transport completed normally"),
+                    Err(e) => {
+                        error!("Transport error: {}", e);
+                        return Err(anyhow::anyhow!("Transport error: {}", e));
+                    }
+                },
+                _ = shutdown.wait_for_shutdown() => info!("Graceful shutdown complete"),
+            }
+        }
+        crate::config::types::TransportType::Http => {
+            info!(
+                "Using HTTP transport on {}{}:{}",
+                if config.server.tls.is_some() { "https://" } else { "http://" },
+                config.server.bind_address,
+                config.server.bind_port
+            );
+            let mut transport = crate::transport::sse::SseTransport::new(server);
+
+            tokio::select! {
+                result = transport.run() => match result {
+                    Ok(_) => info!("Transport completed normally"),
+                    Err(e) => {
+                        error!("Transport error: {}", e);
+                        return Err(anyhow::anyhow!("Transport error: {}", e));
+                    }
+                },
+                _ = shutdown.wait_for_shutdown() => info!("Graceful shutdown complete"),
             }
         }
-        crate::config::types::TransportType::Sse => {
-            error!("SSE transport not implemented yet");
-            return Err(anyhow::anyhow!("SSE transport not implemented yet"));
+        crate::config::types::TransportType::Tcp => {
+            info!(
+                "Using TCP transport on {}:{}",
+                config.server.bind_address, config.server.bind_port
+            );
+            let mut transport = crate::transport::tcp::TcpTransport::new(server);
+
+            tokio::select! {
+                result = transport.run() => match result {
+                    Ok(_) => info!("Transport completed normally"),
+                    Err(e) => {
+                        error!("Transport error: {}", e);
+                        return Err(anyhow::anyhow!("Transport error: {}", e));
+                    }
+                },
+                _ = shutdown.wait_for_shutdown() => info!("Graceful shutdown complete"),
+            }
+        }
+        crate::config::types::TransportType::WebSocket => {
+            info!(
+                "Using WebSocket transport on {}:{}",
+                config.server.bind_address, config.server.bind_port
+            );
+            let mut transport = crate::transport::websocket::WebSocketTransport::new(server);
+
+            tokio::select! {
+                result = transport.run() => match result {
+                    Ok(_) => info!("Transport completed normally"),
+                    Err(e) => {
+                        error!("Transport error: {}", e);
+                        return Err(anyhow::anyhow!("Transport error: {}", e));
+                    }
+                },
+                _ = shutdown.wait_for_shutdown() => info!("Graceful shutdown complete"),
+            }
         }
     }
 
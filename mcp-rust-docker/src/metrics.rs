@@ -0,0 +1,171 @@
+//! Prometheus-style counters and a latency histogram, fed centrally from
+//! `ErrorLogger::log_request_start`/`log_request_end` so every transport
+//! gets the same instrumentation for free. Exposed in text exposition
+//! format over a small HTTP `/metrics` endpoint bound to a configurable
+//! admin address, so operators can scrape this server the same way they'd
+//! scrape any other service instead of grepping the JSON Lines log.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use axum::routing::get;
+use axum::Router;
+use once_cell::sync::Lazy;
+
+use crate::protocol::error::McpError;
+
+/// Bucket boundaries (inclusive, milliseconds) for `mcp_request_duration_ms`.
+const LATENCY_BUCKETS_MS: &[u64] = &[5, 10, 25, 50, 100, 250, 500, 1000, 2500, 5000, 10000];
+
+struct Histogram {
+    // Parallel to `LATENCY_BUCKETS_MS`; each entry is the cumulative count
+    // of observations `<=` that bucket's boundary, Prometheus-style.
+    bucket_counts: Vec<AtomicU64>,
+    sum_ms: AtomicU64,
+    count: AtomicU64,
+}
+
+impl Histogram {
+    fn new() -> Self {
+        Self {
+            bucket_counts: LATENCY_BUCKETS_MS.iter().map(|_| AtomicU64::new(0)).collect(),
+            sum_ms: AtomicU64::new(0),
+            count: AtomicU64::new(0),
+        }
+    }
+
+    fn observe(&self, value_ms: u64) {
+        for (boundary, bucket) in LATENCY_BUCKETS_MS.iter().zip(self.bucket_counts.iter()) {
+            if value_ms <= *boundary {
+                bucket.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        self.sum_ms.fetch_add(value_ms, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn render(&self, name: &str, out: &mut String) {
+        for (boundary, bucket) in LATENCY_BUCKETS_MS.iter().zip(self.bucket_counts.iter()) {
+            out.push_str(&format!(
+                "{}_bucket{{le=\"{}\"}} {}\n",
+                name,
+                boundary,
+                bucket.load(Ordering::Relaxed)
+            ));
+        }
+        let count = self.count.load(Ordering::Relaxed);
+        out.push_str(&format!("{}_bucket{{le=\"+Inf\"}} {}\n", name, count));
+        out.push_str(&format!("{}_sum {}\n", name, self.sum_ms.load(Ordering::Relaxed)));
+        out.push_str(&format!("{}_count {}\n", name, count));
+    }
+}
+
+struct Metrics {
+    total_requests: AtomicU64,
+    rate_limit_rejections: AtomicU64,
+    by_method: Mutex<HashMap<String, u64>>,
+    by_tool: Mutex<HashMap<String, u64>>,
+    errors_by_code: Mutex<HashMap<i32, u64>>,
+    latency_ms: Histogram,
+}
+
+static METRICS: Lazy<Metrics> = Lazy::new(|| Metrics {
+    total_requests: AtomicU64::new(0),
+    rate_limit_rejections: AtomicU64::new(0),
+    by_method: Mutex::new(HashMap::new()),
+    by_tool: Mutex::new(HashMap::new()),
+    errors_by_code: Mutex::new(HashMap::new()),
+    latency_ms: Histogram::new(),
+});
+
+/// Called from `log_request_start`: counts the request against the total
+/// and its method.
+pub fn record_request_start(method: &str) {
+    METRICS.total_requests.fetch_add(1, Ordering::Relaxed);
+    *METRICS.by_method.lock().unwrap().entry(method.to_string()).or_insert(0) += 1;
+}
+
+/// Called from `log_request_end`: records latency and, on failure, the
+/// error code (plus the rate-limit counter, if that's what failed it).
+pub fn record_request_end(success: bool, error_code: Option<i32>, duration_ms: u64) {
+    METRICS.latency_ms.observe(duration_ms);
+
+    if !success {
+        let code = error_code.unwrap_or(0);
+        *METRICS.errors_by_code.lock().unwrap().entry(code).or_insert(0) += 1;
+
+        if error_code == Some(McpError::RateLimitExceeded.to_json_rpc_error().code) {
+            METRICS.rate_limit_rejections.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Called from `handle_call_tool` once the tool name is resolved, since
+/// that's only known past JSON-RPC method dispatch.
+pub fn record_tool_call(tool: &str) {
+    *METRICS.by_tool.lock().unwrap().entry(tool.to_string()).or_insert(0) += 1;
+}
+
+fn render() -> String {
+    let mut out = String::new();
+
+    out.push_str("# HELP mcp_requests_total Total JSON-RPC requests received.\n");
+    out.push_str("# TYPE mcp_requests_total counter\n");
+    out.push_str(&format!("mcp_requests_total {}\n", METRICS.total_requests.load(Ordering::Relaxed)));
+
+    out.push_str("# HELP mcp_requests_by_method_total JSON-RPC requests, by method.\n");
+    out.push_str("# TYPE mcp_requests_by_method_total counter\n");
+    for (method, count) in METRICS.by_method.lock().unwrap().iter() {
+        out.push_str(&format!("mcp_requests_by_method_total{{method=\"{}\"}} {}\n", method, count));
+    }
+
+    out.push_str("# HELP mcp_tool_calls_total Tool invocations, by tool.\n");
+    out.push_str("# TYPE mcp_tool_calls_total counter\n");
+    for (tool, count) in METRICS.by_tool.lock().unwrap().iter() {
+        out.push_str(&format!("mcp_tool_calls_total{{tool=\"{}\"}} {}\n", tool, count));
+    }
+
+    out.push_str("# HELP mcp_errors_total Failed requests, by JSON-RPC error code.\n");
+    out.push_str("# TYPE mcp_errors_total counter\n");
+    for (code, count) in METRICS.errors_by_code.lock().unwrap().iter() {
+        out.push_str(&format!("mcp_errors_total{{error_code=\"{}\"}} {}\n", code, count));
+    }
+
+    out.push_str("# HELP mcp_rate_limit_rejections_total Requests rejected by the rate limiter.\n");
+    out.push_str("# TYPE mcp_rate_limit_rejections_total counter\n");
+    out.push_str(&format!(
+        "mcp_rate_limit_rejections_total {}\n",
+        METRICS.rate_limit_rejections.load(Ordering::Relaxed)
+    ));
+
+    out.push_str("# HELP mcp_request_duration_ms Request latency in milliseconds.\n");
+    out.push_str("# TYPE mcp_request_duration_ms histogram\n");
+    METRICS.latency_ms.render("mcp_request_duration_ms", &mut out);
+
+    out
+}
+
+async fn metrics_handler() -> String {
+    render()
+}
+
+/// Serves `GET /metrics` on `bind_address:bind_port` until the process
+/// exits. Intended to be spawned as its own background task alongside
+/// whichever transport the server is configured with.
+pub async fn run_metrics_server(bind_address: &str, bind_port: u16) -> Result<(), McpError> {
+    let app = Router::new().route("/metrics", get(metrics_handler));
+
+    let addr = format!("{}:{}", bind_address, bind_port);
+    log::info!("Metrics endpoint listening on http://{}/metrics", addr);
+
+    let listener = tokio::net::TcpListener::bind(&addr)
+        .await
+        .map_err(|e| McpError::InternalError(format!("Failed to bind metrics listener on {}: {}", addr, e)))?;
+
+    axum::serve(listener, app)
+        .await
+        .map_err(|e| McpError::InternalError(format!("Metrics server error: {}", e)))?;
+
+    Ok(())
+}
@@ -0,0 +1,90 @@
+//! Direct OCI runtime backend: drives `runc`/`crun`/`youki` (or anything
+//! else exposing the same CLI surface) directly, rather than going through
+//! a Docker daemon — the same `state`/`create`/`start`/`kill`/`delete`
+//! subcommands `rust-runc` shells out to and youki's own test harness
+//! drives against itself. This lets the server manage containers on hosts
+//! with no `dockerd` at all; it's entirely separate from `docker::DockerBackend`
+//! and only active when `ServerConfig::oci` is set.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use tokio::process::Command;
+
+use crate::config::types::OciRuntimeSettings;
+use crate::protocol::error::McpError;
+
+/// The OCI runtime spec `State` structure, as printed by `<runtime> state
+/// <id>`: https://github.com/opencontainers/runtime-spec/blob/main/runtime.md#state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OciState {
+    #[serde(rename = "ociVersion")]
+    pub oci_version: String,
+    pub id: String,
+    pub status: String,
+    #[serde(default)]
+    pub pid: Option<i64>,
+    pub bundle: String,
+    #[serde(default)]
+    pub annotations: HashMap<String, String>,
+    #[serde(default)]
+    pub created: Option<String>,
+}
+
+pub struct OciRuntime {
+    settings: OciRuntimeSettings,
+}
+
+impl OciRuntime {
+    pub fn new(settings: OciRuntimeSettings) -> Self {
+        Self { settings }
+    }
+
+    async fn run(&self, args: &[&str]) -> Result<String, McpError> {
+        let output = Command::new(&self.settings.runtime_path)
+            .args(args)
+            .output()
+            .await
+            .map_err(|e| {
+                McpError::DockerError(format!("Failed to run {}: {}", self.settings.runtime_path.display(), e))
+            })?;
+
+        if !output.status.success() {
+            return Err(McpError::DockerError(format!(
+                "{} {} failed: {}",
+                self.settings.runtime_path.display(),
+                args.join(" "),
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    pub async fn state(&self, id: &str) -> Result<OciState, McpError> {
+        let stdout = self.run(&["state", id]).await?;
+        serde_json::from_str(stdout.trim())
+            .map_err(|e| McpError::DockerError(format!("Failed to parse OCI state for {}: {}", id, e)))
+    }
+
+    pub async fn create(&self, id: &str) -> Result<(), McpError> {
+        let bundle = self.settings.bundle_dir.to_string_lossy().into_owned();
+        self.run(&["create", "--bundle", &bundle, id]).await?;
+        Ok(())
+    }
+
+    pub async fn start(&self, id: &str) -> Result<(), McpError> {
+        self.run(&["start", id]).await?;
+        Ok(())
+    }
+
+    pub async fn kill(&self, id: &str, signal: &str) -> Result<(), McpError> {
+        self.run(&["kill", id, signal]).await?;
+        Ok(())
+    }
+
+    pub async fn delete(&self, id: &str) -> Result<(), McpError> {
+        self.run(&["delete", id]).await?;
+        Ok(())
+    }
+}
@@ -44,6 +44,22 @@ pub enum McpError {
     
     #[error("Operation timeout")]
     OperationTimeout,
+
+    #[error("Server is shutting down")]
+    ServerShuttingDown,
+
+    /// Wraps another `McpError` with structured context for
+    /// `JsonRpcError.data` - a captured `source()` cause chain, and/or
+    /// caller-supplied data (tool name, container id, ...) - without
+    /// altering the message or numeric code the inner error maps to. Built
+    /// via [`McpError::with_data`] or automatically by the `bollard`/`io`/
+    /// `serde_json` `From` impls below, never constructed directly.
+    #[error("{inner}")]
+    WithContext {
+        inner: Box<McpError>,
+        causes: Vec<String>,
+        data: Option<serde_json::Value>,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -56,45 +72,149 @@ pub struct JsonRpcError {
 
 impl McpError {
     pub fn to_json_rpc_error(&self) -> JsonRpcError {
-        let (code, message) = match self {
-            McpError::ParseError(msg) => (-32700, msg.clone()),
-            McpError::InvalidRequest(msg) => (-32600, msg.clone()),
-            McpError::MethodNotFound(msg) => (-32601, msg.clone()),
-            McpError::InvalidParams(msg) => (-32602, msg.clone()),
-            McpError::InternalError(msg) => (-32603, msg.clone()),
-            McpError::ResourceNotFound(msg) => (1, format!("Resource not found: {}", msg)),
-            McpError::ToolNotFound(msg) => (2, format!("Tool not found: {}", msg)),
-            McpError::PromptNotFound(msg) => (3, format!("Prompt not found: {}", msg)),
-            McpError::DockerError(msg) => (4, format!("Docker error: {}", msg)),
-            McpError::SecurityError(msg) => (5, format!("Security error: {}", msg)),
-            McpError::RateLimitExceeded => (6, "Rate limit exceeded".to_string()),
-            McpError::ResourceQuotaExceeded(msg) => (7, format!("Resource quota exceeded: {}", msg)),
-            McpError::OperationNotPermitted(msg) => (8, format!("Operation not permitted: {}", msg)),
-            McpError::OperationTimeout => (9, "Operation timeout".to_string()),
+        let message = match self {
+            McpError::ParseError(msg) => msg.clone(),
+            McpError::InvalidRequest(msg) => msg.clone(),
+            McpError::MethodNotFound(msg) => msg.clone(),
+            McpError::InvalidParams(msg) => msg.clone(),
+            McpError::InternalError(msg) => msg.clone(),
+            McpError::ResourceNotFound(msg) => format!("Resource not found: {}", msg),
+            McpError::ToolNotFound(msg) => format!("Tool not found: {}", msg),
+            McpError::PromptNotFound(msg) => format!("Prompt not found: {}", msg),
+            McpError::DockerError(msg) => format!("Docker error: {}", msg),
+            McpError::SecurityError(msg) => format!("Security error: {}", msg),
+            McpError::RateLimitExceeded => "Rate limit exceeded".to_string(),
+            McpError::ResourceQuotaExceeded(msg) => format!("Resource quota exceeded: {}", msg),
+            McpError::OperationNotPermitted(msg) => format!("Operation not permitted: {}", msg),
+            McpError::OperationTimeout => "Operation timeout".to_string(),
+            McpError::ServerShuttingDown => "Server is shutting down".to_string(),
+            McpError::WithContext { inner, .. } => inner.to_json_rpc_error().message,
         };
 
-        JsonRpcError {
-            code,
-            message,
-            data: None,
+        let (code, class) = classify(self);
+
+        let mut data = serde_json::json!({ "class": class });
+        if let McpError::WithContext { causes, data: extra, .. } = self {
+            if !causes.is_empty() {
+                data["causes"] = serde_json::Value::Array(
+                    causes.iter().map(|c| serde_json::Value::String(c.clone())).collect(),
+                );
+            }
+            if let Some(extra) = extra {
+                data["data"] = extra.clone();
+            }
+        }
+
+        JsonRpcError { code, message, data: Some(data) }
+    }
+
+    /// Attaches caller-supplied context (tool name, container id, ...) to
+    /// `JsonRpcError.data.data`, without altering the message or numeric
+    /// code `self` maps to. Replaces any data attached by an earlier
+    /// `with_data` call on the same error.
+    pub fn with_data(self, data: serde_json::Value) -> Self {
+        match self {
+            McpError::WithContext { inner, causes, .. } => {
+                McpError::WithContext { inner, causes, data: Some(data) }
+            }
+            other => McpError::WithContext { inner: Box::new(other), causes: Vec::new(), data: Some(data) },
+        }
+    }
+
+    /// Wraps `self` with a captured `source()` cause chain, unless it's
+    /// empty. Used by the `From` impls below so a converted `bollard`/`io`/
+    /// `serde_json` error keeps its full chain instead of flattening to
+    /// just the outermost `Display` text.
+    fn with_causes(self, causes: Vec<String>) -> Self {
+        if causes.is_empty() {
+            return self;
+        }
+        match self {
+            McpError::WithContext { inner, causes: existing, data } => {
+                let mut merged = existing;
+                merged.extend(causes);
+                McpError::WithContext { inner, causes: merged, data }
+            }
+            other => McpError::WithContext { inner: Box::new(other), causes, data: None },
         }
     }
 }
 
+/// Walks `err.source()` like anyhow's cause iteration, collecting each
+/// link's `Display` text.
+fn cause_chain(err: &dyn std::error::Error) -> Vec<String> {
+    let mut causes = Vec::new();
+    let mut source = err.source();
+    while let Some(err) = source {
+        causes.push(err.to_string());
+        source = err.source();
+    }
+    causes
+}
+
+/// Maps an `McpError` to its stable JSON-RPC numeric code and a
+/// human-readable class string, so clients can branch on `data.class`
+/// instead of string-matching `message`. Docker-specific errors get a
+/// second pass through [`classify_docker_error`] since `DockerError`
+/// wraps whatever the daemon (or the `docker` CLI fallback) said, and
+/// that message is the only place daemon-unreachable/404/409 show up.
+pub fn classify(err: &McpError) -> (i32, &'static str) {
+    match err {
+        McpError::ParseError(_) => (-32700, "ParseError"),
+        McpError::InvalidRequest(_) => (-32600, "InvalidRequest"),
+        McpError::MethodNotFound(_) => (-32601, "MethodNotFound"),
+        McpError::InvalidParams(_) => (-32602, "InvalidData"),
+        McpError::InternalError(_) => (-32603, "InternalError"),
+        McpError::ResourceNotFound(_) => (1, "NotFound"),
+        McpError::ToolNotFound(_) => (2, "NotFound"),
+        McpError::PromptNotFound(_) => (3, "NotFound"),
+        McpError::DockerError(msg) => classify_docker_error(msg),
+        McpError::SecurityError(_) => (5, "PermissionDenied"),
+        McpError::RateLimitExceeded => (6, "RateLimited"),
+        McpError::ResourceQuotaExceeded(_) => (7, "PermissionDenied"),
+        McpError::OperationNotPermitted(_) => (8, "PermissionDenied"),
+        McpError::OperationTimeout => (9, "Timeout"),
+        McpError::ServerShuttingDown => (13, "Unavailable"),
+        McpError::WithContext { inner, .. } => classify(inner),
+    }
+}
+
+/// Classifies the message carried by `McpError::DockerError` so daemon
+/// connectivity, missing-container, and conflict responses each get a
+/// distinct, stable class instead of being lumped under a single generic
+/// code. The numeric code for unmatched messages stays `4`, matching the
+/// pre-existing `DockerError` code.
+fn classify_docker_error(message: &str) -> (i32, &'static str) {
+    let lower = message.to_lowercase();
+
+    if lower.contains("cannot connect to the docker daemon") || lower.contains("connection refused") {
+        (10, "DockerUnavailable")
+    } else if lower.contains("no such container") || lower.contains("404") {
+        (11, "NotFound")
+    } else if lower.contains("conflict") || lower.contains("409") {
+        (12, "Conflict")
+    } else {
+        (4, "DockerError")
+    }
+}
+
 impl From<bollard::errors::Error> for McpError {
     fn from(error: bollard::errors::Error) -> Self {
-        McpError::DockerError(error.to_string())
+        let causes = cause_chain(&error);
+        McpError::DockerError(error.to_string()).with_causes(causes)
     }
 }
 
 impl From<std::io::Error> for McpError {
     fn from(error: std::io::Error) -> Self {
-        McpError::InternalError(error.to_string())
+        let causes = cause_chain(&error);
+        McpError::InternalError(error.to_string()).with_causes(causes)
     }
 }
 
 impl From<serde_json::Error> for McpError {
     fn from(error: serde_json::Error) -> Self {
-        McpError::ParseError(error.to_string())
+        let causes = cause_chain(&error);
+        McpError::ParseError(error.to_string()).with_causes(causes)
     }
 }
\ No newline at end of file
@@ -1,125 +1,309 @@
 
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+
+use casbin::{CoreApi, DefaultModel, Enforcer, InternalApi, MemoryAdapter, MgmtApi};
+use dashmap::DashMap;
+use tokio::sync::RwLock;
+
+use crate::audit::{self, AuditDecision, AuditEvent};
 use crate::config::types::{RateLimitSettings, SecuritySettings};
+use crate::logging::ErrorLogger;
 use crate::protocol::error::McpError;
 use crate::protocol::types::{CallToolRequest, ReadResourceRequest};
 
+/// Fixed-point scale applied to token counts so fractional per-millisecond
+/// accrual (e.g. 60 requests/minute is 1 token/second) doesn't round down to
+/// zero between checks.
+const TOKEN_SCALE: u64 = 1_000;
+
+/// Embedded default Casbin model: subject/object/action requests, RBAC role
+/// inheritance via `g`, glob-matched objects (`keyMatch2`), and a
+/// deny-overrides effect so a single `p, *, ..., deny` policy always wins
+/// over a broader allow.
+const DEFAULT_MODEL: &str = include_str!("model.conf");
+
+/// A single key's token bucket: `tokens` (in `TOKEN_SCALE`-ths of a request)
+/// and `last_refill_ms` (milliseconds since the limiter's epoch) are each
+/// plain atomics so concurrent checks for the same key race on a
+/// compare-and-swap loop instead of a lock.
+struct Bucket {
+    tokens: AtomicU64,
+    last_refill_ms: AtomicU64,
+}
+
+impl Bucket {
+    fn full(capacity: u64) -> Self {
+        Self {
+            tokens: AtomicU64::new(capacity),
+            last_refill_ms: AtomicU64::new(0),
+        }
+    }
+}
 
-// Rate limiter implementation using Governor crate
+/// Token-bucket rate limiter keyed by JSON-RPC method name (there's no
+/// per-connection client identity to key on yet). Buckets live in a
+/// `DashMap` rather than behind one global `RwLock<HashMap<..>>`, and each
+/// bucket's own refill/consume step is a lock-free CAS loop, so a hot method
+/// doesn't serialize checks for every other method.
 pub struct RateLimiter {
     settings: RateLimitSettings,
+    epoch: Instant,
+    buckets: DashMap<String, Bucket>,
 }
 
 impl RateLimiter {
     pub fn new(settings: &RateLimitSettings) -> Self {
         Self {
             settings: settings.clone(),
+            epoch: Instant::now(),
+            buckets: DashMap::new(),
         }
     }
 
-    pub fn check(&self) -> Result<(), McpError> {
+    /// Refills `key`'s bucket for the time elapsed since it was last
+    /// checked, then tries to consume one token. Returns
+    /// `McpError::RateLimitExceeded` (mapped to its own JSON-RPC code,
+    /// distinct from any Docker/internal error) when none remain, and logs
+    /// the throttle via `log_security_violation` so operators can tell
+    /// "denied by rate limit" apart from "the operation itself failed".
+    pub fn check(&self, key: &str) -> Result<(), McpError> {
         if !self.settings.enabled {
             return Ok(());
         }
-        
-        // For now, just allow all requests
-        // A real implementation would track request rates
-        Ok(())
+
+        let capacity = (self.settings.burst as u64).max(1) * TOKEN_SCALE;
+        let refill_per_ms = self.settings.requests_per_minute as f64 * TOKEN_SCALE as f64 / 60_000.0;
+        let now_ms = self.epoch.elapsed().as_millis() as u64;
+
+        let bucket = self.buckets.entry(key.to_string()).or_insert_with(|| Bucket::full(capacity));
+
+        let prev_ms = bucket.last_refill_ms.swap(now_ms, Ordering::AcqRel);
+        let refill = (now_ms.saturating_sub(prev_ms) as f64 * refill_per_ms) as u64;
+
+        loop {
+            let current = bucket.tokens.load(Ordering::Acquire);
+            let refilled = current.saturating_add(refill).min(capacity);
+
+            if refilled < TOKEN_SCALE {
+                let _ = bucket.tokens.compare_exchange(current, refilled, Ordering::AcqRel, Ordering::Acquire);
+                ErrorLogger::log_security_violation(
+                    &format!("Rate limit exceeded for method '{}'", key),
+                    Some(&format!(
+                        "{} requests/minute, burst {}",
+                        self.settings.requests_per_minute, self.settings.burst
+                    )),
+                );
+                return Err(McpError::RateLimitExceeded);
+            }
+
+            let consumed = refilled - TOKEN_SCALE;
+            match bucket.tokens.compare_exchange(current, consumed, Ordering::AcqRel, Ordering::Acquire) {
+                Ok(_) => return Ok(()),
+                Err(_) => continue,
+            }
+        }
     }
 }
 
-// Security validator for Docker operations
+/// Security validator for Docker operations, backed by a Casbin `Enforcer`.
+/// Every tool call and resource read is modeled as a `(subject, object,
+/// action)` triple and checked with `enforce`, so operators can express
+/// fine-grained RBAC rules (`g, alice, admin`) and glob-scoped object rules
+/// (`p, admin, docker://image/*, read`) instead of being limited to flat
+/// allow/deny lists. The legacy `SecuritySettings` lists are still honored:
+/// they're translated into equivalent policies once at startup.
 pub struct SecurityValidator {
-    settings: SecuritySettings,
+    enforcer: RwLock<Enforcer>,
+    default_subject: String,
 }
 
 impl SecurityValidator {
-    pub fn new(settings: &SecuritySettings) -> Self {
-        Self {
-            settings: settings.clone(),
+    pub async fn new(settings: &SecuritySettings) -> Result<Self, McpError> {
+        let model = match &settings.casbin.model_path {
+            Some(path) => DefaultModel::from_file(path)
+                .await
+                .map_err(|e| McpError::InvalidRequest(format!("Failed to load Casbin model {:?}: {}", path, e)))?,
+            None => DefaultModel::from_str(DEFAULT_MODEL)
+                .await
+                .map_err(|e| McpError::InvalidRequest(format!("Failed to parse embedded Casbin model: {}", e)))?,
+        };
+
+        let mut enforcer = Enforcer::new(model, MemoryAdapter::default())
+            .await
+            .map_err(|e| McpError::InvalidRequest(format!("Failed to build Casbin enforcer: {}", e)))?;
+
+        if let Some(policy_path) = &settings.casbin.policy_path {
+            let adapter = casbin::FileAdapter::new(policy_path.clone());
+            enforcer
+                .set_adapter(adapter)
+                .await
+                .map_err(|e| McpError::InvalidRequest(format!("Failed to load Casbin policy {:?}: {}", policy_path, e)))?;
         }
+
+        for line in &settings.casbin.policies {
+            apply_policy_line(&mut enforcer, line).await?;
+        }
+
+        translate_legacy_lists(&mut enforcer, settings).await?;
+
+        Ok(Self {
+            enforcer: RwLock::new(enforcer),
+            default_subject: settings.casbin.default_subject.clone(),
+        })
     }
 
-    pub fn validate_tool(&self, request: &CallToolRequest) -> Result<(), McpError> {
-        // Check if command is allowed
-        if let Some(allowed) = &self.settings.commands.allowed_commands {
-            if !allowed.contains(&request.name) {
-                return Err(McpError::OperationNotPermitted(format!(
-                    "Tool '{}' is not in the allowed list",
-                    request.name
-                )));
-            }
-        } else if self.settings.commands.denied_commands.contains(&request.name) {
+    pub async fn validate_tool(&self, request: &CallToolRequest) -> Result<(), McpError> {
+        let object = tool_object(request);
+        self.enforce(&object, &request.name).await
+    }
+
+    pub async fn validate_resource(&self, request: &ReadResourceRequest) -> Result<(), McpError> {
+        self.enforce(&request.uri, "read").await
+    }
+
+    async fn enforce(&self, object: &str, action: &str) -> Result<(), McpError> {
+        let enforcer = self.enforcer.read().await;
+        let allowed = enforcer
+            .enforce((self.default_subject.as_str(), object, action))
+            .map_err(|e| McpError::InternalError(format!("Policy evaluation failed: {}", e)))?;
+
+        if !allowed {
+            audit::record(
+                AuditEvent::new(action, AuditDecision::Deny)
+                    .with_actor(&self.default_subject)
+                    .with_target(object)
+                    .with_matched_rule("casbin policy"),
+            );
             return Err(McpError::OperationNotPermitted(format!(
-                "Tool '{}' is in the denied list",
-                request.name
+                "'{}' on '{}' is not permitted by policy",
+                action, object
             )));
         }
 
-        // Additional validation for specific tools
-        match request.name.as_str() {
-            "compose-up" | "compose-down" => {
-                if let Some(project_dir) = request.arguments.get("project_directory").and_then(|v| v.as_str()) {
-                    // Check if project directory is allowed
-                    if let Some(allowed_projects) = &self.settings.networks.allowed_networks {
-                        if !allowed_projects.contains(project_dir) {
-                            return Err(McpError::OperationNotPermitted(format!(
-                                "Project directory '{}' is not in the allowed list",
-                                project_dir
-                            )));
-                        }
-                    }
-                }
-            }
-            _ => {}
-        }
-
         Ok(())
     }
+}
 
-    pub fn validate_resource(&self, request: &ReadResourceRequest) -> Result<(), McpError> {
-        // Validate container resources
-        if request.uri.starts_with("docker://container/") {
-            // Nothing to validate for now
+/// Derives the object half of the `(subject, object, action)` triple from a
+/// tool call's arguments, following the same `docker://<kind>/<id>` scheme
+/// already used for resource URIs so one policy can cover both.
+fn tool_object(request: &CallToolRequest) -> String {
+    if let Some(id) = request.arguments.get("container_id").and_then(|v| v.as_str()) {
+        return format!("docker://container/{}", id);
+    }
+    if let Some(image) = request.arguments.get("image").and_then(|v| v.as_str()) {
+        return format!("docker://image/{}", image);
+    }
+    if let Some(dir) = request.arguments.get("project_directory").and_then(|v| v.as_str()) {
+        return format!("docker://compose/{}", dir);
+    }
+    "*".to_string()
+}
+
+async fn apply_policy_line(enforcer: &mut Enforcer, line: &str) -> Result<(), McpError> {
+    let fields: Vec<String> = line.split(',').map(|s| s.trim().to_string()).collect();
+
+    match fields.split_first() {
+        Some((kind, rest)) if kind == "p" && (rest.len() == 3 || rest.len() == 4) => {
+            // A line without an effect field is an allow rule.
+            let mut rule = rest.to_vec();
+            if rule.len() == 3 {
+                rule.push("allow".to_string());
+            }
+            enforcer
+                .add_policy(rule)
+                .await
+                .map_err(|e| McpError::InvalidRequest(format!("Invalid policy line '{}': {}", line, e)))?;
+        }
+        Some((kind, rest)) if kind == "g" && rest.len() == 2 => {
+            enforcer
+                .add_grouping_policy(rest.to_vec())
+                .await
+                .map_err(|e| McpError::InvalidRequest(format!("Invalid grouping policy line '{}': {}", line, e)))?;
         }
-        // Validate image resources
-        else if request.uri.starts_with("docker://image/") {
-            let image_id = request.uri.replace("docker://image/", "");
-            
-            // Check if image name contains a registry that's denied
-            for denied in &self.settings.registries.denied_registries {
-                if image_id.starts_with(&format!("{}/", denied)) {
-                    return Err(McpError::OperationNotPermitted(format!(
-                        "Image from registry '{}' is not allowed",
-                        denied
-                    )));
-                }
+        _ => {
+            return Err(McpError::InvalidRequest(format!(
+                "Unrecognized Casbin policy line (expected 'p, sub, obj, act, eft' or 'g, user, role'): {}",
+                line
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+async fn add_policy(enforcer: &mut Enforcer, sub: &str, obj: &str, act: &str, eft: &str) -> Result<(), McpError> {
+    enforcer
+        .add_policy(vec![sub.to_string(), obj.to_string(), act.to_string(), eft.to_string()])
+        .await
+        .map_err(|e| McpError::InvalidRequest(format!("Failed to add policy ({}, {}, {}, {}): {}", sub, obj, act, eft, e)))?;
+    Ok(())
+}
+
+/// Auto-translates the legacy `HashSet<String>` allow/deny lists in
+/// `SecuritySettings` into equivalent Casbin policies, so existing configs
+/// keep working unchanged on top of the new engine.
+async fn translate_legacy_lists(enforcer: &mut Enforcer, settings: &SecuritySettings) -> Result<(), McpError> {
+    match &settings.commands.allowed_commands {
+        Some(allowed) => {
+            for cmd in allowed {
+                add_policy(enforcer, "*", "*", cmd, "allow").await?;
             }
-            
-            // Check if it's in the denied base images list
-            for denied in &self.settings.registries.denied_base_images {
-                if image_id == *denied {
-                    return Err(McpError::OperationNotPermitted(format!(
-                        "Base image '{}' is not allowed",
-                        denied
-                    )));
-                }
+        }
+        None => add_policy(enforcer, "*", "*", "*", "allow").await?,
+    }
+    for cmd in &settings.commands.denied_commands {
+        add_policy(enforcer, "*", "*", cmd, "deny").await?;
+    }
+
+    match &settings.registries.allowed_registries {
+        Some(allowed) => {
+            for registry in allowed {
+                add_policy(enforcer, "*", &format!("docker://image/{}/*", registry), "read", "allow").await?;
             }
         }
-        // Validate compose resources
-        else if request.uri.starts_with("docker://compose/") {
-            let project_dir = request.uri.replace("docker://compose/", "");
-            
-            // Check if project directory is allowed
-            if let Some(allowed_projects) = &self.settings.networks.allowed_networks {
-                if !allowed_projects.contains(&project_dir) {
-                    return Err(McpError::OperationNotPermitted(format!(
-                        "Project directory '{}' is not in the allowed list",
-                        project_dir
-                    )));
-                }
+        None => add_policy(enforcer, "*", "docker://image/*", "read", "allow").await?,
+    }
+    for registry in &settings.registries.denied_registries {
+        add_policy(enforcer, "*", &format!("docker://image/{}/*", registry), "read", "deny").await?;
+    }
+
+    if let Some(allowed) = &settings.registries.allowed_base_images {
+        for image in allowed {
+            add_policy(enforcer, "*", &format!("docker://image/{}", image), "read", "allow").await?;
+        }
+    }
+    for image in &settings.registries.denied_base_images {
+        add_policy(enforcer, "*", &format!("docker://image/{}", image), "read", "deny").await?;
+    }
+
+    match &settings.networks.allowed_networks {
+        Some(allowed) => {
+            for dir in allowed {
+                add_policy(enforcer, "*", &format!("docker://compose/{}", dir), "*", "allow").await?;
             }
         }
+        None => add_policy(enforcer, "*", "docker://compose/*", "*", "allow").await?,
+    }
+    for dir in &settings.networks.denied_networks {
+        add_policy(enforcer, "*", &format!("docker://compose/{}", dir), "*", "deny").await?;
+    }
 
-        Ok(())
+    // Volume mounts aren't modeled as their own object kind yet (nothing
+    // resolves a `docker://volume/...` URI today), but translate them so
+    // the policies exist once that plumbing lands.
+    match &settings.volumes.allowed_mounts {
+        Some(allowed) => {
+            for mount in allowed {
+                add_policy(enforcer, "*", &format!("docker://volume/{}", mount), "*", "allow").await?;
+            }
+        }
+        None => add_policy(enforcer, "*", "docker://volume/*", "*", "allow").await?,
+    }
+    for mount in &settings.volumes.denied_mounts {
+        add_policy(enforcer, "*", &format!("docker://volume/{}", mount), "*", "deny").await?;
     }
-}
\ No newline at end of file
+
+    Ok(())
+}
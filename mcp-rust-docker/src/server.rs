@@ -1,92 +1,412 @@
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, mpsc, RwLock};
 
 use crate::config::types::ServerConfig;
-use crate::docker::{DockerClient, DockerClientImpl};
+use crate::docker::{DockerBackend, DockerClient};
 use crate::protocol::error::McpError;
 use crate::protocol::types::{
-    CallToolRequest, GetPromptRequest, GetPromptResult, JsonRpcId, JsonRpcRequest,
+    CallToolRequest, GetPromptRequest, GetPromptResult, JsonRpcId, JsonRpcNotification, JsonRpcRequest,
     JsonRpcResponse, ListPromptsResult, ListResourcesResult, ListToolsResult, Prompt, ReadResourceRequest,
-    ReadResourceResult, Resource, ResourceContent, ServerCapabilities, ServerInfo, Tool,
+    ReadResourceResult, Resource, ResourceContent, ServerCapabilities, ServerInfo, SubscribeResourceRequest,
+    Tool, UnsubscribeResourceRequest,
 };
 use crate::security::{RateLimiter, SecurityValidator};
 use crate::logging::ErrorLogger;
 
+/// Capacity of the server-initiated notification broadcast channel. Every
+/// transport subscribes its own receiver; slow subscribers only risk
+/// lagging (and being told so via `RecvError::Lagged`), never blocking
+/// the publisher.
+const NOTIFICATION_CHANNEL_CAPACITY: usize = 256;
+
 pub struct McpServer {
     config: ServerConfig,
-    docker_client: Arc<DockerClientImpl>,
+    docker_client: Arc<DockerBackend>,
+    // Every named endpoint in `config.docker.connections` besides the
+    // default one above, keyed the same way `tools/call`'s `connection`
+    // argument names them. Never mutated after startup today, but held
+    // behind a lock rather than a plain `HashMap` so a future "register a
+    // connection at runtime" tool doesn't need a field type change.
+    connections: Arc<RwLock<HashMap<String, Arc<DockerBackend>>>>,
+    // Scheduling weight and concurrency cap for every endpoint (`"default"`
+    // plus each key of `connections`), built once at startup from
+    // `DockerSettings::speed`/`max_jobs` and each `DockerConnectionSettings`.
+    // `resolve_docker` uses these to pick a target endpoint when a call
+    // doesn't name one explicitly.
+    endpoint_weights: HashMap<String, EndpointWeight>,
+    // Which endpoint last served a given container/compose project, keyed
+    // `"container:<id>"`/`"project:<dir>"`. Populated as calls naming one
+    // resolve, so a container/project started on (or ever successfully
+    // reached through) a given endpoint keeps being routed there instead of
+    // bouncing between endpoints on every call.
+    resource_owners: Arc<RwLock<HashMap<String, String>>>,
     tools: Arc<RwLock<HashMap<String, Tool>>>,
     resources: Arc<RwLock<HashMap<String, Resource>>>,
     prompts: Arc<RwLock<HashMap<String, Prompt>>>,
     security_validator: Arc<SecurityValidator>,
     rate_limiter: Arc<RateLimiter>,
+    notification_tx: broadcast::Sender<JsonRpcNotification>,
+    subscriptions: Arc<RwLock<HashMap<String, tokio::task::AbortHandle>>>,
+    exec_sessions: Arc<RwLock<HashMap<String, ExecSession>>>,
+    // `resources/subscribe` registry, keyed by resource URI. Every
+    // transport shares one `notification_tx`, so there's no per-connection
+    // identity to key a "URI + subscriber" pair on; subscriber_count instead
+    // tracks how many `resources/subscribe` calls are outstanding for that
+    // URI, and the tailing task is only aborted once the count drops to 0.
+    resource_subscriptions: Arc<RwLock<HashMap<String, ResourceSubscription>>>,
+    // Per-container mount table for `docker::paths` translation, fetched
+    // once via `DockerBackend::get_container_mounts` and cached from then
+    // on — a container's mounts are fixed for its lifetime, so there's
+    // nothing to invalidate short of the container being recreated under
+    // the same id.
+    mount_cache: Arc<RwLock<HashMap<String, Vec<crate::docker::MountInfo>>>>,
+    // Direct OCI runtime backend (`oci/state`, `oci/create`, ...), active
+    // only when `ServerConfig::oci` is configured; `None` otherwise, in
+    // which case the `oci/*` tools return an error rather than being
+    // omitted from the tool list (so clients can discover them regardless
+    // of whether this particular server has a runtime configured).
+    oci_runtime: Option<Arc<crate::oci::OciRuntime>>,
+    // Self-healing subsystem that restarts labeled containers stuck
+    // `unhealthy` past a grace period; `None` unless
+    // `DockerSettings::health_watcher.enabled` is set. Kept alive for the
+    // server's lifetime purely by being held here — dropping it (e.g. on
+    // shutdown) aborts the background task.
+    health_watcher: Option<crate::health_watcher::HealthWatcher>,
+    // Per-command (JSON-RPC method, or tool name for `tools/call`) call
+    // counters and latency, fed from `process_request` around every
+    // dispatch; what the `server-commands` tool reports.
+    command_stats: Arc<RwLock<HashMap<String, CommandStat>>>,
+    // Requests currently being dispatched, keyed the same way `ErrorLogger`
+    // keys its own request-id logging. Populated at the start of
+    // `process_request` and removed once it returns; `server-requests`
+    // lists this, and `server-cancel` fires the stored `CancellationToken`
+    // to signal one of these to stop.
+    in_flight: Arc<RwLock<HashMap<String, InFlightRequest>>>,
+    // Set by `ShutdownHandle::begin_shutdown` once a termination signal
+    // arrives; `dispatch_request` checks this to reject new `tools/call`
+    // requests while already-accepted ones keep running. Shared with every
+    // `ShutdownHandle` cloned off `shutdown_handle()`, so the flag is the
+    // same one the signal task flips regardless of which handle sees it.
+    draining: Arc<std::sync::atomic::AtomicBool>,
+    // Resolves once draining completes (in-flight count hit zero, or
+    // `ServerSettings::shutdown_grace` elapsed); `main` selects on
+    // `ShutdownHandle::wait_for_shutdown` against the transport's `run()`
+    // future so the process exits promptly after a signal instead of
+    // waiting on transports that block forever accepting new connections.
+    shutdown_tx: broadcast::Sender<()>,
+    // `(connection, project_directory)` of every compose project
+    // successfully brought up through the `compose-up` tool, in the order
+    // they were started; an explicit `compose-down` for one removes it
+    // again. Only consulted by `ShutdownHandle::begin_shutdown` when
+    // `ServerSettings::cleanup_on_exit` is set, and by `run_diagnostic`'s
+    // planned-cleanup-set report — tracked unconditionally either way, since
+    // a project a client brought up before `cleanup_on_exit` was flipped on
+    // mid-run should still be covered.
+    managed_compose_projects: Arc<RwLock<Vec<(String, String)>>>,
+}
+
+// One command's (JSON-RPC method, or tool name) running totals;
+// `server-commands` derives mean duration as `total_duration_ms / calls`.
+#[derive(Default, Clone)]
+struct CommandStat {
+    calls: u64,
+    errors: u64,
+    total_duration_ms: u64,
+    max_duration_ms: u64,
+}
+
+struct InFlightRequest {
+    command: String,
+    started_at: std::time::Instant,
+    cancel: tokio_util::sync::CancellationToken,
+}
+
+struct ResourceSubscription {
+    handle: tokio::task::AbortHandle,
+    subscriber_count: usize,
+}
+
+/// One endpoint's scheduling inputs: `speed` breaks ties between endpoints
+/// that both have a free job slot, and `semaphore` (sized to `max_jobs`)
+/// is what "free job slot" means — a permit acquired before dispatch and
+/// held for its duration, so `available_permits()` is always this
+/// endpoint's current spare capacity.
+struct EndpointWeight {
+    speed: f64,
+    max_jobs: usize,
+    semaphore: Arc<tokio::sync::Semaphore>,
+}
+
+/// Cloneable handle to a server's graceful-shutdown state, obtained via
+/// [`McpServer::shutdown_handle`] before the server is handed off to a
+/// transport. Lets `main` install signal handlers and wait for shutdown
+/// to complete without holding onto the server itself.
+#[derive(Clone)]
+pub struct ShutdownHandle {
+    draining: Arc<std::sync::atomic::AtomicBool>,
+    in_flight: Arc<RwLock<HashMap<String, InFlightRequest>>>,
+    shutdown_tx: broadcast::Sender<()>,
+    grace: std::time::Duration,
+    // Resources needed to tear down server-managed compose projects once
+    // draining finishes; `None` when `ServerSettings::cleanup_on_exit` is
+    // off, in which case `begin_shutdown` skips the cleanup step entirely.
+    cleanup: Option<ShutdownCleanup>,
+}
+
+#[derive(Clone)]
+struct ShutdownCleanup {
+    docker_client: Arc<DockerBackend>,
+    connections: Arc<RwLock<HashMap<String, Arc<DockerBackend>>>>,
+    managed_compose_projects: Arc<RwLock<Vec<(String, String)>>>,
+}
+
+impl ShutdownHandle {
+    /// Flips the draining flag (a no-op if already set), waits for every
+    /// in-flight request to finish or `grace` to elapse, whichever comes
+    /// first, then resolves `wait_for_shutdown` for every waiter.
+    pub async fn begin_shutdown(&self) {
+        if self.draining.swap(true, std::sync::atomic::Ordering::SeqCst) {
+            return;
+        }
+        log::info!("Graceful shutdown requested; draining in-flight requests");
+
+        let deadline = tokio::time::Instant::now() + self.grace;
+        loop {
+            let remaining = self.in_flight.read().await.len();
+            if remaining == 0 {
+                break;
+            }
+            if tokio::time::Instant::now() >= deadline {
+                log::warn!(
+                    "Shutdown grace period ({:?}) elapsed with {} request(s) still in flight",
+                    self.grace,
+                    remaining
+                );
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        }
+
+        if let Some(cleanup) = &self.cleanup {
+            self.tear_down_managed_projects(cleanup).await;
+        }
+
+        let _ = self.shutdown_tx.send(());
+    }
+
+    /// Tears down every tracked compose project in reverse start order (the
+    /// last project brought up is the first torn down, the same LIFO
+    /// ordering `compose_down` applies to services within one project) via
+    /// the same `compose_down` each project's own teardown already goes
+    /// through `docker/compose`'s container-level stop-then-force-remove.
+    /// Bounded by `grace` overall so a project whose daemon has gone
+    /// unresponsive can't block process exit indefinitely; whatever hasn't
+    /// torn down by then is left for the next `compose-down` or `docker
+    /// system prune` to catch.
+    async fn tear_down_managed_projects(&self, cleanup: &ShutdownCleanup) {
+        let projects: Vec<(String, String)> = {
+            let mut projects = cleanup.managed_compose_projects.write().await;
+            std::mem::take(&mut *projects).into_iter().rev().collect()
+        };
+
+        if projects.is_empty() {
+            return;
+        }
+
+        log::info!("Tearing down {} server-managed compose project(s)", projects.len());
+        let teardown = async {
+            for (connection, project_directory) in &projects {
+                let docker = if connection == "default" {
+                    Some(cleanup.docker_client.clone())
+                } else {
+                    cleanup.connections.read().await.get(connection).cloned()
+                };
+                let Some(docker) = docker else {
+                    log::warn!("Skipping managed compose project '{}': connection '{}' no longer exists", project_directory, connection);
+                    continue;
+                };
+
+                let args = serde_json::json!({ "project_directory": project_directory });
+                match docker.compose_down(args).await {
+                    Ok(_) => log::info!("Tore down managed compose project '{}'", project_directory),
+                    Err(e) => log::warn!("Failed to tear down managed compose project '{}': {}", project_directory, e),
+                }
+            }
+        };
+
+        if tokio::time::timeout(self.grace, teardown).await.is_err() {
+            log::warn!("Shutdown grace period ({:?}) elapsed before all managed compose projects were torn down", self.grace);
+        }
+    }
+
+    /// Resolves once `begin_shutdown` has finished draining. Never resolves
+    /// if shutdown is never requested, so callers should race it against
+    /// other work (e.g. a transport's `run()` future) rather than awaiting
+    /// it alone.
+    pub async fn wait_for_shutdown(&self) {
+        let _ = self.shutdown_tx.subscribe().recv().await;
+    }
+
+    /// Installs SIGTERM/SIGINT handlers on Unix (via `tokio::signal::unix`'s
+    /// signal registry) or a `Ctrl-C` handler elsewhere, and spawns a task
+    /// that calls `begin_shutdown` the first time one fires.
+    pub fn install_signal_handlers(self) {
+        tokio::spawn(async move {
+            #[cfg(unix)]
+            {
+                use tokio::signal::unix::{signal, SignalKind};
+
+                let mut sigterm = match signal(SignalKind::terminate()) {
+                    Ok(s) => s,
+                    Err(e) => {
+                        log::error!("Failed to install SIGTERM handler: {}", e);
+                        return;
+                    }
+                };
+                let mut sigint = match signal(SignalKind::interrupt()) {
+                    Ok(s) => s,
+                    Err(e) => {
+                        log::error!("Failed to install SIGINT handler: {}", e);
+                        return;
+                    }
+                };
+
+                tokio::select! {
+                    _ = sigterm.recv() => log::info!("Received SIGTERM"),
+                    _ = sigint.recv() => log::info!("Received SIGINT"),
+                }
+            }
+            #[cfg(not(unix))]
+            {
+                if let Err(e) = tokio::signal::ctrl_c().await {
+                    log::error!("Failed to install Ctrl-C handler: {}", e);
+                    return;
+                }
+                log::info!("Received Ctrl-C");
+            }
+
+            self.begin_shutdown().await;
+        });
+    }
+}
+
+/// A running `docker/exec/start` session: the channel used to forward
+/// `docker/exec/stdin` bytes, plus the exec id needed to resize its PTY
+/// (`docker/exec/resize`) once it's running.
+struct ExecSession {
+    stdin_tx: mpsc::Sender<Vec<u8>>,
+    exec_id: String,
+    backend: Arc<DockerBackend>,
 }
 
 impl McpServer {
-    pub fn new(config: ServerConfig) -> Self {
-        let docker_client = Arc::new(DockerClientImpl::new(&config.docker));
-        let security_validator = Arc::new(SecurityValidator::new(&config.security));
+    /// Builds the server, loading the Casbin policy engine (model, policy
+    /// file, and legacy allow/deny lists translated into policies) once up
+    /// front so a bad model or policy fails fast at startup rather than on
+    /// the first request that happens to touch it.
+    pub async fn new(config: &ServerConfig) -> Result<Self, McpError> {
+        let docker_client = Arc::new(DockerBackend::new(&config.docker).await?);
+        let security_validator = Arc::new(SecurityValidator::new(&config.security).await?);
         let rate_limiter = Arc::new(RateLimiter::new(&config.security.rate_limiting));
+        let (notification_tx, _) = broadcast::channel(NOTIFICATION_CHANNEL_CAPACITY);
+        let oci_runtime = config.oci.clone().map(|settings| Arc::new(crate::oci::OciRuntime::new(settings)));
+
+        // Every extra named endpoint in `docker.connections` gets its own
+        // `DockerBackend`, built up front (same as the default connection)
+        // so a bad one is caught at startup rather than on its first
+        // `tools/call`.
+        let mut connections = HashMap::new();
+        let mut endpoint_weights = HashMap::new();
+        endpoint_weights.insert(
+            "default".to_string(),
+            EndpointWeight {
+                speed: config.docker.speed,
+                max_jobs: config.docker.max_jobs,
+                semaphore: Arc::new(tokio::sync::Semaphore::new(config.docker.max_jobs)),
+            },
+        );
+        for (name, conn_settings) in &config.docker.connections {
+            let resolved = conn_settings.resolve(&config.docker);
+            connections.insert(name.clone(), Arc::new(DockerBackend::new(&resolved).await?));
+            endpoint_weights.insert(
+                name.clone(),
+                EndpointWeight {
+                    speed: conn_settings.speed,
+                    max_jobs: conn_settings.max_jobs,
+                    semaphore: Arc::new(tokio::sync::Semaphore::new(conn_settings.max_jobs)),
+                },
+            );
+        }
+
+        let health_watcher = config.docker.health_watcher.enabled.then(|| {
+            crate::health_watcher::HealthWatcher::start(docker_client.clone(), config.docker.health_watcher.clone())
+        });
 
-        Self {
-            config,
+        Ok(Self {
+            config: config.clone(),
             docker_client,
+            connections: Arc::new(RwLock::new(connections)),
+            endpoint_weights,
+            resource_owners: Arc::new(RwLock::new(HashMap::new())),
             tools: Arc::new(RwLock::new(HashMap::new())),
             resources: Arc::new(RwLock::new(HashMap::new())),
             prompts: Arc::new(RwLock::new(HashMap::new())),
             security_validator,
             rate_limiter,
+            notification_tx,
+            subscriptions: Arc::new(RwLock::new(HashMap::new())),
+            resource_subscriptions: Arc::new(RwLock::new(HashMap::new())),
+            exec_sessions: Arc::new(RwLock::new(HashMap::new())),
+            mount_cache: Arc::new(RwLock::new(HashMap::new())),
+            oci_runtime,
+            health_watcher,
+            command_stats: Arc::new(RwLock::new(HashMap::new())),
+            in_flight: Arc::new(RwLock::new(HashMap::new())),
+            draining: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            shutdown_tx: broadcast::channel(1).0,
+            managed_compose_projects: Arc::new(RwLock::new(Vec::new())),
+        })
+    }
+
+    /// Hands out a cheap, cloneable handle to this server's shutdown state,
+    /// meant to be taken before the server itself is moved into a transport
+    /// (`StdioTransport::new` and friends all consume `McpServer` by value).
+    /// `main` uses it to install signal handlers and race the transport's
+    /// `run()` future against graceful shutdown.
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        ShutdownHandle {
+            draining: self.draining.clone(),
+            in_flight: self.in_flight.clone(),
+            shutdown_tx: self.shutdown_tx.clone(),
+            grace: self.config.server.shutdown_grace,
+            cleanup: self.config.server.cleanup_on_exit.then(|| ShutdownCleanup {
+                docker_client: self.docker_client.clone(),
+                connections: self.connections.clone(),
+                managed_compose_projects: self.managed_compose_projects.clone(),
+            }),
         }
     }
-    // Add this method to improve error logging
-    fn log_request(&self, request: &JsonRpcRequest, response: &JsonRpcResponse) {
-        let id = match &request.id {
-            JsonRpcId::Null => "null".to_string(),
-            JsonRpcId::String(s) => s.clone(),
-            JsonRpcId::Number(n) => n.to_string(),
-        };
-        
-        let success = response.error.is_none();
-        let error_code = response.error.as_ref().map(|e| e.code);
-        let error_message = response.error.as_ref().map(|e| e.message.as_str());
-        
-        ErrorLogger::log_request_end(&id, &request.method, success, error_code, error_message);
+
+    fn is_draining(&self) -> bool {
+        self.draining.load(std::sync::atomic::Ordering::SeqCst)
     }
-    
-    // Modify your existing process_request method to add logging
-    pub async fn process_request(&self, request: JsonRpcRequest) -> JsonRpcResponse {
-        // Log request start
-        let id_str = match &request.id {
-            JsonRpcId::Null => "null".to_string(),
-            JsonRpcId::String(s) => s.clone(),
-            JsonRpcId::Number(n) => n.to_string(),
-        };
-        
-        ErrorLogger::log_request_start(&id_str, &request.method);
-        
-        // Apply rate limiting
-        if let Err(e) = self.rate_limiter.check() {
-            let response = self.error_response(request.id, e);
-            self.log_request(&request, &response);
-            return response;
-        }
 
-        // Your existing match block for request.method.as_str()...
-        let response = match request.method.as_str() {
-            // Your existing handlers...
-            _ => self.error_response(
-                request.id,
-                McpError::MethodNotFound(format!("Method '{}' not found", request.method)),
-            ),
-        };
-        
-        // Log request completion
-        self.log_request(&request, &response);
-        
-        response
+    /// Stops the health watcher (if running) ahead of server shutdown.
+    /// Idempotent: calling this when the watcher isn't enabled, or more
+    /// than once, is a no-op.
+    pub fn stop_health_watcher(&self) {
+        if let Some(watcher) = &self.health_watcher {
+            watcher.stop();
+        }
     }
 
+    /// Subscribes to every notification the server emits from here on
+    /// (log lines, Docker events, resource updates, ...). Transports call
+    /// this once and interleave whatever arrives with normal responses.
+    pub fn subscribe_notifications(&self) -> broadcast::Receiver<JsonRpcNotification> {
+        self.notification_tx.subscribe()
+    }
     pub fn get_transport_type(&self) -> &crate::config::types::TransportType {
         &self.config.server.transport
     }
@@ -94,6 +414,22 @@ impl McpServer {
     pub fn get_request_timeout(&self) -> std::time::Duration {
         self.config.server.request_timeout
     }
+
+    pub fn get_bind_address(&self) -> &str {
+        &self.config.server.bind_address
+    }
+
+    pub fn get_bind_port(&self) -> u16 {
+        self.config.server.bind_port
+    }
+
+    pub fn get_max_in_flight(&self) -> usize {
+        self.config.server.max_in_flight
+    }
+
+    pub fn get_tls_settings(&self) -> Option<&crate::config::types::TlsSettings> {
+        self.config.server.tls.as_ref()
+    }
     
     // Add a diagnostic tool to help with debugging
     async fn register_diagnostic_tool(&self, tools: &mut std::collections::HashMap<String, crate::protocol::types::Tool>) {
@@ -121,6 +457,57 @@ impl McpServer {
                 }),
             },
         );
+
+        tools.insert(
+            "server-commands".to_string(),
+            crate::protocol::types::Tool {
+                name: "server-commands".to_string(),
+                description: Some(
+                    "List every registered method/tool this server has handled, with its call \
+                     count, error count, and mean/max duration"
+                        .to_string(),
+                ),
+                input_schema: serde_json::json!({
+                    "type": "object",
+                    "properties": {}
+                }),
+            },
+        );
+
+        tools.insert(
+            "server-requests".to_string(),
+            crate::protocol::types::Tool {
+                name: "server-requests".to_string(),
+                description: Some("List requests currently being dispatched, with elapsed time".to_string()),
+                input_schema: serde_json::json!({
+                    "type": "object",
+                    "properties": {}
+                }),
+            },
+        );
+
+        tools.insert(
+            "server-cancel".to_string(),
+            crate::protocol::types::Tool {
+                name: "server-cancel".to_string(),
+                description: Some(
+                    "Signal cancellation of an in-flight request by its JSON-RPC id (see \
+                     `server-requests`); whether the request actually stops depends on whether \
+                     the operation it's running checks its CancellationToken"
+                        .to_string(),
+                ),
+                input_schema: serde_json::json!({
+                    "type": "object",
+                    "required": ["id"],
+                    "properties": {
+                        "id": {
+                            "type": "string",
+                            "description": "JSON-RPC request id to cancel, as reported by server-requests"
+                        }
+                    }
+                }),
+            },
+        );
     }
 
     pub async fn initialize(&self) -> Result<(), crate::protocol::error::McpError> {
@@ -146,6 +533,10 @@ impl McpServer {
                         "filter": {
                             "type": "string",
                             "description": "Filter output based on conditions provided"
+                        },
+                        "connection": {
+                            "type": "string",
+                            "description": "Named Docker connection to run against (see DockerSettings::connections); omitted or \"default\" uses the default connection"
                         }
                     }
                 }),
@@ -164,6 +555,10 @@ impl McpServer {
                         "container_id": {
                             "type": "string",
                             "description": "Container ID or name to start"
+                        },
+                        "connection": {
+                            "type": "string",
+                            "description": "Named Docker connection to run against (see DockerSettings::connections); omitted or \"default\" uses the default connection"
                         }
                     }
                 }),
@@ -186,6 +581,10 @@ impl McpServer {
                         "timeout": {
                             "type": "integer",
                             "description": "Seconds to wait for stop before killing it (default 10)"
+                        },
+                        "connection": {
+                            "type": "string",
+                            "description": "Named Docker connection to run against (see DockerSettings::connections); omitted or \"default\" uses the default connection"
                         }
                     }
                 }),
@@ -212,6 +611,50 @@ impl McpServer {
                         "since": {
                             "type": "string",
                             "description": "Show logs since timestamp (e.g., '2013-01-02T13:23:37Z') or relative (e.g., '42m' for 42 minutes)"
+                        },
+                        "connection": {
+                            "type": "string",
+                            "description": "Named Docker connection to run against (see DockerSettings::connections); omitted or \"default\" uses the default connection"
+                        }
+                    }
+                }),
+            },
+        );
+
+        tools.insert(
+            "container-exec".to_string(),
+            Tool {
+                name: "container-exec".to_string(),
+                description: Some("Run a command inside a running container and return its combined output".to_string()),
+                input_schema: serde_json::json!({
+                    "type": "object",
+                    "required": ["container_id", "cmd"],
+                    "properties": {
+                        "container_id": {
+                            "type": "string",
+                            "description": "Container ID or name to run the command in"
+                        },
+                        "cmd": {
+                            "type": "array",
+                            "items": { "type": "string" },
+                            "description": "Command and arguments to run, e.g. [\"ls\", \"-la\"]"
+                        },
+                        "working_dir": {
+                            "type": "string",
+                            "description": "Working directory inside the container to run the command from"
+                        },
+                        "env": {
+                            "type": "array",
+                            "items": { "type": "string" },
+                            "description": "Extra environment variables for the command, as \"KEY=VALUE\" entries"
+                        },
+                        "tty": {
+                            "type": "boolean",
+                            "description": "Allocate a pseudo-TTY for the command (default false)"
+                        },
+                        "connection": {
+                            "type": "string",
+                            "description": "Named Docker connection to run against (see DockerSettings::connections); omitted or \"default\" uses the default connection"
                         }
                     }
                 }),
@@ -234,6 +677,47 @@ impl McpServer {
                         "filter": {
                             "type": "string",
                             "description": "Filter output based on conditions provided"
+                        },
+                        "connection": {
+                            "type": "string",
+                            "description": "Named Docker connection to run against (see DockerSettings::connections); omitted or \"default\" uses the default connection"
+                        }
+                    }
+                }),
+            },
+        );
+
+        tools.insert(
+            "image-build".to_string(),
+            Tool {
+                name: "image-build".to_string(),
+                description: Some(
+                    "Build an image from an inline Dockerfile or a base64-encoded tar build context".to_string(),
+                ),
+                input_schema: serde_json::json!({
+                    "type": "object",
+                    "required": ["tag"],
+                    "properties": {
+                        "tag": {
+                            "type": "string",
+                            "description": "Tag to apply to the built image, e.g. \"myapp:latest\""
+                        },
+                        "dockerfile": {
+                            "type": "string",
+                            "description": "Inline Dockerfile contents, wrapped into an in-memory build context; mutually exclusive with context_tar"
+                        },
+                        "context_tar": {
+                            "type": "string",
+                            "description": "Base64-encoded tar archive to use as the build context; mutually exclusive with dockerfile"
+                        },
+                        "build_args": {
+                            "type": "object",
+                            "description": "Build-time variables passed to the build, e.g. {\"VERSION\": \"1.2.3\"}",
+                            "additionalProperties": { "type": "string" }
+                        },
+                        "connection": {
+                            "type": "string",
+                            "description": "Named Docker connection to run against (see DockerSettings::connections); omitted or \"default\" uses the default connection"
                         }
                     }
                 }),
@@ -264,6 +748,10 @@ impl McpServer {
                                 "type": "string"
                             },
                             "description": "Specific services to start (default: all services)"
+                        },
+                        "connection": {
+                            "type": "string",
+                            "description": "Named Docker connection to run against (see DockerSettings::connections); omitted or \"default\" uses the default connection"
                         }
                     }
                 }),
@@ -291,6 +779,10 @@ impl McpServer {
                             "type": "string",
                             "enum": ["all", "local"],
                             "description": "Remove images, 'all': remove all images, 'local': remove only images without a tag"
+                        },
+                        "connection": {
+                            "type": "string",
+                            "description": "Named Docker connection to run against (see DockerSettings::connections); omitted or \"default\" uses the default connection"
                         }
                     }
                 }),
@@ -301,7 +793,13 @@ impl McpServer {
             "validate-compose".to_string(),
             Tool {
                 name: "validate-compose".to_string(),
-                description: Some("Validate a Docker Compose file".to_string()),
+                description: Some(
+                    "Validate a Docker Compose file: schema/structural checks (missing image, undefined or \
+                     cyclic depends_on) plus semantic lints (undeclared volumes, duplicate published host \
+                     ports, images pinned to latest). Returns every violation found, each with its path \
+                     (e.g. services.web.ports[0]), the rule broken, and a human-readable message."
+                        .to_string(),
+                ),
                 input_schema: serde_json::json!({
                     "type": "object",
                     "required": ["compose_content"],
@@ -309,185 +807,1427 @@ impl McpServer {
                         "compose_content": {
                             "type": "string",
                             "description": "Content of the docker-compose.yml file to validate"
+                        },
+                        "connection": {
+                            "type": "string",
+                            "description": "Named Docker connection to run against (see DockerSettings::connections); omitted or \"default\" uses the default connection"
                         }
                     }
                 }),
             },
         );
 
-        // Register resources
-        let mut resources = self.resources.write().await;
-
-        resources.insert(
-            "docker://info".to_string(),
-            Resource {
-                uri: "docker://info".to_string(),
-                name: "Docker Info".to_string(),
-                description: Some("Information about the Docker host system".to_string()),
-                mime_type: Some("application/json".to_string()),
-                text: None,
-                blob: None,
+        // Volume tools
+        tools.insert(
+            "list-volumes".to_string(),
+            Tool {
+                name: "list-volumes".to_string(),
+                description: Some("List Docker volumes".to_string()),
+                input_schema: serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "filter": {
+                            "type": "string",
+                            "description": "Filter output based on conditions provided, e.g. \"label=mcp.volume.owner=mcp-server\""
+                        },
+                        "connection": {
+                            "type": "string",
+                            "description": "Named Docker connection to run against (see DockerSettings::connections); omitted or \"default\" uses the default connection"
+                        }
+                    }
+                }),
             },
         );
 
-        resources.insert(
-            "docker://version".to_string(),
-            Resource {
-                uri: "docker://version".to_string(),
-                name: "Docker Version".to_string(),
-                description: Some("Docker version information".to_string()),
-                mime_type: Some("application/json".to_string()),
-                text: None,
-                blob: None,
+        tools.insert(
+            "create-volume".to_string(),
+            Tool {
+                name: "create-volume".to_string(),
+                description: Some(
+                    "Create a Docker volume, tagged with this server's ownership label so it can later be \
+                     found by list-volumes/prune-volumes without risking unrelated volumes on the host"
+                        .to_string(),
+                ),
+                input_schema: serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "name": {
+                            "type": "string",
+                            "description": "Volume name (default: daemon-assigned random name)"
+                        },
+                        "driver": {
+                            "type": "string",
+                            "description": "Volume driver to use (default: \"local\")"
+                        },
+                        "labels": {
+                            "type": "object",
+                            "description": "Extra labels to apply, e.g. {\"project\": \"myapp\"}",
+                            "additionalProperties": { "type": "string" }
+                        },
+                        "connection": {
+                            "type": "string",
+                            "description": "Named Docker connection to run against (see DockerSettings::connections); omitted or \"default\" uses the default connection"
+                        }
+                    }
+                }),
             },
         );
 
-        // Register prompts
-        let mut prompts = self.prompts.write().await;
+        tools.insert(
+            "remove-volume".to_string(),
+            Tool {
+                name: "remove-volume".to_string(),
+                description: Some("Remove a Docker volume".to_string()),
+                input_schema: serde_json::json!({
+                    "type": "object",
+                    "required": ["name"],
+                    "properties": {
+                        "name": {
+                            "type": "string",
+                            "description": "Volume name to remove"
+                        },
+                        "force": {
+                            "type": "boolean",
+                            "description": "Force removal even if the volume is in use (default false)"
+                        },
+                        "connection": {
+                            "type": "string",
+                            "description": "Named Docker connection to run against (see DockerSettings::connections); omitted or \"default\" uses the default connection"
+                        }
+                    }
+                }),
+            },
+        );
 
-        prompts.insert(
-            "generate-dockerfile".to_string(),
-            Prompt {
-                name: "generate-dockerfile".to_string(),
-                description: Some("Generate an optimized Dockerfile for a specific application type".to_string()),
-                arguments: vec![
-                    crate::protocol::types::PromptArgument {
-                        name: "app_type".to_string(),
-                        description: Some("Type of application (e.g., nodejs, python, go, rust)".to_string()),
-                        required: true,
-                    },
-                    crate::protocol::types::PromptArgument {
-                        name: "version".to_string(),
-                        description: Some("Version of the application runtime".to_string()),
-                        required: false,
-                    },
-                    crate::protocol::types::PromptArgument {
-                        name: "production".to_string(),
-                        description: Some("Whether this is for production use (yes/no)".to_string()),
-                        required: false,
-                    },
-                ],
+        tools.insert(
+            "prune-volumes".to_string(),
+            Tool {
+                name: "prune-volumes".to_string(),
+                description: Some(
+                    "Remove dangling (unattached) volumes. By default only removes volumes carrying this \
+                     server's ownership label; pass all: true to remove every dangling volume on the host \
+                     regardless of origin."
+                        .to_string(),
+                ),
+                input_schema: serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "all": {
+                            "type": "boolean",
+                            "description": "Remove every dangling volume, not just ones this server created (default false)"
+                        },
+                        "connection": {
+                            "type": "string",
+                            "description": "Named Docker connection to run against (see DockerSettings::connections); omitted or \"default\" uses the default connection"
+                        }
+                    }
+                }),
             },
         );
 
-        prompts.insert(
-            "generate-compose".to_string(),
-            Prompt {
-                name: "generate-compose".to_string(),
-                description: Some("Generate a Docker Compose configuration for a specific scenario".to_string()),
-                arguments: vec![
-                    crate::protocol::types::PromptArgument {
-                        name: "scenario".to_string(),
-                        description: Some("Type of scenario (e.g., webapp, database, microservices)".to_string()),
-                        required: true,
-                    },
-                    crate::protocol::types::PromptArgument {
-                        name: "services".to_string(),
-                        description: Some("Comma-separated list of services to include".to_string()),
-                        required: true,
-                    },
-                    crate::protocol::types::PromptArgument {
-                        name: "with_volumes".to_string(),
-                        description: Some("Whether to include persistent volumes (yes/no)".to_string()),
-                        required: false,
-                    },
-                ],
+        tools.insert(
+            "docker-events".to_string(),
+            Tool {
+                name: "docker-events".to_string(),
+                description: Some(
+                    "Collect Docker daemon events over a bounded window, optionally scoped by time range and filters"
+                        .to_string(),
+                ),
+                input_schema: serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "since": {
+                            "type": "string",
+                            "description": "Collect events from this point on: RFC3339 timestamp or relative offset (e.g. '42m', '3h')"
+                        },
+                        "until": {
+                            "type": "string",
+                            "description": "Stop collecting events at this point: RFC3339 timestamp or relative offset (e.g. '42m', '3h')"
+                        },
+                        "filters": {
+                            "type": "object",
+                            "description": "Event filters, e.g. {\"type\": [\"container\"], \"event\": [\"die\"]}",
+                            "additionalProperties": {
+                                "type": "array",
+                                "items": { "type": "string" }
+                            }
+                        },
+                        "connection": {
+                            "type": "string",
+                            "description": "Named Docker connection to run against (see DockerSettings::connections); omitted or \"default\" uses the default connection"
+                        }
+                    }
+                }),
             },
         );
 
-        // Register the diagnostic tool
-        self.register_diagnostic_tool(&mut tools).await;
+        tools.insert(
+            "container-stats".to_string(),
+            Tool {
+                name: "container-stats".to_string(),
+                description: Some(
+                    "Get a one-shot snapshot of a container's CPU, memory, network, and block I/O usage".to_string(),
+                ),
+                input_schema: serde_json::json!({
+                    "type": "object",
+                    "required": ["container_id"],
+                    "properties": {
+                        "container_id": {
+                            "type": "string",
+                            "description": "Container ID or name to get stats for"
+                        },
+                        "stream": {
+                            "type": "boolean",
+                            "description": "Reserved for future use; only one-shot sampling is supported (use docker/stats/subscribe for continuous streaming)"
+                        },
+                        "connection": {
+                            "type": "string",
+                            "description": "Named Docker connection to run against (see DockerSettings::connections); omitted or \"default\" uses the default connection"
+                        }
+                    }
+                }),
+            },
+        );
 
-        Ok(())
+        tools.insert(
+            "wait-for-container".to_string(),
+            Tool {
+                name: "wait-for-container".to_string(),
+                description: Some(
+                    "Block until a container satisfies every given readiness condition or a timeout elapses. \
+                     Returns the container's terminal state (created/running/healthy/exited/dead) and how long \
+                     the wait took; a container that exits or dies while waiting fails immediately instead of \
+                     waiting out the timeout."
+                        .to_string(),
+                ),
+                input_schema: serde_json::json!({
+                    "type": "object",
+                    "required": ["container_id", "conditions"],
+                    "properties": {
+                        "container_id": {
+                            "type": "string",
+                            "description": "Container ID or name to wait on"
+                        },
+                        "conditions": {
+                            "type": "array",
+                            "description": "Conditions that must all hold before the wait succeeds",
+                            "items": {
+                                "type": "object",
+                                "required": ["type"],
+                                "properties": {
+                                    "type": {
+                                        "type": "string",
+                                        "enum": ["healthcheck", "running", "log_match", "port_open"],
+                                        "description": "healthcheck: State.Health.Status == \"healthy\". running: State.Running and not restarting. log_match: `pattern` appears in stdout/stderr. port_open: a TCP connection to `host`:`port` succeeds"
+                                    },
+                                    "pattern": {
+                                        "type": "string",
+                                        "description": "Regex to search for in the container's combined stdout/stderr; required for type \"log_match\""
+                                    },
+                                    "host": {
+                                        "type": "string",
+                                        "description": "Host to connect to for type \"port_open\" (default \"127.0.0.1\")"
+                                    },
+                                    "port": {
+                                        "type": "integer",
+                                        "description": "Port to connect to; required for type \"port_open\""
+                                    }
+                                }
+                            }
+                        },
+                        "timeout_seconds": {
+                            "type": "integer",
+                            "description": "Seconds to wait before giving up (default 30)"
+                        },
+                        "connection": {
+                            "type": "string",
+                            "description": "Named Docker connection to run against (see DockerSettings::connections); omitted or \"default\" uses the default connection"
+                        }
+                    }
+                }),
+            },
+        );
+
+        tools.insert(
+            "container-copy-in".to_string(),
+            Tool {
+                name: "container-copy-in".to_string(),
+                description: Some(
+                    "Copy a base64-encoded tar archive into a running container at the given path".to_string(),
+                ),
+                input_schema: serde_json::json!({
+                    "type": "object",
+                    "required": ["container_id", "path", "tar_base64"],
+                    "properties": {
+                        "container_id": {
+                            "type": "string",
+                            "description": "Container ID or name to copy into"
+                        },
+                        "path": {
+                            "type": "string",
+                            "description": "Destination directory inside the container where the archive is extracted"
+                        },
+                        "tar_base64": {
+                            "type": "string",
+                            "description": "Base64-encoded tar archive to extract at `path`"
+                        },
+                        "connection": {
+                            "type": "string",
+                            "description": "Named Docker connection to run against (see DockerSettings::connections); omitted or \"default\" uses the default connection"
+                        }
+                    }
+                }),
+            },
+        );
+
+        tools.insert(
+            "container-copy-out".to_string(),
+            Tool {
+                name: "container-copy-out".to_string(),
+                description: Some(
+                    "Copy a path out of a running container as a base64-encoded tar archive".to_string(),
+                ),
+                input_schema: serde_json::json!({
+                    "type": "object",
+                    "required": ["container_id", "path"],
+                    "properties": {
+                        "container_id": {
+                            "type": "string",
+                            "description": "Container ID or name to copy from"
+                        },
+                        "path": {
+                            "type": "string",
+                            "description": "File or directory inside the container to archive"
+                        },
+                        "connection": {
+                            "type": "string",
+                            "description": "Named Docker connection to run against (see DockerSettings::connections); omitted or \"default\" uses the default connection"
+                        }
+                    }
+                }),
+            },
+        );
+
+        // Streaming tools: these return immediately with a subscription id
+        // and push further data as `notifications/*` messages rather than
+        // in the tool call's own response.
+        tools.insert(
+            "docker/logs/follow".to_string(),
+            Tool {
+                name: "docker/logs/follow".to_string(),
+                description: Some("Tail a container's logs live, streaming new lines as notifications".to_string()),
+                input_schema: serde_json::json!({
+                    "type": "object",
+                    "required": ["container_id"],
+                    "properties": {
+                        "container_id": {
+                            "type": "string",
+                            "description": "Container ID or name to follow logs from"
+                        },
+                        "connection": {
+                            "type": "string",
+                            "description": "Named Docker connection to run against (see DockerSettings::connections); omitted or \"default\" uses the default connection"
+                        }
+                    }
+                }),
+            },
+        );
+
+        tools.insert(
+            "docker/events/subscribe".to_string(),
+            Tool {
+                name: "docker/events/subscribe".to_string(),
+                description: Some("Subscribe to the Docker daemon's event stream".to_string()),
+                input_schema: serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "filters": {
+                            "type": "object",
+                            "description": "Event filters, e.g. {\"type\": [\"container\"], \"event\": [\"die\"]}",
+                            "additionalProperties": {
+                                "type": "array",
+                                "items": { "type": "string" }
+                            }
+                        },
+                        "connection": {
+                            "type": "string",
+                            "description": "Named Docker connection to run against (see DockerSettings::connections); omitted or \"default\" uses the default connection"
+                        }
+                    }
+                }),
+            },
+        );
+
+        tools.insert(
+            "docker/stats/subscribe".to_string(),
+            Tool {
+                name: "docker/stats/subscribe".to_string(),
+                description: Some(
+                    "Poll a running container's resource usage at a fixed interval and stream each sample \
+                     (CPU percent/total/per-core, memory usage/limit/cache, pids, block I/O) as a \
+                     docker/stats/sample notification, until the container exits or the subscription is cancelled."
+                        .to_string(),
+                ),
+                input_schema: serde_json::json!({
+                    "type": "object",
+                    "required": ["container_id"],
+                    "properties": {
+                        "container_id": {
+                            "type": "string",
+                            "description": "Container ID or name to sample"
+                        },
+                        "interval_ms": {
+                            "type": "integer",
+                            "description": "Milliseconds between samples (default 1000)"
+                        },
+                        "connection": {
+                            "type": "string",
+                            "description": "Named Docker connection to run against (see DockerSettings::connections); omitted or \"default\" uses the default connection"
+                        }
+                    }
+                }),
+            },
+        );
+
+        tools.insert(
+            "resolve_path".to_string(),
+            Tool {
+                name: "resolve_path".to_string(),
+                description: Some(
+                    "Translate a path between a container's filesystem and the host, using the container's \
+                     mount table (bind mounts and volumes). Picks the longest matching mount prefix; a path \
+                     outside every mount is returned unchanged and marked container-only. Tools that take both \
+                     a `container_id` and a `path` argument apply this translation automatically."
+                        .to_string(),
+                ),
+                input_schema: serde_json::json!({
+                    "type": "object",
+                    "required": ["container_id", "path"],
+                    "properties": {
+                        "container_id": {
+                            "type": "string",
+                            "description": "Container ID or name whose mounts to resolve against"
+                        },
+                        "path": {
+                            "type": "string",
+                            "description": "The path to translate"
+                        },
+                        "direction": {
+                            "type": "string",
+                            "enum": ["to_host", "to_container"],
+                            "description": "Which way to translate `path`. Defaults to auto-detecting by \
+                                             trying both directions."
+                        }
+                    }
+                }),
+            },
+        );
+
+        tools.insert(
+            "oci/state".to_string(),
+            Tool {
+                name: "oci/state".to_string(),
+                description: Some(
+                    "Query a container's OCI runtime spec State (ociVersion, id, status, pid, bundle, \
+                     annotations, created) directly from the configured OCI runtime (runc/crun/youki), \
+                     bypassing Docker entirely. Requires the `oci` backend to be configured."
+                        .to_string(),
+                ),
+                input_schema: serde_json::json!({
+                    "type": "object",
+                    "required": ["id"],
+                    "properties": {
+                        "id": { "type": "string", "description": "Container id" }
+                    }
+                }),
+            },
+        );
+
+        tools.insert(
+            "oci/create".to_string(),
+            Tool {
+                name: "oci/create".to_string(),
+                description: Some(
+                    "Create a container from the configured OCI bundle directory via the OCI runtime's \
+                     `create` subcommand. Requires the `oci` backend to be configured."
+                        .to_string(),
+                ),
+                input_schema: serde_json::json!({
+                    "type": "object",
+                    "required": ["id"],
+                    "properties": {
+                        "id": { "type": "string", "description": "Container id to assign" }
+                    }
+                }),
+            },
+        );
+
+        tools.insert(
+            "oci/start".to_string(),
+            Tool {
+                name: "oci/start".to_string(),
+                description: Some(
+                    "Start a previously created container via the OCI runtime's `start` subcommand. \
+                     Requires the `oci` backend to be configured."
+                        .to_string(),
+                ),
+                input_schema: serde_json::json!({
+                    "type": "object",
+                    "required": ["id"],
+                    "properties": {
+                        "id": { "type": "string", "description": "Container id" }
+                    }
+                }),
+            },
+        );
+
+        tools.insert(
+            "oci/kill".to_string(),
+            Tool {
+                name: "oci/kill".to_string(),
+                description: Some(
+                    "Send a signal to a container via the OCI runtime's `kill` subcommand. Requires the \
+                     `oci` backend to be configured."
+                        .to_string(),
+                ),
+                input_schema: serde_json::json!({
+                    "type": "object",
+                    "required": ["id"],
+                    "properties": {
+                        "id": { "type": "string", "description": "Container id" },
+                        "signal": {
+                            "type": "string",
+                            "description": "Signal to send (default SIGTERM)"
+                        }
+                    }
+                }),
+            },
+        );
+
+        tools.insert(
+            "oci/delete".to_string(),
+            Tool {
+                name: "oci/delete".to_string(),
+                description: Some(
+                    "Remove a stopped container's runtime state via the OCI runtime's `delete` subcommand. \
+                     Requires the `oci` backend to be configured."
+                        .to_string(),
+                ),
+                input_schema: serde_json::json!({
+                    "type": "object",
+                    "required": ["id"],
+                    "properties": {
+                        "id": { "type": "string", "description": "Container id" }
+                    }
+                }),
+            },
+        );
+
+        tools.insert(
+            "docker/unsubscribe".to_string(),
+            Tool {
+                name: "docker/unsubscribe".to_string(),
+                description: Some("Cancel a previously created log-follow, event-subscribe, or stats-subscribe subscription".to_string()),
+                input_schema: serde_json::json!({
+                    "type": "object",
+                    "required": ["subscription_id"],
+                    "properties": {
+                        "subscription_id": {
+                            "type": "string",
+                            "description": "Subscription id returned by docker/logs/follow, docker/events/subscribe, docker/stats/subscribe, or docker/exec/start"
+                        }
+                    }
+                }),
+            },
+        );
+
+        tools.insert(
+            "docker/exec/start".to_string(),
+            Tool {
+                name: "docker/exec/start".to_string(),
+                description: Some(
+                    "Run a command inside a running container and stream its stdout/stderr back as notifications. \
+                     Set pty to allocate a pseudo-terminal for an interactive session. \
+                     Returns a subscription id usable with docker/exec/stdin, docker/exec/resize, and docker/unsubscribe."
+                        .to_string(),
+                ),
+                input_schema: serde_json::json!({
+                    "type": "object",
+                    "required": ["container_id", "cmd"],
+                    "properties": {
+                        "container_id": {
+                            "type": "string",
+                            "description": "Container ID or name to run the command in"
+                        },
+                        "cmd": {
+                            "type": "array",
+                            "items": { "type": "string" },
+                            "description": "Command and arguments to execute"
+                        },
+                        "working_dir": {
+                            "type": "string",
+                            "description": "Working directory for the command inside the container"
+                        },
+                        "env": {
+                            "type": "array",
+                            "items": { "type": "string" },
+                            "description": "Environment variables in KEY=VALUE form"
+                        },
+                        "pty": {
+                            "type": "boolean",
+                            "description": "Allocate a pseudo-terminal for the command (default false). \
+                                            Resizing afterwards requires the api Docker backend."
+                        },
+                        "rows": {
+                            "type": "integer",
+                            "description": "Initial PTY row count (pty must be true)"
+                        },
+                        "cols": {
+                            "type": "integer",
+                            "description": "Initial PTY column count (pty must be true)"
+                        },
+                        "connection": {
+                            "type": "string",
+                            "description": "Named Docker connection to run against (see DockerSettings::connections); omitted or \"default\" uses the default connection"
+                        }
+                    }
+                }),
+            },
+        );
+
+        tools.insert(
+            "docker/exec/stdin".to_string(),
+            Tool {
+                name: "docker/exec/stdin".to_string(),
+                description: Some("Send input to a running docker/exec/start session".to_string()),
+                input_schema: serde_json::json!({
+                    "type": "object",
+                    "required": ["subscription_id", "data"],
+                    "properties": {
+                        "subscription_id": {
+                            "type": "string",
+                            "description": "Subscription id returned by docker/exec/start"
+                        },
+                        "data": {
+                            "type": "string",
+                            "description": "Text to write to the command's stdin"
+                        }
+                    }
+                }),
+            },
+        );
+
+        tools.insert(
+            "docker/exec/resize".to_string(),
+            Tool {
+                name: "docker/exec/resize".to_string(),
+                description: Some(
+                    "Update the terminal size of a docker/exec/start session started with pty: true. \
+                     Only supported when the server is running the api Docker backend."
+                        .to_string(),
+                ),
+                input_schema: serde_json::json!({
+                    "type": "object",
+                    "required": ["subscription_id", "rows", "cols"],
+                    "properties": {
+                        "subscription_id": {
+                            "type": "string",
+                            "description": "Subscription id returned by docker/exec/start"
+                        },
+                        "rows": {
+                            "type": "integer",
+                            "description": "New PTY row count"
+                        },
+                        "cols": {
+                            "type": "integer",
+                            "description": "New PTY column count"
+                        }
+                    }
+                }),
+            },
+        );
+
+        tools.insert(
+            "docker/exec/run".to_string(),
+            Tool {
+                name: "docker/exec/run".to_string(),
+                description: Some(
+                    "Run a short-lived command inside a running container and return its buffered \
+                     stdout/stderr/exit code directly, instead of streaming notifications. \
+                     Bounded by the server's configured operation_timeout."
+                        .to_string(),
+                ),
+                input_schema: serde_json::json!({
+                    "type": "object",
+                    "required": ["container_id", "cmd"],
+                    "properties": {
+                        "container_id": {
+                            "type": "string",
+                            "description": "Container ID or name to run the command in"
+                        },
+                        "cmd": {
+                            "type": "array",
+                            "items": { "type": "string" },
+                            "description": "Command and arguments to execute"
+                        },
+                        "working_dir": {
+                            "type": "string",
+                            "description": "Working directory for the command inside the container"
+                        },
+                        "env": {
+                            "type": "array",
+                            "items": { "type": "string" },
+                            "description": "Environment variables in KEY=VALUE form"
+                        },
+                        "connection": {
+                            "type": "string",
+                            "description": "Named Docker connection to run against (see DockerSettings::connections); omitted or \"default\" uses the default connection"
+                        }
+                    }
+                }),
+            },
+        );
+
+        tools.insert(
+            "endpoint-ping".to_string(),
+            Tool {
+                name: "endpoint-ping".to_string(),
+                description: Some(
+                    "Check reachability of every configured Docker endpoint (the default connection \
+                     plus each of DockerSettings::connections)."
+                        .to_string(),
+                ),
+                input_schema: serde_json::json!({
+                    "type": "object",
+                    "properties": {}
+                }),
+            },
+        );
+
+        tools.insert(
+            "endpoint-stats".to_string(),
+            Tool {
+                name: "endpoint-stats".to_string(),
+                description: Some(
+                    "Report container/image counts and scheduling load (speed, max_jobs, jobs \
+                     currently in flight) for every configured Docker endpoint."
+                        .to_string(),
+                ),
+                input_schema: serde_json::json!({
+                    "type": "object",
+                    "properties": {}
+                }),
+            },
+        );
+
+        // Register resources
+        let mut resources = self.resources.write().await;
+
+        resources.insert(
+            "docker://info".to_string(),
+            Resource {
+                uri: "docker://info".to_string(),
+                name: "Docker Info".to_string(),
+                description: Some("Information about the Docker host system".to_string()),
+                mime_type: Some("application/json".to_string()),
+                text: None,
+                blob: None,
+            },
+        );
+
+        resources.insert(
+            "docker://version".to_string(),
+            Resource {
+                uri: "docker://version".to_string(),
+                name: "Docker Version".to_string(),
+                description: Some("Docker version information".to_string()),
+                mime_type: Some("application/json".to_string()),
+                text: None,
+                blob: None,
+            },
+        );
+
+        resources.insert(
+            "docker://context".to_string(),
+            Resource {
+                uri: "docker://context".to_string(),
+                name: "Docker Context".to_string(),
+                description: Some("Active Docker CLI context name and its resolved host".to_string()),
+                mime_type: Some("application/json".to_string()),
+                text: None,
+                blob: None,
+            },
+        );
+
+        // Register prompts
+        let mut prompts = self.prompts.write().await;
+
+        prompts.insert(
+            "generate-dockerfile".to_string(),
+            Prompt {
+                name: "generate-dockerfile".to_string(),
+                description: Some("Generate an optimized Dockerfile for a specific application type".to_string()),
+                arguments: vec![
+                    crate::protocol::types::PromptArgument {
+                        name: "app_type".to_string(),
+                        description: Some("Type of application (e.g., nodejs, python, go, rust)".to_string()),
+                        required: true,
+                    },
+                    crate::protocol::types::PromptArgument {
+                        name: "version".to_string(),
+                        description: Some("Version of the application runtime".to_string()),
+                        required: false,
+                    },
+                    crate::protocol::types::PromptArgument {
+                        name: "production".to_string(),
+                        description: Some("Whether this is for production use (yes/no)".to_string()),
+                        required: false,
+                    },
+                ],
+            },
+        );
+
+        prompts.insert(
+            "generate-compose".to_string(),
+            Prompt {
+                name: "generate-compose".to_string(),
+                description: Some("Generate a Docker Compose configuration for a specific scenario".to_string()),
+                arguments: vec![
+                    crate::protocol::types::PromptArgument {
+                        name: "scenario".to_string(),
+                        description: Some("Type of scenario (e.g., webapp, database, microservices)".to_string()),
+                        required: true,
+                    },
+                    crate::protocol::types::PromptArgument {
+                        name: "services".to_string(),
+                        description: Some("Comma-separated list of services to include".to_string()),
+                        required: true,
+                    },
+                    crate::protocol::types::PromptArgument {
+                        name: "with_volumes".to_string(),
+                        description: Some("Whether to include persistent volumes (yes/no)".to_string()),
+                        required: false,
+                    },
+                ],
+            },
+        );
+
+        // Register the diagnostic tool
+        self.register_diagnostic_tool(&mut tools).await;
+
+        Ok(())
+    }
+
+    // Records this request in `command_stats`/`in_flight` around dispatch,
+    // then delegates to `dispatch_request` for the actual method routing.
+    // Split out from `dispatch_request` (rather than instrumenting it
+    // directly) because several of its match arms `return` early on
+    // malformed params, and those still need to hit the bookkeeping below.
+    pub async fn process_request(&self, request: JsonRpcRequest) -> JsonRpcResponse {
+        let command = match request.method.as_str() {
+            "tools/call" => request
+                .params
+                .as_ref()
+                .and_then(|p| p.get("name"))
+                .and_then(|v| v.as_str())
+                .map(String::from)
+                .unwrap_or_else(|| "tools/call".to_string()),
+            other => other.to_string(),
+        };
+        let request_key = Self::request_id_key(&request.id);
+        let cancel = tokio_util::sync::CancellationToken::new();
+
+        self.in_flight.write().await.insert(
+            request_key.clone(),
+            InFlightRequest { command: command.clone(), started_at: std::time::Instant::now(), cancel },
+        );
+        let start = std::time::Instant::now();
+
+        ErrorLogger::log_request_start(&request_key, &command);
+
+        let response = self.dispatch_request(request).await;
+
+        self.in_flight.write().await.remove(&request_key);
+        let duration_ms = start.elapsed().as_millis() as u64;
+        let mut stats = self.command_stats.write().await;
+        let stat = stats.entry(command.clone()).or_default();
+        stat.calls += 1;
+        if response.error.is_some() {
+            stat.errors += 1;
+        }
+        stat.total_duration_ms += duration_ms;
+        stat.max_duration_ms = stat.max_duration_ms.max(duration_ms);
+        drop(stats);
+
+        let success = response.error.is_none();
+        let error_code = response.error.as_ref().map(|e| e.code);
+        let error_class = response
+            .error
+            .as_ref()
+            .and_then(|e| e.data.as_ref())
+            .and_then(|d| d.get("class"))
+            .and_then(|c| c.as_str());
+        let error_message = response.error.as_ref().map(|e| e.message.as_str());
+        ErrorLogger::log_request_end(&request_key, &command, success, error_code, error_class, error_message);
+
+        response
+    }
+
+    fn request_id_key(id: &JsonRpcId) -> String {
+        match id {
+            JsonRpcId::Null => "null".to_string(),
+            JsonRpcId::String(s) => s.clone(),
+            JsonRpcId::Number(n) => n.to_string(),
+        }
+    }
+
+    async fn dispatch_request(&self, request: JsonRpcRequest) -> JsonRpcResponse {
+        // Apply rate limiting
+        if let Err(e) = self.rate_limiter.check(&request.method) {
+            crate::audit::record(
+                crate::audit::AuditEvent::new(&request.method, crate::audit::AuditDecision::Deny)
+                    .with_matched_rule("rate limit"),
+            );
+            return self.error_response(request.id, e);
+        }
+
+        match request.method.as_str() {
+            "initialize" => self.handle_initialize(request.id).await,
+            "tools/list" => self.handle_list_tools(request.id).await,
+            "tools/call" => {
+                if self.is_draining() {
+                    return self.error_response(request.id, McpError::ServerShuttingDown);
+                }
+
+                let params = match request.params {
+                    Some(params) => params,
+                    None => return self.error_response(request.id, McpError::InvalidParams("Missing params".to_string())),
+                };
+
+                match serde_json::from_value::<CallToolRequest>(params) {
+                    Ok(params) => self.handle_call_tool(request.id, params).await,
+                    Err(e) => self.error_response(request.id, McpError::InvalidParams(e.to_string())),
+                }
+            }
+            "resources/list" => self.handle_list_resources(request.id).await,
+            "resources/read" => {
+                let params = match request.params {
+                    Some(params) => params,
+                    None => return self.error_response(request.id, McpError::InvalidParams("Missing params".to_string())),
+                };
+
+                match serde_json::from_value::<ReadResourceRequest>(params) {
+                    Ok(params) => self.handle_read_resource(request.id, params).await,
+                    Err(e) => self.error_response(request.id, McpError::InvalidParams(e.to_string())),
+                }
+            }
+            "resources/subscribe" => {
+                let params = match request.params {
+                    Some(params) => params,
+                    None => return self.error_response(request.id, McpError::InvalidParams("Missing params".to_string())),
+                };
+
+                match serde_json::from_value::<SubscribeResourceRequest>(params) {
+                    Ok(params) => self.handle_subscribe_resource(request.id, params).await,
+                    Err(e) => self.error_response(request.id, McpError::InvalidParams(e.to_string())),
+                }
+            }
+            "resources/unsubscribe" => {
+                let params = match request.params {
+                    Some(params) => params,
+                    None => return self.error_response(request.id, McpError::InvalidParams("Missing params".to_string())),
+                };
+
+                match serde_json::from_value::<UnsubscribeResourceRequest>(params) {
+                    Ok(params) => self.handle_unsubscribe_resource(request.id, params).await,
+                    Err(e) => self.error_response(request.id, McpError::InvalidParams(e.to_string())),
+                }
+            }
+            "prompts/list" => self.handle_list_prompts(request.id).await,
+            "prompts/get" => {
+                let params = match request.params {
+                    Some(params) => params,
+                    None => return self.error_response(request.id, McpError::InvalidParams("Missing params".to_string())),
+                };
+
+                match serde_json::from_value::<GetPromptRequest>(params) {
+                    Ok(params) => self.handle_get_prompt(request.id, params).await,
+                    Err(e) => self.error_response(request.id, McpError::InvalidParams(e.to_string())),
+                }
+            }
+            _ => self.error_response(
+                request.id,
+                McpError::MethodNotFound(format!("Method '{}' not found", request.method)),
+            ),
+        }
+    }
+
+    async fn handle_initialize(&self, id: JsonRpcId) -> JsonRpcResponse {
+        let server_info = ServerInfo {
+            name: self.config.server.name.clone(),
+            version: self.config.server.version.clone(),
+        };
+
+        let capabilities = ServerCapabilities {
+            resources: Some(crate::protocol::types::ResourcesCapability {
+                list_changed: true,
+                subscribe: true,
+            }),
+            tools: Some(crate::protocol::types::ToolsCapability {
+                list_changed: true,
+            }),
+            prompts: Some(crate::protocol::types::PromptsCapability {
+                list_changed: true,
+            }),
+        };
+
+        let result = serde_json::json!({
+            "server": server_info,
+            "capabilities": capabilities,
+        });
+
+        JsonRpcResponse {
+            jsonrpc: "2.0".to_string(),
+            id,
+            result: Some(result),
+            error: None,
+        }
+    }
+
+    async fn handle_list_tools(&self, id: JsonRpcId) -> JsonRpcResponse {
+        let tools = self.tools.read().await;
+        let tools_list: Vec<Tool> = tools.values().cloned().collect();
+
+        let result = ListToolsResult { tools: tools_list };
+
+        JsonRpcResponse {
+            jsonrpc: "2.0".to_string(),
+            id,
+            result: Some(serde_json::to_value(result).unwrap()),
+            error: None,
+        }
+    }
+
+    async fn handle_call_tool(&self, id: crate::protocol::types::JsonRpcId, request: crate::protocol::types::CallToolRequest) -> crate::protocol::types::JsonRpcResponse {        // Check security restrictions
+        if let Err(e) = self.security_validator.validate_tool(&request).await {
+            return self.error_response(id, e);
+        }
+
+        // Get the tool
+        let tool_name = request.name.clone();
+        let tools = self.tools.read().await;
+
+        if !tools.contains_key(&tool_name) {
+            return self.error_response(id, McpError::ToolNotFound(tool_name));
+        }
+
+        crate::metrics::record_tool_call(&tool_name);
+
+        let mut arguments = request.arguments;
+        if let Err(e) = self.rewrite_container_path(&mut arguments).await {
+            return self.error_response(id, e);
+        }
+
+        let (docker, _permit) = match self.resolve_docker(&arguments).await {
+            Ok(resolved) => resolved,
+            Err(e) => return self.error_response(id, e),
+        };
+
+        // Execute the tool
+        let result = match tool_name.as_str() {
+            "list-containers" => docker.list_containers(arguments).await,
+            "container-start" => docker.container_start(arguments).await,
+            "container-stop" => docker.container_stop(arguments).await,
+            "container-logs" => docker.container_logs(arguments).await,
+            "container-exec" => self.container_exec(docker.clone(), arguments).await,
+            "list-images" => docker.list_images(arguments).await,
+            "image-build" => docker.image_build(arguments).await,
+            "docker-events" => docker.docker_events(arguments).await,
+            "container-stats" => docker.container_stats(arguments).await,
+            "wait-for-container" => self.wait_for_container(docker.clone(), arguments).await,
+            "container-copy-in" => docker.container_copy_in(arguments).await,
+            "container-copy-out" => docker.container_copy_out(arguments).await,
+            "compose-up" => self.compose_up(docker.clone(), arguments).await,
+            "compose-down" => self.compose_down(docker.clone(), arguments).await,
+            "validate-compose" => docker.validate_compose(arguments).await,
+            "list-volumes" => docker.list_volumes(arguments).await,
+            "create-volume" => docker.create_volume(arguments).await,
+            "remove-volume" => docker.remove_volume(arguments).await,
+            "prune-volumes" => docker.prune_volumes(arguments).await,
+            "diagnostic" => self.run_diagnostic(arguments).await,
+            "server-commands" => self.server_commands().await,
+            "server-requests" => self.server_requests().await,
+            "server-cancel" => self.server_cancel(arguments).await,
+            "docker/logs/follow" => self.start_log_follow(arguments).await,
+            "docker/events/subscribe" => self.start_events_subscribe(arguments).await,
+            "docker/unsubscribe" => self.stop_subscription(arguments).await,
+            "docker/exec/start" => self.start_exec(arguments).await,
+            "docker/exec/stdin" => self.send_exec_stdin(arguments).await,
+            "docker/exec/resize" => self.resize_exec(arguments).await,
+            "docker/exec/run" => self.run_exec(arguments).await,
+            "docker/stats/subscribe" => self.start_stats_subscribe(arguments).await,
+            "resolve_path" => self.resolve_path(arguments).await,
+            "endpoint-ping" => self.endpoint_ping().await,
+            "endpoint-stats" => self.endpoint_stats().await,
+            "oci/state" => self.oci_state(arguments).await,
+            "oci/create" => self.oci_create(arguments).await,
+            "oci/start" => self.oci_start(arguments).await,
+            "oci/kill" => self.oci_kill(arguments).await,
+            "oci/delete" => self.oci_delete(arguments).await,
+            _ => Err(crate::protocol::error::McpError::ToolNotFound(request.name)),
+        };
+
+        match result {
+            Ok(result) => JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                id,
+                result: Some(serde_json::to_value(result).unwrap()),
+                error: None,
+            },
+            Err(e) => self.error_response(id, e),
+        }
+    }
+
+    async fn handle_list_resources(&self, id: JsonRpcId) -> JsonRpcResponse {
+        let resources = self.resources.read().await;
+        let resources_list: Vec<Resource> = resources.values().cloned().collect();
+
+        let result = ListResourcesResult {
+            resources: resources_list,
+            resource_templates: Some(vec![
+                crate::protocol::types::ResourceTemplate {
+                    uri_template: "docker://container/{container_id}".to_string(),
+                    name: "Container Details".to_string(),
+                    description: Some(
+                        "Information about a specific container; subscribable via resources/subscribe \
+                         for lifecycle events (start, die, destroy, ...)"
+                            .to_string(),
+                    ),
+                    mime_type: Some("application/json".to_string()),
+                },
+                crate::protocol::types::ResourceTemplate {
+                    uri_template: "docker://container/{container_id}/logs".to_string(),
+                    name: "Container Logs".to_string(),
+                    description: Some("Subscribable via resources/subscribe for incremental log lines".to_string()),
+                    mime_type: Some("text/plain".to_string()),
+                },
+                crate::protocol::types::ResourceTemplate {
+                    uri_template: "docker://image/{image_id}".to_string(),
+                    name: "Image Details".to_string(),
+                    description: Some(
+                        "Information about a specific image; subscribable via resources/subscribe \
+                         for lifecycle events (pull, tag, delete, ...)"
+                            .to_string(),
+                    ),
+                    mime_type: Some("application/json".to_string()),
+                },
+                crate::protocol::types::ResourceTemplate {
+                    uri_template: "docker://compose/{project_directory}".to_string(),
+                    name: "Compose Project Status".to_string(),
+                    description: Some("Status of a Docker Compose project".to_string()),
+                    mime_type: Some("application/json".to_string()),
+                },
+                crate::protocol::types::ResourceTemplate {
+                    uri_template: "docker://volume/{name}".to_string(),
+                    name: "Volume Details".to_string(),
+                    description: Some("Information about a specific volume".to_string()),
+                    mime_type: Some("application/json".to_string()),
+                },
+            ]),
+        };
+
+        JsonRpcResponse {
+            jsonrpc: "2.0".to_string(),
+            id,
+            result: Some(serde_json::to_value(result).unwrap()),
+            error: None,
+        }
+    }
+
+    async fn handle_read_resource(&self, id: JsonRpcId, request: ReadResourceRequest) -> JsonRpcResponse {
+        // Check security restrictions
+        if let Err(e) = self.security_validator.validate_resource(&request).await {
+            return self.error_response(id, e);
+        }
+
+        // Check if it's a static resource
+        let resources = self.resources.read().await;
+        if let Some(resource) = resources.get(&request.uri) {
+            // Fetch the resource content dynamically
+            let content = match resource.uri.as_str() {
+                "docker://info" => self.docker_client.get_docker_info().await,
+                "docker://version" => self.docker_client.get_docker_version().await,
+                "docker://context" => self.docker_context_resource(),
+                _ => Err(McpError::ResourceNotFound(request.uri.clone())),
+            };
+
+            match content {
+                Ok(text) => {
+                    let content = ResourceContent {
+                        uri: request.uri.clone(),
+                        mime_type: resource.mime_type.clone(),
+                        text: Some(text),
+                        blob: None,
+                    };
+                    let result = ReadResourceResult {
+                        contents: vec![content],
+                    };
+                    JsonRpcResponse {
+                        jsonrpc: "2.0".to_string(),
+                        id,
+                        result: Some(serde_json::to_value(result).unwrap()),
+                        error: None,
+                    }
+                }
+                Err(e) => self.error_response(id, e),
+            }
+        } else {
+            // Handle dynamic resources using URI templates
+            if request.uri.starts_with("docker://container/") {
+                let container_id = request.uri.replace("docker://container/", "");
+                match self.docker_client.get_container_details(&container_id).await {
+                    Ok(text) => {
+                        let content = ResourceContent {
+                            uri: request.uri.clone(),
+                            mime_type: Some("application/json".to_string()),
+                            text: Some(text),
+                            blob: None,
+                        };
+                        let result = ReadResourceResult {
+                            contents: vec![content],
+                        };
+                        JsonRpcResponse {
+                            jsonrpc: "2.0".to_string(),
+                            id,
+                            result: Some(serde_json::to_value(result).unwrap()),
+                            error: None,
+                        }
+                    }
+                    Err(e) => self.error_response(id, e),
+                }
+            } else if request.uri.starts_with("docker://image/") {
+                let image_id = request.uri.replace("docker://image/", "");
+                match self.docker_client.get_image_details(&image_id).await {
+                    Ok(text) => {
+                        let content = ResourceContent {
+                            uri: request.uri.clone(),
+                            mime_type: Some("application/json".to_string()),
+                            text: Some(text),
+                            blob: None,
+                        };
+                        let result = ReadResourceResult {
+                            contents: vec![content],
+                        };
+                        JsonRpcResponse {
+                            jsonrpc: "2.0".to_string(),
+                            id,
+                            result: Some(serde_json::to_value(result).unwrap()),
+                            error: None,
+                        }
+                    }
+                    Err(e) => self.error_response(id, e),
+                }
+            } else if request.uri.starts_with("docker://compose/") {
+                let project_dir = request.uri.replace("docker://compose/", "");
+                match self.docker_client.get_compose_status(&project_dir).await {
+                    Ok(text) => {
+                        let content = ResourceContent {
+                            uri: request.uri.clone(),
+                            mime_type: Some("application/json".to_string()),
+                            text: Some(text),
+                            blob: None,
+                        };
+                        let result = ReadResourceResult {
+                            contents: vec![content],
+                        };
+                        JsonRpcResponse {
+                            jsonrpc: "2.0".to_string(),
+                            id,
+                            result: Some(serde_json::to_value(result).unwrap()),
+                            error: None,
+                        }
+                    }
+                    Err(e) => self.error_response(id, e),
+                }
+            } else if request.uri.starts_with("docker://volume/") {
+                let volume_name = request.uri.replace("docker://volume/", "");
+                match self.docker_client.get_volume_details(&volume_name).await {
+                    Ok(text) => {
+                        let content = ResourceContent {
+                            uri: request.uri.clone(),
+                            mime_type: Some("application/json".to_string()),
+                            text: Some(text),
+                            blob: None,
+                        };
+                        let result = ReadResourceResult {
+                            contents: vec![content],
+                        };
+                        JsonRpcResponse {
+                            jsonrpc: "2.0".to_string(),
+                            id,
+                            result: Some(serde_json::to_value(result).unwrap()),
+                            error: None,
+                        }
+                    }
+                    Err(e) => self.error_response(id, e),
+                }
+            } else {
+                self.error_response(id, McpError::ResourceNotFound(request.uri))
+            }
+        }
     }
 
-    pub async fn process_request(&self, request: JsonRpcRequest) -> JsonRpcResponse {
-        // Apply rate limiting
-        if let Err(e) = self.rate_limiter.check() {
-            return self.error_response(request.id, e);
+    // Starts (or joins) a background task pushing `notifications/resources/updated`
+    // for one of `resources/subscribe`'s two supported URI shapes:
+    // `docker://container/<id>/logs` tails the container's log output, while a
+    // bare `docker://container/<id>` or `docker://image/<id>` watches the
+    // Docker event stream for that id's lifecycle events. Other resource kinds
+    // are point-in-time reads.
+    async fn handle_subscribe_resource(&self, id: JsonRpcId, request: SubscribeResourceRequest) -> JsonRpcResponse {
+        let read_check = ReadResourceRequest { uri: request.uri.clone() };
+        if let Err(e) = self.security_validator.validate_resource(&read_check).await {
+            return self.error_response(id, e);
         }
 
-        match request.method.as_str() {
-            "initialize" => self.handle_initialize(request.id).await,
-            "tools/list" => self.handle_list_tools(request.id).await,
-            "tools/call" => {
-                let params = match request.params {
-                    Some(params) => params,
-                    None => return self.error_response(request.id, McpError::InvalidParams("Missing params".to_string())),
-                };
-
-                match serde_json::from_value::<CallToolRequest>(params) {
-                    Ok(params) => self.handle_call_tool(request.id, params).await,
-                    Err(e) => self.error_response(request.id, McpError::InvalidParams(e.to_string())),
-                }
-            }
-            "resources/list" => self.handle_list_resources(request.id).await,
-            "resources/read" => {
-                let params = match request.params {
-                    Some(params) => params,
-                    None => return self.error_response(request.id, McpError::InvalidParams("Missing params".to_string())),
-                };
-
-                match serde_json::from_value::<ReadResourceRequest>(params) {
-                    Ok(params) => self.handle_read_resource(request.id, params).await,
-                    Err(e) => self.error_response(request.id, McpError::InvalidParams(e.to_string())),
-                }
-            }
-            "prompts/list" => self.handle_list_prompts(request.id).await,
-            "prompts/get" => {
-                let params = match request.params {
-                    Some(params) => params,
-                    None => return self.error_response(request.id, McpError::InvalidParams("Missing params".to_string())),
+        {
+            let mut subs = self.resource_subscriptions.write().await;
+            if let Some(existing) = subs.get_mut(&request.uri) {
+                existing.subscriber_count += 1;
+                return JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    id,
+                    result: Some(serde_json::json!({})),
+                    error: None,
                 };
-
-                match serde_json::from_value::<GetPromptRequest>(params) {
-                    Ok(params) => self.handle_get_prompt(request.id, params).await,
-                    Err(e) => self.error_response(request.id, McpError::InvalidParams(e.to_string())),
-                }
             }
-            _ => self.error_response(
-                request.id,
-                McpError::MethodNotFound(format!("Method '{}' not found", request.method)),
-            ),
         }
-    }
 
-    async fn handle_initialize(&self, id: JsonRpcId) -> JsonRpcResponse {
-        let server_info = ServerInfo {
-            name: self.config.server.name.clone(),
-            version: self.config.server.version.clone(),
-        };
+        let handle = if let Some(container_id) = parse_container_logs_uri(&request.uri) {
+            let docker_client = self.docker_client.clone();
+            let notification_tx = self.notification_tx.clone();
+            let uri = request.uri.clone();
+
+            tokio::spawn(async move {
+                use futures::StreamExt;
+                use crate::docker::LogStream;
+
+                let mut stream = docker_client.follow_logs(&container_id);
+                while let Some(chunk) = stream.next().await {
+                    let (stream_name, text) = match chunk {
+                        Ok(crate::docker::LogChunk { stream: LogStream::Stdout, text }) => ("stdout", text),
+                        Ok(crate::docker::LogChunk { stream: LogStream::Stderr, text }) => ("stderr", text),
+                        Err(e) => {
+                            let _ = notification_tx.send(JsonRpcNotification::new(
+                                "notifications/resources/updated",
+                                serde_json::json!({ "uri": uri, "error": e.to_string() }),
+                            ));
+                            break;
+                        }
+                    };
 
-        let capabilities = ServerCapabilities {
-            resources: Some(crate::protocol::types::ResourcesCapability {
-                list_changed: true,
-            }),
-            tools: Some(crate::protocol::types::ToolsCapability {
-                list_changed: true,
-            }),
-            prompts: Some(crate::protocol::types::PromptsCapability {
-                list_changed: true,
-            }),
+                    let _ = notification_tx.send(JsonRpcNotification::new(
+                        "notifications/resources/updated",
+                        serde_json::json!({ "uri": uri, "stream": stream_name, "text": text }),
+                    ));
+                }
+            })
+        } else if let Some((filter_key, entity_id)) = parse_lifecycle_uri(&request.uri) {
+            let docker_client = self.docker_client.clone();
+            let notification_tx = self.notification_tx.clone();
+            let uri = request.uri.clone();
+            let mut filters = HashMap::new();
+            filters.insert(filter_key.to_string(), vec![entity_id]);
+
+            tokio::spawn(async move {
+                use futures::StreamExt;
+
+                let mut stream = docker_client.stream_events(None, None, filters);
+                while let Some(event) = stream.next().await {
+                    match event {
+                        Ok(event) => {
+                            let action = event_action(&event).unwrap_or("unknown").to_string();
+                            let _ = notification_tx.send(JsonRpcNotification::new(
+                                "notifications/resources/updated",
+                                serde_json::json!({ "uri": uri, "action": action, "event": event }),
+                            ));
+
+                            // Destruction/removal events change what `resources/list`
+                            // would return next, on top of updating this one resource.
+                            if matches!(action.as_str(), "destroy" | "die" | "remove" | "delete" | "untag") {
+                                let _ = notification_tx.send(JsonRpcNotification::new(
+                                    "notifications/resources/list_changed",
+                                    serde_json::json!({}),
+                                ));
+                            }
+                        }
+                        Err(e) => {
+                            let _ = notification_tx.send(JsonRpcNotification::new(
+                                "notifications/resources/updated",
+                                serde_json::json!({ "uri": uri, "error": e.to_string() }),
+                            ));
+                            break;
+                        }
+                    }
+                }
+            })
+        } else {
+            return self.error_response(
+                id,
+                McpError::InvalidParams(format!(
+                    "resources/subscribe only supports 'docker://container/<id>/logs', \
+                     'docker://container/<id>', and 'docker://image/<id>' URIs, got '{}'",
+                    request.uri
+                )),
+            );
         };
 
-        let result = serde_json::json!({
-            "server": server_info,
-            "capabilities": capabilities,
-        });
+        self.resource_subscriptions.write().await.insert(
+            request.uri,
+            ResourceSubscription {
+                handle: handle.abort_handle(),
+                subscriber_count: 1,
+            },
+        );
 
         JsonRpcResponse {
             jsonrpc: "2.0".to_string(),
             id,
-            result: Some(result),
+            result: Some(serde_json::json!({})),
             error: None,
         }
     }
 
-    async fn handle_list_tools(&self, id: JsonRpcId) -> JsonRpcResponse {
-        let tools = self.tools.read().await;
-        let tools_list: Vec<Tool> = tools.values().cloned().collect();
+    // Decrements the subscriber count for `request.uri` and only aborts the
+    // tailing task once the last subscriber has unsubscribed.
+    async fn handle_unsubscribe_resource(&self, id: JsonRpcId, request: UnsubscribeResourceRequest) -> JsonRpcResponse {
+        let mut subs = self.resource_subscriptions.write().await;
+        match subs.get_mut(&request.uri) {
+            Some(sub) => {
+                sub.subscriber_count = sub.subscriber_count.saturating_sub(1);
+                if sub.subscriber_count == 0 {
+                    sub.handle.abort();
+                    subs.remove(&request.uri);
+                }
 
-        let result = ListToolsResult { tools: tools_list };
+                JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    id,
+                    result: Some(serde_json::json!({})),
+                    error: None,
+                }
+            }
+            None => {
+                drop(subs);
+                self.error_response(id, McpError::InvalidParams(format!("Not subscribed to '{}'", request.uri)))
+            }
+        }
+    }
+
+    async fn handle_list_prompts(&self, id: JsonRpcId) -> JsonRpcResponse {
+        let prompts = self.prompts.read().await;
+        let prompts_list: Vec<Prompt> = prompts.values().cloned().collect();
+
+        let result = ListPromptsResult { prompts: prompts_list };
 
         JsonRpcResponse {
             jsonrpc: "2.0".to_string(),
@@ -497,476 +2237,1513 @@ impl McpServer {
         }
     }
 
-    async fn handle_call_tool(&self, id: crate::protocol::types::JsonRpcId, request: crate::protocol::types::CallToolRequest) -> crate::protocol::types::JsonRpcResponse {        // Check security restrictions
-        if let Err(e) = self.security_validator.validate_tool(&request) {
-            return self.error_response(id, e);
+    async fn handle_get_prompt(&self, id: JsonRpcId, request: GetPromptRequest) -> JsonRpcResponse {
+        let prompts = self.prompts.read().await;
+        
+        if let Some(prompt) = prompts.get(&request.name) {
+            // Validate required arguments are present
+            if let Some(args) = &request.arguments {
+                for arg in &prompt.arguments {
+                    if arg.required && !args.contains_key(&arg.name) {
+                        return self.error_response(
+                            id,
+                            McpError::InvalidParams(format!("Required argument '{}' is missing", arg.name)),
+                        );
+                    }
+                }
+            } else if prompt.arguments.iter().any(|arg| arg.required) {
+                return self.error_response(
+                    id,
+                    McpError::InvalidParams("Required arguments are missing".to_string()),
+                );
+            }
+
+            // Generate prompt messages based on the template type
+            let result = match request.name.as_str() {
+                "generate-dockerfile" => self.generate_dockerfile_prompt(request.arguments).await,
+                "generate-compose" => self.generate_compose_prompt(request.arguments).await,
+                _ => Err(McpError::PromptNotFound(request.name)),
+            };
+
+            match result {
+                Ok(result) => JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    id,
+                    result: Some(serde_json::to_value(result).unwrap()),
+                    error: None,
+                },
+                Err(e) => self.error_response(id, e),
+            }
+        } else {
+            self.error_response(id, McpError::PromptNotFound(request.name))
         }
+    }
 
-        // Get the tool
-        let tool_name = request.name.clone();
-        let tools = self.tools.read().await;
+    async fn generate_dockerfile_prompt(
+        &self,
+        args: Option<HashMap<String, String>>,
+    ) -> Result<GetPromptResult, McpError> {
+        let args = args.unwrap_or_default();
+        let app_type = args
+            .get("app_type")
+            .ok_or_else(|| McpError::InvalidParams("Missing required argument 'app_type'".to_string()))?;
         
-        if !tools.contains_key(&tool_name) {
-            return self.error_response(id, McpError::ToolNotFound(tool_name));
+        let version = args.get("version").map(|s| s.as_str()).unwrap_or("latest");
+        let production = args.get("production").map(|s| s.as_str()).unwrap_or("yes") == "yes";
+
+        let mut prompt_text = format!(
+            "Generate an optimized Dockerfile for a {} application",
+            app_type
+        );
+
+        if version != "latest" {
+            prompt_text.push_str(&format!(", using version {}", version));
+        }
+
+        if production {
+            prompt_text.push_str(", optimized for production use.");
+            prompt_text.push_str("\n\nThe Dockerfile should include:");
+            prompt_text.push_str("\n- Multi-stage builds for smaller final image");
+            prompt_text.push_str("\n- Proper security practices (non-root user, minimal permissions)");
+            prompt_text.push_str("\n- Optimization for caching during builds");
+            prompt_text.push_str("\n- Health checks and proper signal handling");
+        } else {
+            prompt_text.push_str(", configured for development.");
+            prompt_text.push_str("\n\nThe Dockerfile should include:");
+            prompt_text.push_str("\n- Fast rebuilds and good developer experience");
+            prompt_text.push_str("\n- Volume mounting for code changes");
+            prompt_text.push_str("\n- Debugging tools included");
         }
 
-        // Execute the tool
-        let result = match tool_name.as_str() {
-            "list-containers" => self.docker_client.list_containers(request.arguments).await,
-            "container-start" => self.docker_client.container_start(request.arguments).await,
-            "container-stop" => self.docker_client.container_stop(request.arguments).await,
-            "container-logs" => self.docker_client.container_logs(request.arguments).await,
-            "list-images" => self.docker_client.list_images(request.arguments).await,
-            "compose-up" => self.docker_client.compose_up(request.arguments).await,
-            "compose-down" => self.docker_client.compose_down(request.arguments).await,
-            "validate-compose" => self.docker_client.validate_compose(request.arguments).await,
-            "diagnostic" => self.run_diagnostic(request.arguments).await,
-            _ => Err(crate::protocol::error::McpError::ToolNotFound(request.name)),
-        };
+        prompt_text.push_str("\n\nPlease include comments explaining key decisions.");
+
+        let messages = vec![crate::protocol::types::PromptMessage {
+            role: "user".to_string(),
+            content: crate::protocol::types::PromptContent {
+                r#type: "text".to_string(),
+                text: Some(prompt_text),
+                resource: None,
+            },
+        }];
+
+        Ok(GetPromptResult {
+            description: Some(format!(
+                "Optimized Dockerfile for {} {} application",
+                if production { "production" } else { "development" },
+                app_type
+            )),
+            messages,
+        })
+    }
+
+    async fn generate_compose_prompt(
+        &self,
+        args: Option<HashMap<String, String>>,
+    ) -> Result<GetPromptResult, McpError> {
+        let args = args.unwrap_or_default();
+        let scenario = args
+            .get("scenario")
+            .ok_or_else(|| McpError::InvalidParams("Missing required argument 'scenario'".to_string()))?;
+        
+        let services = args
+            .get("services")
+            .ok_or_else(|| McpError::InvalidParams("Missing required argument 'services'".to_string()))?;
+        
+        let with_volumes = args.get("with_volumes").map(|s| s.as_str()).unwrap_or("yes") == "yes";
+
+        let mut prompt_text = format!(
+            "Generate a Docker Compose configuration for a {} scenario",
+            scenario
+        );
+
+        prompt_text.push_str(&format!(" that includes the following services: {}.", services));
 
-        match result {
-            Ok(result) => JsonRpcResponse {
-                jsonrpc: "2.0".to_string(),
-                id,
-                result: Some(serde_json::to_value(result).unwrap()),
-                error: None,
-            },
-            Err(e) => self.error_response(id, e),
+        if with_volumes {
+            prompt_text.push_str("\n\nInclude persistent volumes for data that should be preserved across container restarts.");
         }
-    }
 
-    async fn handle_list_resources(&self, id: JsonRpcId) -> JsonRpcResponse {
-        let resources = self.resources.read().await;
-        let resources_list: Vec<Resource> = resources.values().cloned().collect();
+        prompt_text.push_str("\n\nThe configuration should include:");
+        prompt_text.push_str("\n- Proper networking between services");
+        prompt_text.push_str("\n- Environment variables for configuration");
+        prompt_text.push_str("\n- Health checks where appropriate");
+        prompt_text.push_str("\n- Restart policies for reliability");
+        prompt_text.push_str("\n\nPlease include comments explaining the purpose of each service and any important configuration details.");
 
-        let result = ListResourcesResult {
-            resources: resources_list,
-            resource_templates: Some(vec![
-                crate::protocol::types::ResourceTemplate {
-                    uri_template: "docker://container/{container_id}".to_string(),
-                    name: "Container Details".to_string(),
-                    description: Some("Information about a specific container".to_string()),
-                    mime_type: Some("application/json".to_string()),
-                },
-                crate::protocol::types::ResourceTemplate {
-                    uri_template: "docker://image/{image_id}".to_string(),
-                    name: "Image Details".to_string(),
-                    description: Some("Information about a specific image".to_string()),
-                    mime_type: Some("application/json".to_string()),
-                },
-                crate::protocol::types::ResourceTemplate {
-                    uri_template: "docker://compose/{project_directory}".to_string(),
-                    name: "Compose Project Status".to_string(),
-                    description: Some("Status of a Docker Compose project".to_string()),
-                    mime_type: Some("application/json".to_string()),
-                },
-            ]),
-        };
+        let messages = vec![crate::protocol::types::PromptMessage {
+            role: "user".to_string(),
+            content: crate::protocol::types::PromptContent {
+                r#type: "text".to_string(),
+                text: Some(prompt_text),
+                resource: None,
+            },
+        }];
 
-        JsonRpcResponse {
-            jsonrpc: "2.0".to_string(),
-            id,
-            result: Some(serde_json::to_value(result).unwrap()),
-            error: None,
-        }
+        Ok(GetPromptResult {
+            description: Some(format!(
+                "Docker Compose configuration for {} scenario with services: {}",
+                scenario, services
+            )),
+            messages,
+        })
     }
 
-    async fn handle_read_resource(&self, id: JsonRpcId, request: ReadResourceRequest) -> JsonRpcResponse {
-        // Check security restrictions
-        if let Err(e) = self.security_validator.validate_resource(&request) {
-            return self.error_response(id, e);
-        }
+    // Implementation of the diagnostic tool
+    // `docker://context` resource: the active Docker CLI context name and
+    // its resolved host (`None` for the `default` context, which has no
+    // metadata file of its own), the same values `load_config` itself
+    // already layers into `docker.host` at startup.
+    fn docker_context_resource(&self) -> Result<String, McpError> {
+        let active_context = crate::config::loader::active_docker_context();
+        Ok(serde_json::json!({
+            "context": active_context.name,
+            "host": active_context.host,
+        })
+        .to_string())
+    }
 
-        // Check if it's a static resource
-        let resources = self.resources.read().await;
-        if let Some(resource) = resources.get(&request.uri) {
-            // Fetch the resource content dynamically
-            let content = match resource.uri.as_str() {
-                "docker://info" => self.docker_client.get_docker_info().await,
-                "docker://version" => self.docker_client.get_docker_version().await,
-                _ => Err(McpError::ResourceNotFound(request.uri.clone())),
-            };
+    async fn run_diagnostic(&self, args: serde_json::Value) -> Result<crate::protocol::types::CallToolResult, crate::protocol::error::McpError> {
+        let check_docker = args.get("check_docker").and_then(|v| v.as_bool()).unwrap_or(true);
+        let check_compose = args.get("check_compose").and_then(|v| v.as_bool()).unwrap_or(true);
+        let list_env_vars = args.get("list_env_vars").and_then(|v| v.as_bool()).unwrap_or(false);
+        
+        let mut results = Vec::new();
+        
+        results.push("=== Docker MCP Server Diagnostics ===".to_string());
+        results.push(format!("Server name: {}", self.config.server.name));
+        results.push(format!("Server version: {}", self.config.server.version));
+        results.push(format!("Transport type: {:?}", self.config.server.transport));
+        results.push(format!("Request timeout: {:?}", self.config.server.request_timeout));
+        results.push(format!("Docker host: {}", self.config.docker.host));
+        results.push(format!("Read-only mode: {}", self.config.docker.read_only));
+        results.push(format!("Configured Docker backend: {:?}", self.config.docker.backend));
+        results.push(format!("Active Docker backend: {}", self.docker_client.active_backend()));
 
-            match content {
-                Ok(text) => {
-                    let content = ResourceContent {
-                        uri: request.uri.clone(),
-                        mime_type: resource.mime_type.clone(),
-                        text: Some(text),
-                        blob: None,
-                    };
-                    let result = ReadResourceResult {
-                        contents: vec![content],
-                    };
-                    JsonRpcResponse {
-                        jsonrpc: "2.0".to_string(),
-                        id,
-                        result: Some(serde_json::to_value(result).unwrap()),
-                        error: None,
+        let active_context = crate::config::loader::active_docker_context();
+        results.push(format!(
+            "Active Docker context: {} (host: {})",
+            active_context.name,
+            active_context.host.as_deref().unwrap_or("unresolved, using configured docker.host")
+        ));
+        results.push(format!("Available Docker contexts: {}", crate::config::loader::list_docker_contexts().join(", ")));
+
+        if check_docker {
+            results.push("\n=== Docker Connectivity ===".to_string());
+            match self.docker_client.get_docker_version().await {
+                Ok(version) => {
+                    let parsed: Result<serde_json::Value, _> = serde_json::from_str(&version);
+                    match parsed {
+                        Ok(v) => {
+                            if let Some(api_version) = v.get("ApiVersion").and_then(|v| v.as_str()) {
+                                results.push(format!("Docker API version: {}", api_version));
+                            }
+                            if let Some(engine_version) = v.get("Version").and_then(|v| v.as_str()) {
+                                results.push(format!("Docker Engine version: {}", engine_version));
+                            }
+                            results.push("Docker connection: OK".to_string());
+                        },
+                        Err(_) => {
+                            results.push(format!("Docker connection: OK (raw data: {})", version));
+                        }
                     }
-                }
-                Err(e) => self.error_response(id, e),
-            }
-        } else {
-            // Handle dynamic resources using URI templates
-            if request.uri.starts_with("docker://container/") {
-                let container_id = request.uri.replace("docker://container/", "");
-                match self.docker_client.get_container_details(&container_id).await {
-                    Ok(text) => {
-                        let content = ResourceContent {
-                            uri: request.uri.clone(),
-                            mime_type: Some("application/json".to_string()),
-                            text: Some(text),
-                            blob: None,
-                        };
-                        let result = ReadResourceResult {
-                            contents: vec![content],
-                        };
-                        JsonRpcResponse {
-                            jsonrpc: "2.0".to_string(),
-                            id,
-                            result: Some(serde_json::to_value(result).unwrap()),
-                            error: None,
+                },
+                Err(e) => {
+                    results.push(format!("Docker connection: FAILED - {}", e));
+                    results.push("Possible causes:".to_string());
+                    results.push(" - Docker daemon not running".to_string());
+                    results.push(" - Incorrect Docker host configuration".to_string());
+                    results.push(" - Permission issues with Docker socket".to_string());
+
+                    if self.config.docker.host.starts_with("unix://") {
+                        // Check if the Docker socket exists
+                        let socket_path = self.config.docker.host.trim_start_matches("unix://");
+                        if let Ok(metadata) = std::fs::metadata(socket_path) {
+                            results.push(format!("Docker socket exists: {}", socket_path));
+
+                            // Check if it's a socket
+                            #[cfg(unix)]
+                            {
+                                use std::os::unix::fs::FileTypeExt;
+                                if metadata.file_type().is_socket() {
+                                    results.push("File is a valid socket: YES".to_string());
+                                } else {
+                                    results.push("File is a valid socket: NO".to_string());
+                                }
+                            }
+                        } else {
+                            results.push(format!("Docker socket not found at: {}", socket_path));
                         }
                     }
-                    Err(e) => self.error_response(id, e),
                 }
-            } else if request.uri.starts_with("docker://image/") {
-                let image_id = request.uri.replace("docker://image/", "");
-                match self.docker_client.get_image_details(&image_id).await {
-                    Ok(text) => {
-                        let content = ResourceContent {
-                            uri: request.uri.clone(),
-                            mime_type: Some("application/json".to_string()),
-                            text: Some(text),
-                            blob: None,
-                        };
-                        let result = ReadResourceResult {
-                            contents: vec![content],
-                        };
-                        JsonRpcResponse {
-                            jsonrpc: "2.0".to_string(),
-                            id,
-                            result: Some(serde_json::to_value(result).unwrap()),
-                            error: None,
-                        }
+            }
+
+            if !self.config.docker.connections.is_empty() {
+                results.push("\n=== Additional Connections ===".to_string());
+                let connections = self.connections.read().await;
+                for (name, conn_settings) in &self.config.docker.connections {
+                    let resolved = conn_settings.resolve(&self.config.docker);
+                    match connections.get(name) {
+                        Some(docker) => match docker.get_docker_version().await {
+                            Ok(version) => {
+                                let api_version = serde_json::from_str::<serde_json::Value>(&version)
+                                    .ok()
+                                    .and_then(|v| v.get("ApiVersion").and_then(|v| v.as_str()).map(String::from));
+                                results.push(format!(
+                                    "{}: OK (backend: {}, api_version: {}, read_only: {}, host: {})",
+                                    name,
+                                    docker.active_backend(),
+                                    api_version.as_deref().unwrap_or("unknown"),
+                                    resolved.read_only,
+                                    resolved.host,
+                                ));
+                            }
+                            Err(e) => {
+                                results.push(format!(
+                                    "{}: FAILED - {} (backend: {}, read_only: {}, host: {})",
+                                    name,
+                                    e,
+                                    docker.active_backend(),
+                                    resolved.read_only,
+                                    resolved.host,
+                                ));
+                            }
+                        },
+                        None => results.push(format!("{}: not initialized", name)),
                     }
-                    Err(e) => self.error_response(id, e),
                 }
-            } else if request.uri.starts_with("docker://compose/") {
-                let project_dir = request.uri.replace("docker://compose/", "");
-                match self.docker_client.get_compose_status(&project_dir).await {
-                    Ok(text) => {
-                        let content = ResourceContent {
-                            uri: request.uri.clone(),
-                            mime_type: Some("application/json".to_string()),
-                            text: Some(text),
-                            blob: None,
-                        };
-                        let result = ReadResourceResult {
-                            contents: vec![content],
-                        };
-                        JsonRpcResponse {
-                            jsonrpc: "2.0".to_string(),
-                            id,
-                            result: Some(serde_json::to_value(result).unwrap()),
-                            error: None,
+            }
+        }
+
+        if check_compose {
+            results.push("\n=== Docker Compose ===".to_string());
+            
+            let compose_path = &self.config.docker.compose_path;
+            results.push(format!("Docker Compose path: {:?}", compose_path));
+            
+            // Check if the compose binary exists
+            if compose_path.exists() {
+                results.push("Docker Compose binary exists: YES".to_string());
+                
+                // Try to run docker-compose version
+                let output = tokio::process::Command::new(compose_path)
+                    .arg("version")
+                    .output()
+                    .await;
+                
+                match output {
+                    Ok(output) => {
+                        if output.status.success() {
+                            let version = String::from_utf8_lossy(&output.stdout);
+                            results.push(format!("Docker Compose version: {}", version.trim()));
+                            results.push("Docker Compose command: OK".to_string());
+                        } else {
+                            let stderr = String::from_utf8_lossy(&output.stderr);
+                            results.push(format!("Docker Compose command failed: {}", stderr.trim()));
                         }
+                    },
+                    Err(e) => {
+                        results.push(format!("Docker Compose command error: {}", e));
                     }
-                    Err(e) => self.error_response(id, e),
                 }
             } else {
-                self.error_response(id, McpError::ResourceNotFound(request.uri))
+                results.push("Docker Compose binary exists: NO".to_string());
+                results.push("Possible causes:".to_string());
+                results.push(" - Docker Compose not installed".to_string());
+                results.push(" - Incorrect path in configuration".to_string());
+                results.push(format!(" - Current working directory: {:?}", std::env::current_dir().ok()));
+            }
+        }
+        
+        let preconditions = &self.config.docker.preconditions;
+        if preconditions.required_docker_version.is_some()
+            || preconditions.required_api_version.is_some()
+            || !preconditions.required_images.is_empty()
+        {
+            results.push("\n=== Preconditions ===".to_string());
+            let assertions = crate::config::validate::check_preconditions(&self.docker_client, &self.config).await;
+            for assertion in &assertions {
+                if assertion.in_desired_state {
+                    results.push(format!("PASS  {} (expected: {})", assertion.resource, assertion.expected));
+                } else {
+                    results.push(format!(
+                        "FAIL  {} (expected: {}, actual: {})",
+                        assertion.resource, assertion.expected, assertion.actual
+                    ));
+                }
+            }
+        }
+
+        results.push("\n=== Cleanup on Exit ===".to_string());
+        results.push(format!("cleanup_on_exit: {}", self.config.server.cleanup_on_exit));
+        let managed_projects = self.managed_compose_projects.read().await;
+        if managed_projects.is_empty() {
+            results.push("Tracked compose projects: none".to_string());
+        } else {
+            results.push(format!("Tracked compose projects ({}):", managed_projects.len()));
+            for (connection, project_directory) in managed_projects.iter() {
+                results.push(format!(
+                    " - {} (connection: {}){}",
+                    project_directory,
+                    connection,
+                    if self.config.server.cleanup_on_exit { "" } else { " [not torn down: cleanup_on_exit is off]" }
+                ));
+            }
+        }
+        drop(managed_projects);
+
+        results.push("\n=== Volumes ===".to_string());
+        match self.docker_client.owned_volumes_usage().await {
+            Ok(usage) => {
+                results.push(format!("Owned volumes: {}", usage.owned_count));
+                match usage.total_reclaimable_bytes {
+                    Some(bytes) => results.push(format!("Reclaimable on prune: {} bytes", bytes)),
+                    None => results.push(
+                        "Reclaimable on prune: unknown (cli backend does not report per-volume size)".to_string(),
+                    ),
+                }
+            }
+            Err(e) => results.push(format!("Failed to gather volume usage: {}", e)),
+        }
+
+        if list_env_vars {
+            results.push("\n=== Environment Variables ===".to_string());
+            for (key, value) in std::env::vars() {
+                if key.starts_with("DOCKER_") || key.contains("MCP") || key.contains("RUST") {
+                    results.push(format!("{}={}", key, value));
+                }
             }
         }
+        
+        let result_text = results.join("\n");
+        
+        Ok(crate::protocol::types::CallToolResult {
+            content: vec![crate::protocol::types::Content::Text(crate::protocol::types::TextContent {
+                r#type: "text".to_string(),
+                text: result_text,
+            })],
+            is_error: false,
+        })
     }
 
-    async fn handle_list_prompts(&self, id: JsonRpcId) -> JsonRpcResponse {
-        let prompts = self.prompts.read().await;
-        let prompts_list: Vec<Prompt> = prompts.values().cloned().collect();
+    // Starts a background task that tails a container's logs and emits a
+    // `docker/logs/line` notification per line, tagged with a subscription
+    // id so the client can tell streams apart and unsubscribe later.
+    async fn start_log_follow(&self, args: serde_json::Value) -> Result<crate::protocol::types::CallToolResult, McpError> {
+        let (docker_client, _permit) = self.resolve_docker(&args).await?;
+        docker_client.check_read_only("docker/logs/follow")?;
+
+        let container_id = args
+            .get("container_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| McpError::InvalidParams("Missing container_id parameter".to_string()))?
+            .to_string();
+
+        let subscription_id = uuid::Uuid::new_v4().to_string();
+        let notification_tx = self.notification_tx.clone();
+        let sub_id = subscription_id.clone();
+        let cid = container_id.clone();
+
+        let handle = tokio::spawn(async move {
+            use futures::StreamExt;
+            use crate::docker::LogStream;
+
+            let mut stream = docker_client.follow_logs(&cid);
+            while let Some(chunk) = stream.next().await {
+                let (stream_name, text) = match chunk {
+                    Ok(crate::docker::LogChunk { stream: LogStream::Stdout, text }) => ("stdout", text),
+                    Ok(crate::docker::LogChunk { stream: LogStream::Stderr, text }) => ("stderr", text),
+                    Err(e) => {
+                        let _ = notification_tx.send(JsonRpcNotification::new(
+                            "docker/logs/error",
+                            serde_json::json!({
+                                "subscription_id": sub_id,
+                                "container_id": cid,
+                                "error": e.to_string(),
+                            }),
+                        ));
+                        break;
+                    }
+                };
 
-        let result = ListPromptsResult { prompts: prompts_list };
+                let _ = notification_tx.send(JsonRpcNotification::new(
+                    "docker/logs/line",
+                    serde_json::json!({
+                        "subscription_id": sub_id,
+                        "container_id": cid,
+                        "stream": stream_name,
+                        "text": text,
+                    }),
+                ));
+            }
+        });
 
-        JsonRpcResponse {
-            jsonrpc: "2.0".to_string(),
-            id,
-            result: Some(serde_json::to_value(result).unwrap()),
-            error: None,
-        }
+        self.subscriptions
+            .write()
+            .await
+            .insert(subscription_id.clone(), handle.abort_handle());
+
+        Ok(crate::protocol::types::CallToolResult {
+            content: vec![crate::protocol::types::Content::Text(crate::protocol::types::TextContent {
+                r#type: "text".to_string(),
+                text: serde_json::json!({ "subscription_id": subscription_id }).to_string(),
+            })],
+            is_error: false,
+        })
     }
 
-    async fn handle_get_prompt(&self, id: JsonRpcId, request: GetPromptRequest) -> JsonRpcResponse {
-        let prompts = self.prompts.read().await;
-        
-        if let Some(prompt) = prompts.get(&request.name) {
-            // Validate required arguments are present
-            if let Some(args) = &request.arguments {
-                for arg in &prompt.arguments {
-                    if arg.required && !args.contains_key(&arg.name) {
-                        return self.error_response(
-                            id,
-                            McpError::InvalidParams(format!("Required argument '{}' is missing", arg.name)),
-                        );
+    // Starts a background task that relays the Docker daemon's event
+    // stream as `docker/events/event` notifications until unsubscribed.
+    async fn start_events_subscribe(&self, args: serde_json::Value) -> Result<crate::protocol::types::CallToolResult, McpError> {
+        let (docker_client, _permit) = self.resolve_docker(&args).await?;
+        docker_client.check_read_only("docker/events/subscribe")?;
+
+        let filters: HashMap<String, Vec<String>> = args
+            .get("filters")
+            .and_then(|v| v.as_object())
+            .map(|obj| {
+                obj.iter()
+                    .map(|(k, v)| {
+                        let values = v
+                            .as_array()
+                            .map(|arr| arr.iter().filter_map(|s| s.as_str().map(String::from)).collect())
+                            .unwrap_or_default();
+                        (k.clone(), values)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let subscription_id = uuid::Uuid::new_v4().to_string();
+        let notification_tx = self.notification_tx.clone();
+        let sub_id = subscription_id.clone();
+
+        let handle = tokio::spawn(async move {
+            use futures::StreamExt;
+            let mut stream = docker_client.stream_events(None, None, filters);
+            while let Some(event) = stream.next().await {
+                match event {
+                    Ok(event) => {
+                        let _ = notification_tx.send(JsonRpcNotification::new(
+                            "docker/events/event",
+                            serde_json::json!({
+                                "subscription_id": sub_id,
+                                "event": event,
+                            }),
+                        ));
+                    }
+                    Err(e) => {
+                        let _ = notification_tx.send(JsonRpcNotification::new(
+                            "docker/events/error",
+                            serde_json::json!({
+                                "subscription_id": sub_id,
+                                "error": e.to_string(),
+                            }),
+                        ));
+                        break;
                     }
                 }
-            } else if prompt.arguments.iter().any(|arg| arg.required) {
-                return self.error_response(
-                    id,
-                    McpError::InvalidParams("Required arguments are missing".to_string()),
-                );
             }
+        });
 
-            // Generate prompt messages based on the template type
-            let result = match request.name.as_str() {
-                "generate-dockerfile" => self.generate_dockerfile_prompt(request.arguments).await,
-                "generate-compose" => self.generate_compose_prompt(request.arguments).await,
-                _ => Err(McpError::PromptNotFound(request.name)),
-            };
+        self.subscriptions
+            .write()
+            .await
+            .insert(subscription_id.clone(), handle.abort_handle());
 
-            match result {
-                Ok(result) => JsonRpcResponse {
-                    jsonrpc: "2.0".to_string(),
-                    id,
-                    result: Some(serde_json::to_value(result).unwrap()),
-                    error: None,
-                },
-                Err(e) => self.error_response(id, e),
+        Ok(crate::protocol::types::CallToolResult {
+            content: vec![crate::protocol::types::Content::Text(crate::protocol::types::TextContent {
+                r#type: "text".to_string(),
+                text: serde_json::json!({ "subscription_id": subscription_id }).to_string(),
+            })],
+            is_error: false,
+        })
+    }
+
+    // Starts a background task that polls `DockerBackend::get_container_stats`
+    // at `interval_ms` and relays each sample as a `docker/stats/sample`
+    // notification, until the container stops responding (the poll errors,
+    // most commonly because it exited) or the subscription is cancelled.
+    async fn start_stats_subscribe(&self, args: serde_json::Value) -> Result<crate::protocol::types::CallToolResult, McpError> {
+        let (docker_client, _permit) = self.resolve_docker(&args).await?;
+        docker_client.check_read_only("docker/stats/subscribe")?;
+
+        let container_id = args
+            .get("container_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| McpError::InvalidParams("Missing container_id parameter".to_string()))?
+            .to_string();
+
+        let interval_ms = args.get("interval_ms").and_then(|v| v.as_u64()).unwrap_or(1000);
+
+        let subscription_id = uuid::Uuid::new_v4().to_string();
+        let notification_tx = self.notification_tx.clone();
+        let sub_id = subscription_id.clone();
+        let cid = container_id.clone();
+
+        let handle = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(std::time::Duration::from_millis(interval_ms));
+            loop {
+                ticker.tick().await;
+                match docker_client.get_container_stats(&cid).await {
+                    Ok(sample) => {
+                        let _ = notification_tx.send(JsonRpcNotification::new(
+                            "docker/stats/sample",
+                            serde_json::json!({
+                                "subscription_id": sub_id,
+                                "container_id": cid,
+                                "cpu_percent": sample.cpu_percent,
+                                "cpu_total_usage": sample.cpu_total_usage,
+                                "per_cpu_usage": sample.per_cpu_usage,
+                                "memory_usage": sample.memory_usage,
+                                "memory_limit": sample.memory_limit,
+                                "memory_cache": sample.memory_cache,
+                                "pids_current": sample.pids_current,
+                                "pids_limit": sample.pids_limit,
+                                "blk_read": sample.blk_read,
+                                "blk_write": sample.blk_write,
+                            }),
+                        ));
+                    }
+                    Err(e) => {
+                        let _ = notification_tx.send(JsonRpcNotification::new(
+                            "docker/stats/error",
+                            serde_json::json!({
+                                "subscription_id": sub_id,
+                                "container_id": cid,
+                                "error": e.to_string(),
+                            }),
+                        ));
+                        break;
+                    }
+                }
             }
-        } else {
-            self.error_response(id, McpError::PromptNotFound(request.name))
-        }
+        });
+
+        self.subscriptions
+            .write()
+            .await
+            .insert(subscription_id.clone(), handle.abort_handle());
+
+        Ok(crate::protocol::types::CallToolResult {
+            content: vec![crate::protocol::types::Content::Text(crate::protocol::types::TextContent {
+                r#type: "text".to_string(),
+                text: serde_json::json!({ "subscription_id": subscription_id }).to_string(),
+            })],
+            is_error: false,
+        })
     }
 
-    async fn generate_dockerfile_prompt(
+    // Picks the `DockerBackend` a `tools/call` should run against and
+    // acquires one of its `max_jobs` permits before returning, releasing it
+    // when the caller drops the permit. Resolution order: an explicit
+    // `connection` argument always wins; otherwise a call naming a
+    // container/compose project sticks to whichever endpoint last served
+    // it (see `resource_owners`); otherwise `pick_endpoint` load-balances
+    // across every endpoint with a free permit, preferring the highest
+    // `speed` weight and breaking ties by fewest jobs in flight.
+    async fn resolve_docker(
         &self,
-        args: Option<HashMap<String, String>>,
-    ) -> Result<GetPromptResult, McpError> {
-        let args = args.unwrap_or_default();
-        let app_type = args
-            .get("app_type")
-            .ok_or_else(|| McpError::InvalidParams("Missing required argument 'app_type'".to_string()))?;
-        
-        let version = args.get("version").map(|s| s.as_str()).unwrap_or("latest");
-        let production = args.get("production").map(|s| s.as_str()).unwrap_or("yes") == "yes";
+        args: &serde_json::Value,
+    ) -> Result<(Arc<DockerBackend>, tokio::sync::OwnedSemaphorePermit), McpError> {
+        let resource_key = Self::resource_key(args);
+
+        let name = match args.get("connection").and_then(|v| v.as_str()) {
+            Some("default") => "default".to_string(),
+            Some(requested) => {
+                if !self.connections.read().await.contains_key(requested) {
+                    return Err(McpError::InvalidParams(format!("Unknown connection '{}'", requested)));
+                }
+                requested.to_string()
+            }
+            None => match &resource_key {
+                Some(key) => match self.resource_owners.read().await.get(key).cloned() {
+                    Some(owner) => owner,
+                    None => self.pick_endpoint().await,
+                },
+                None => self.pick_endpoint().await,
+            },
+        };
 
-        let mut prompt_text = format!(
-            "Generate an optimized Dockerfile for a {} application",
-            app_type
-        );
+        let docker = self.backend_for(&name).await?;
+        let permit = self.acquire_permit(&name).await?;
 
-        if version != "latest" {
-            prompt_text.push_str(&format!(", using version {}", version));
+        if let Some(key) = resource_key {
+            self.resource_owners.write().await.insert(key, name);
         }
 
-        if production {
-            prompt_text.push_str(", optimized for production use.");
-            prompt_text.push_str("\n\nThe Dockerfile should include:");
-            prompt_text.push_str("\n- Multi-stage builds for smaller final image");
-            prompt_text.push_str("\n- Proper security practices (non-root user, minimal permissions)");
-            prompt_text.push_str("\n- Optimization for caching during builds");
-            prompt_text.push_str("\n- Health checks and proper signal handling");
+        Ok((docker, permit))
+    }
+
+    // Extracts the key `resource_owners` pins a tool call's target
+    // container or compose project under, from whichever of the two
+    // arguments the call carries.
+    fn resource_key(args: &serde_json::Value) -> Option<String> {
+        if let Some(id) = args.get("container_id").and_then(|v| v.as_str()) {
+            return Some(format!("container:{}", id));
+        }
+        if let Some(dir) = args.get("project_directory").and_then(|v| v.as_str()) {
+            return Some(format!("project:{}", dir));
+        }
+        None
+    }
+
+    // Resolves an endpoint name (`"default"` or a `connections` key) to its
+    // `DockerBackend`.
+    async fn backend_for(&self, name: &str) -> Result<Arc<DockerBackend>, McpError> {
+        if name == "default" {
+            Ok(self.docker_client.clone())
         } else {
-            prompt_text.push_str(", configured for development.");
-            prompt_text.push_str("\n\nThe Dockerfile should include:");
-            prompt_text.push_str("\n- Fast rebuilds and good developer experience");
-            prompt_text.push_str("\n- Volume mounting for code changes");
-            prompt_text.push_str("\n- Debugging tools included");
+            self.connections
+                .read()
+                .await
+                .get(name)
+                .cloned()
+                .ok_or_else(|| McpError::InvalidParams(format!("Unknown connection '{}'", name)))
         }
+    }
 
-        prompt_text.push_str("\n\nPlease include comments explaining key decisions.");
+    async fn acquire_permit(&self, name: &str) -> Result<tokio::sync::OwnedSemaphorePermit, McpError> {
+        let semaphore = self
+            .endpoint_weights
+            .get(name)
+            .ok_or_else(|| McpError::InvalidParams(format!("Unknown connection '{}'", name)))?
+            .semaphore
+            .clone();
+        semaphore
+            .acquire_owned()
+            .await
+            .map_err(|_| McpError::InternalError(format!("Endpoint '{}' semaphore closed", name)))
+    }
 
-        let messages = vec![crate::protocol::types::PromptMessage {
-            role: "user".to_string(),
-            content: crate::protocol::types::PromptContent {
+    // Every configured endpoint name, `"default"` first then the rest of
+    // `connections` in a stable order.
+    fn endpoint_names(&self) -> Vec<String> {
+        let mut names: Vec<String> =
+            self.endpoint_weights.keys().filter(|name| name.as_str() != "default").cloned().collect();
+        names.sort();
+        let mut all = vec!["default".to_string()];
+        all.extend(names);
+        all
+    }
+
+    // Picks the endpoint `resolve_docker` should route an unpinned call to:
+    // among endpoints with a free job slot, the highest `speed`, ties
+    // broken by fewest jobs currently in flight. Falls back to the same
+    // ranking over every endpoint (ignoring free slots) if none are free,
+    // so the caller just waits on `acquire_permit` instead of failing.
+    async fn pick_endpoint(&self) -> String {
+        let mut free_best: Option<(String, f64, usize)> = None;
+        let mut overall_best: Option<(String, f64, usize)> = None;
+
+        for (name, weight) in &self.endpoint_weights {
+            let available = weight.semaphore.available_permits();
+            let in_flight = weight.max_jobs.saturating_sub(available);
+            let better = |current: &Option<(String, f64, usize)>| match current {
+                None => true,
+                Some((_, speed, flight)) => weight.speed > *speed || (weight.speed == *speed && in_flight < *flight),
+            };
+
+            if better(&overall_best) {
+                overall_best = Some((name.clone(), weight.speed, in_flight));
+            }
+            if available > 0 && better(&free_best) {
+                free_best = Some((name.clone(), weight.speed, in_flight));
+            }
+        }
+
+        free_best.or(overall_best).map(|(name, _, _)| name).unwrap_or_else(|| "default".to_string())
+    }
+
+    // `endpoint-ping` tool: reports whether each configured endpoint
+    // answers a Docker version query.
+    async fn endpoint_ping(&self) -> Result<crate::protocol::types::CallToolResult, McpError> {
+        let mut lines = Vec::new();
+        for name in self.endpoint_names() {
+            let backend = self.backend_for(&name).await?;
+            match backend.get_docker_version().await {
+                Ok(_) => lines.push(format!("{}: reachable (backend: {})", name, backend.active_backend())),
+                Err(e) => lines.push(format!("{}: unreachable - {}", name, e)),
+            }
+        }
+
+        Ok(crate::protocol::types::CallToolResult {
+            content: vec![crate::protocol::types::Content::Text(crate::protocol::types::TextContent {
                 r#type: "text".to_string(),
-                text: Some(prompt_text),
-                resource: None,
-            },
-        }];
+                text: lines.join("\n"),
+            })],
+            is_error: false,
+        })
+    }
+
+    // `endpoint-stats` tool: container/image counts plus the scheduling
+    // inputs (`speed`, `max_jobs`, jobs currently in flight) `pick_endpoint`
+    // uses, per configured endpoint.
+    async fn endpoint_stats(&self) -> Result<crate::protocol::types::CallToolResult, McpError> {
+        fn count(result: Result<crate::protocol::types::CallToolResult, McpError>) -> Option<usize> {
+            let content = result.ok()?.content.into_iter().next()?;
+            let crate::protocol::types::Content::Text(text) = content else {
+                return None;
+            };
+            serde_json::from_str::<serde_json::Value>(&text.text).ok()?.as_array().map(|a| a.len())
+        }
+
+        let mut entries = Vec::new();
+        for name in self.endpoint_names() {
+            let backend = self.backend_for(&name).await?;
+            let weight = self.endpoint_weights.get(&name);
+            let containers = count(backend.list_containers(serde_json::json!({ "all": true })).await);
+            let images = count(backend.list_images(serde_json::json!({})).await);
+
+            entries.push(serde_json::json!({
+                "name": name,
+                "containers": containers,
+                "images": images,
+                "speed": weight.map(|w| w.speed),
+                "max_jobs": weight.map(|w| w.max_jobs),
+                "jobs_in_flight": weight.map(|w| w.max_jobs.saturating_sub(w.semaphore.available_permits())),
+            }));
+        }
+
+        Ok(crate::protocol::types::CallToolResult {
+            content: vec![crate::protocol::types::Content::Text(crate::protocol::types::TextContent {
+                r#type: "text".to_string(),
+                text: serde_json::to_string_pretty(&entries)?,
+            })],
+            is_error: false,
+        })
+    }
+
+    // `server-commands` tool: every command `process_request` has recorded
+    // stats for, with its call count, error count, and mean/max duration.
+    async fn server_commands(&self) -> Result<crate::protocol::types::CallToolResult, McpError> {
+        let stats = self.command_stats.read().await;
+        let mut entries: Vec<serde_json::Value> = stats
+            .iter()
+            .map(|(command, stat)| {
+                let mean_duration_ms = if stat.calls > 0 { stat.total_duration_ms / stat.calls } else { 0 };
+                serde_json::json!({
+                    "command": command,
+                    "calls": stat.calls,
+                    "errors": stat.errors,
+                    "mean_duration_ms": mean_duration_ms,
+                    "max_duration_ms": stat.max_duration_ms,
+                })
+            })
+            .collect();
+        entries.sort_by(|a, b| a["command"].as_str().cmp(&b["command"].as_str()));
+
+        Ok(crate::protocol::types::CallToolResult {
+            content: vec![crate::protocol::types::Content::Text(crate::protocol::types::TextContent {
+                r#type: "text".to_string(),
+                text: serde_json::to_string_pretty(&entries)?,
+            })],
+            is_error: false,
+        })
+    }
+
+    // `server-requests` tool: requests currently being dispatched, with
+    // elapsed time, as recorded by `process_request`.
+    async fn server_requests(&self) -> Result<crate::protocol::types::CallToolResult, McpError> {
+        let in_flight = self.in_flight.read().await;
+        let mut entries: Vec<serde_json::Value> = in_flight
+            .iter()
+            .map(|(id, req)| {
+                serde_json::json!({
+                    "id": id,
+                    "command": req.command,
+                    "elapsed_ms": req.started_at.elapsed().as_millis() as u64,
+                })
+            })
+            .collect();
+        entries.sort_by(|a, b| a["id"].as_str().cmp(&b["id"].as_str()));
+
+        Ok(crate::protocol::types::CallToolResult {
+            content: vec![crate::protocol::types::Content::Text(crate::protocol::types::TextContent {
+                r#type: "text".to_string(),
+                text: serde_json::to_string_pretty(&entries)?,
+            })],
+            is_error: false,
+        })
+    }
+
+    // `server-cancel` tool: fires the `CancellationToken` stored for the
+    // named in-flight request's id. A no-op from the server's point of
+    // view unless whatever that request is running polls the token.
+    async fn server_cancel(&self, args: serde_json::Value) -> Result<crate::protocol::types::CallToolResult, McpError> {
+        let id = args
+            .get("id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| McpError::InvalidParams("Missing id parameter".to_string()))?;
+
+        let in_flight = self.in_flight.read().await;
+        let request = in_flight
+            .get(id)
+            .ok_or_else(|| McpError::InvalidParams(format!("No in-flight request with id '{}'", id)))?;
+        request.cancel.cancel();
+
+        Ok(crate::protocol::types::CallToolResult {
+            content: vec![crate::protocol::types::Content::Text(crate::protocol::types::TextContent {
+                r#type: "text".to_string(),
+                text: format!("Cancellation signalled for request {}", id),
+            })],
+            is_error: false,
+        })
+    }
+
+    // Returns `container_id`'s mount table, fetching and caching it via
+    // `DockerBackend::get_container_mounts` on first use.
+    async fn mounts_for(&self, container_id: &str) -> Result<Vec<crate::docker::MountInfo>, McpError> {
+        if let Some(mounts) = self.mount_cache.read().await.get(container_id) {
+            return Ok(mounts.clone());
+        }
+
+        let mounts = self.docker_client.get_container_mounts(container_id).await?;
+        self.mount_cache.write().await.insert(container_id.to_string(), mounts.clone());
+        Ok(mounts)
+    }
+
+    // Rewrites `path` in place from a container-internal path to its host
+    // equivalent for any tool call whose arguments carry both
+    // `container_id` and `path` — the same translation `resolve_path`
+    // exposes explicitly, applied transparently so file-touching tools
+    // never have to call `docker::paths` themselves. A no-op for tools
+    // that don't have both fields, or whose `path` isn't under any mount.
+    async fn rewrite_container_path(&self, arguments: &mut serde_json::Value) -> Result<(), McpError> {
+        let (container_id, path) = match (
+            arguments.get("container_id").and_then(|v| v.as_str()).map(str::to_string),
+            arguments.get("path").and_then(|v| v.as_str()).map(str::to_string),
+        ) {
+            (Some(container_id), Some(path)) => (container_id, path),
+            _ => return Ok(()),
+        };
+
+        let mounts = self.mounts_for(&container_id).await?;
+        if let crate::docker::paths::ResolvedPath::Translated { path: host_path, .. } = crate::docker::paths::to_host(&mounts, &path) {
+            arguments["path"] = serde_json::Value::String(host_path);
+        }
+        Ok(())
+    }
+
+    // `container-exec` tool: runs a command to completion inside a
+    // container and returns its combined output plus exit code, sourced
+    // from the same `DockerBackend::start_exec`/`ExecStream` demuxing
+    // `docker/exec/start` uses. That lets each chunk double as a
+    // `docker/exec/chunk` notification as it arrives, instead of the whole
+    // command's output only appearing once it exits and the buffer is
+    // flushed.
+    async fn container_exec(
+        &self,
+        docker_client: Arc<DockerBackend>,
+        args: serde_json::Value,
+    ) -> Result<crate::protocol::types::CallToolResult, McpError> {
+        use futures::StreamExt;
+
+        docker_client.check_read_only("container-exec")?;
+
+        let container_id = args
+            .get("container_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| McpError::InvalidParams("Missing container_id parameter".to_string()))?
+            .to_string();
+
+        let cmd: Vec<String> = args
+            .get("cmd")
+            .and_then(|v| v.as_array())
+            .ok_or_else(|| McpError::InvalidParams("Missing cmd parameter".to_string()))?
+            .iter()
+            .filter_map(|v| v.as_str().map(String::from))
+            .collect();
+
+        let working_dir = args.get("working_dir").and_then(|v| v.as_str()).map(String::from);
+        let env: Option<Vec<String>> = args
+            .get("env")
+            .and_then(|v| v.as_array())
+            .map(|arr| arr.iter().filter_map(|v| v.as_str().map(String::from)).collect());
+        let tty = args.get("tty").and_then(|v| v.as_bool()).unwrap_or(false);
+
+        let (exec_id, mut handle) = docker_client.start_exec(&container_id, cmd, working_dir, env, tty).await?;
+
+        // Not registered in `exec_sessions`: a one-shot exec has no stdin to
+        // forward and nothing for `docker/exec/stdin`/`docker/unsubscribe`
+        // to find, so the id only exists to let a client correlate these
+        // notifications with this particular call.
+        let subscription_id = uuid::Uuid::new_v4().to_string();
+        let max_log_size = self.config.docker.max_log_size;
+        let mut log_text = String::new();
+
+        while let Some(chunk) = handle.output.next().await {
+            let chunk = chunk?;
+            let stream_name = match chunk.stream {
+                crate::docker::ExecStream::Stdout => "stdout",
+                crate::docker::ExecStream::Stderr => "stderr",
+                crate::docker::ExecStream::Stdin => continue,
+            };
+            let text = String::from_utf8_lossy(&chunk.data).into_owned();
+
+            let _ = self.notification_tx.send(JsonRpcNotification::new(
+                "docker/exec/chunk",
+                serde_json::json!({
+                    "subscription_id": subscription_id,
+                    "stream": stream_name,
+                    "text": text,
+                }),
+            ));
+
+            log_text.push_str(&format!("[{}] {}\n", stream_name.to_uppercase(), text));
+            if log_text.len() > max_log_size {
+                log_text.truncate(max_log_size);
+                log_text.push_str("\n... (output truncated due to size limit)");
+                break;
+            }
+        }
+
+        let exit_code = docker_client.exec_exit_code(&exec_id).await.ok().flatten();
+        let _ = self.notification_tx.send(JsonRpcNotification::new(
+            "docker/exec/exit",
+            serde_json::json!({ "subscription_id": subscription_id, "exit_code": exit_code }),
+        ));
+
+        if let Some(code) = exit_code {
+            log_text.push_str(&format!("\n(exit code: {})", code));
+        }
+
+        Ok(crate::protocol::types::CallToolResult {
+            content: vec![crate::protocol::types::Content::Text(crate::protocol::types::TextContent {
+                r#type: "text".to_string(),
+                text: log_text,
+            })],
+            is_error: exit_code.is_some_and(|code| code != 0),
+        })
+    }
+
+    /// Parses `wait-for-container`'s `conditions` array into
+    /// `docker::wait::WaitCondition`s and runs `docker::wait::wait_for_container`
+    /// to completion, reporting the terminal state plus elapsed time.
+    async fn wait_for_container(
+        &self,
+        docker_client: Arc<DockerBackend>,
+        args: serde_json::Value,
+    ) -> Result<crate::protocol::types::CallToolResult, McpError> {
+        use crate::docker::wait::WaitCondition;
+
+        let container_id = args
+            .get("container_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| McpError::InvalidParams("Missing container_id parameter".to_string()))?;
+
+        let raw_conditions = args
+            .get("conditions")
+            .and_then(|v| v.as_array())
+            .ok_or_else(|| McpError::InvalidParams("Missing conditions parameter".to_string()))?;
+
+        let conditions: Vec<WaitCondition> = raw_conditions
+            .iter()
+            .map(|c| {
+                let condition_type = c
+                    .get("type")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| McpError::InvalidParams("Condition is missing a type".to_string()))?;
+
+                match condition_type {
+                    "healthcheck" => Ok(WaitCondition::Healthcheck),
+                    "running" => Ok(WaitCondition::Running),
+                    "log_match" => {
+                        let pattern = c
+                            .get("pattern")
+                            .and_then(|v| v.as_str())
+                            .ok_or_else(|| McpError::InvalidParams("log_match condition is missing pattern".to_string()))?;
+                        let pattern = regex::Regex::new(pattern)
+                            .map_err(|e| McpError::InvalidParams(format!("Invalid log_match pattern: {}", e)))?;
+                        Ok(WaitCondition::LogMatch(pattern))
+                    }
+                    "port_open" => {
+                        let host = c.get("host").and_then(|v| v.as_str()).unwrap_or("127.0.0.1").to_string();
+                        let port = c
+                            .get("port")
+                            .and_then(|v| v.as_u64())
+                            .ok_or_else(|| McpError::InvalidParams("port_open condition is missing port".to_string()))?
+                            as u16;
+                        Ok(WaitCondition::PortOpen { host, port })
+                    }
+                    other => Err(McpError::InvalidParams(format!("Unknown wait condition type: {}", other))),
+                }
+            })
+            .collect::<Result<Vec<_>, McpError>>()?;
+
+        let timeout_seconds = args.get("timeout_seconds").and_then(|v| v.as_u64()).unwrap_or(30);
+        let timeout = std::time::Duration::from_secs(timeout_seconds);
+        let outcome = crate::docker::wait::wait_for_container(&docker_client, container_id, &conditions, timeout).await?;
+
+        let state = match outcome.state {
+            crate::docker::types::ContainerState::Created => serde_json::json!("created"),
+            crate::docker::types::ContainerState::Running => serde_json::json!("running"),
+            crate::docker::types::ContainerState::Healthy => serde_json::json!("healthy"),
+            crate::docker::types::ContainerState::Exited(code) => serde_json::json!({ "exited": code }),
+            crate::docker::types::ContainerState::Dead => serde_json::json!("dead"),
+        };
 
-        Ok(GetPromptResult {
-            description: Some(format!(
-                "Optimized Dockerfile for {} {} application",
-                if production { "production" } else { "development" },
-                app_type
-            )),
-            messages,
+        Ok(crate::protocol::types::CallToolResult {
+            content: vec![crate::protocol::types::Content::Text(crate::protocol::types::TextContent {
+                r#type: "text".to_string(),
+                text: serde_json::json!({
+                    "state": state,
+                    "elapsed_ms": outcome.elapsed.as_millis(),
+                })
+                .to_string(),
+            })],
+            is_error: false,
         })
     }
 
-    async fn generate_compose_prompt(
+    /// Wraps the `compose-up` tool: forwards to `DockerClient::compose_up`,
+    /// then (only on success) tracks the project under the connection it
+    /// resolved to (`resolve_docker` already recorded this in
+    /// `resource_owners` keyed `"project:<dir>"`) so
+    /// `ShutdownHandle::begin_shutdown` (when `cleanup_on_exit` is set) and
+    /// `run_diagnostic`'s planned-cleanup-set report can find it later.
+    /// Tracking is idempotent, since re-running `compose-up` against an
+    /// already-up project is a supported no-op/reconcile call, not a new
+    /// bring-up.
+    async fn compose_up(
         &self,
-        args: Option<HashMap<String, String>>,
-    ) -> Result<GetPromptResult, McpError> {
-        let args = args.unwrap_or_default();
-        let scenario = args
-            .get("scenario")
-            .ok_or_else(|| McpError::InvalidParams("Missing required argument 'scenario'".to_string()))?;
-        
-        let services = args
-            .get("services")
-            .ok_or_else(|| McpError::InvalidParams("Missing required argument 'services'".to_string()))?;
-        
-        let with_volumes = args.get("with_volumes").map(|s| s.as_str()).unwrap_or("yes") == "yes";
+        docker: Arc<DockerBackend>,
+        args: serde_json::Value,
+    ) -> Result<crate::protocol::types::CallToolResult, McpError> {
+        let result = docker.compose_up(args.clone()).await?;
+
+        if let Some(project_directory) = args.get("project_directory").and_then(|v| v.as_str()) {
+            let connection = self
+                .resource_owners
+                .read()
+                .await
+                .get(&format!("project:{}", project_directory))
+                .cloned()
+                .unwrap_or_else(|| "default".to_string());
+
+            let mut projects = self.managed_compose_projects.write().await;
+            let entry = (connection, project_directory.to_string());
+            if !projects.contains(&entry) {
+                projects.push(entry);
+            }
+        }
 
-        let mut prompt_text = format!(
-            "Generate a Docker Compose configuration for a {} scenario",
-            scenario
-        );
+        Ok(result)
+    }
 
-        prompt_text.push_str(&format!(" that includes the following services: {}.", services));
+    /// Wraps the `compose-down` tool: forwards to
+    /// `DockerClient::compose_down`, then stops tracking the project
+    /// regardless of whether `cleanup_on_exit` is on — it's gone either
+    /// way, so there's nothing left for shutdown to tear down.
+    async fn compose_down(
+        &self,
+        docker: Arc<DockerBackend>,
+        args: serde_json::Value,
+    ) -> Result<crate::protocol::types::CallToolResult, McpError> {
+        let result = docker.compose_down(args.clone()).await?;
 
-        if with_volumes {
-            prompt_text.push_str("\n\nInclude persistent volumes for data that should be preserved across container restarts.");
+        if let Some(project_directory) = args.get("project_directory").and_then(|v| v.as_str()) {
+            self.managed_compose_projects.write().await.retain(|(_, dir)| dir != project_directory);
         }
 
-        prompt_text.push_str("\n\nThe configuration should include:");
-        prompt_text.push_str("\n- Proper networking between services");
-        prompt_text.push_str("\n- Environment variables for configuration");
-        prompt_text.push_str("\n- Health checks where appropriate");
-        prompt_text.push_str("\n- Restart policies for reliability");
-        prompt_text.push_str("\n\nPlease include comments explaining the purpose of each service and any important configuration details.");
+        Ok(result)
+    }
 
-        let messages = vec![crate::protocol::types::PromptMessage {
-            role: "user".to_string(),
-            content: crate::protocol::types::PromptContent {
-                r#type: "text".to_string(),
-                text: Some(prompt_text),
-                resource: None,
+    // Explicit `resolve_path` tool: translates `path` through
+    // `container_id`'s mount table, trying both directions when
+    // `direction` isn't given since a caller may hand either side.
+    async fn resolve_path(&self, args: serde_json::Value) -> Result<crate::protocol::types::CallToolResult, McpError> {
+        let container_id = args
+            .get("container_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| McpError::InvalidParams("Missing container_id parameter".to_string()))?;
+
+        let path = args
+            .get("path")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| McpError::InvalidParams("Missing path parameter".to_string()))?;
+
+        let direction = args.get("direction").and_then(|v| v.as_str());
+
+        let mounts = self.mounts_for(container_id).await?;
+
+        let resolved = match direction {
+            Some("to_host") => crate::docker::paths::to_host(&mounts, path),
+            Some("to_container") => crate::docker::paths::to_container(&mounts, path),
+            Some(other) => {
+                return Err(McpError::InvalidParams(format!(
+                    "Invalid direction '{}': expected 'to_host' or 'to_container'",
+                    other
+                )))
+            }
+            None => match crate::docker::paths::to_host(&mounts, path) {
+                resolved @ crate::docker::paths::ResolvedPath::Translated { .. } => resolved,
+                crate::docker::paths::ResolvedPath::ContainerOnly(_) => crate::docker::paths::to_container(&mounts, path),
             },
-        }];
+        };
 
-        Ok(GetPromptResult {
-            description: Some(format!(
-                "Docker Compose configuration for {} scenario with services: {}",
-                scenario, services
-            )),
-            messages,
+        let result = match resolved {
+            crate::docker::paths::ResolvedPath::Translated { path, read_only } => serde_json::json!({
+                "path": path,
+                "translated": true,
+                "read_only": read_only,
+            }),
+            crate::docker::paths::ResolvedPath::ContainerOnly(path) => serde_json::json!({
+                "path": path,
+                "translated": false,
+            }),
+        };
+
+        Ok(crate::protocol::types::CallToolResult {
+            content: vec![crate::protocol::types::Content::Text(crate::protocol::types::TextContent {
+                r#type: "text".to_string(),
+                text: result.to_string(),
+            })],
+            is_error: false,
         })
     }
 
-    // Implementation of the diagnostic tool
-    async fn run_diagnostic(&self, args: serde_json::Value) -> Result<crate::protocol::types::CallToolResult, crate::protocol::error::McpError> {
-        let check_docker = args.get("check_docker").and_then(|v| v.as_bool()).unwrap_or(true);
-        let check_compose = args.get("check_compose").and_then(|v| v.as_bool()).unwrap_or(true);
-        let list_env_vars = args.get("list_env_vars").and_then(|v| v.as_bool()).unwrap_or(false);
-        
-        let mut results = Vec::new();
-        
-        results.push("=== Docker MCP Server Diagnostics ===".to_string());
-        results.push(format!("Server name: {}", self.config.server.name));
-        results.push(format!("Server version: {}", self.config.server.version));
-        results.push(format!("Transport type: {:?}", self.config.server.transport));
-        results.push(format!("Request timeout: {:?}", self.config.server.request_timeout));
-        results.push(format!("Docker host: {}", self.config.docker.host));
-        results.push(format!("Read-only mode: {}", self.config.docker.read_only));
-        
-        if check_docker {
-            results.push("\n=== Docker Connectivity ===".to_string());
-            match self.docker_client.get_docker_version().await {
-                Ok(version) => {
-                    let parsed: Result<serde_json::Value, _> = serde_json::from_str(&version);
-                    match parsed {
-                        Ok(v) => {
-                            if let Some(api_version) = v.get("ApiVersion").and_then(|v| v.as_str()) {
-                                results.push(format!("Docker API version: {}", api_version));
-                            }
-                            if let Some(engine_version) = v.get("Version").and_then(|v| v.as_str()) {
-                                results.push(format!("Docker Engine version: {}", engine_version));
-                            }
-                            results.push("Docker connection: OK".to_string());
-                        },
-                        Err(_) => {
-                            results.push(format!("Docker connection: OK (raw data: {})", version));
-                        }
-                    }
-                },
-                Err(e) => {
-                    results.push(format!("Docker connection: FAILED - {}", e));
-                    results.push("Possible causes:".to_string());
-                    results.push(" - Docker daemon not running".to_string());
-                    results.push(" - Incorrect Docker host configuration".to_string());
-                    results.push(" - Permission issues with Docker socket".to_string());
-                    
-                    if self.config.docker.host.starts_with("unix://") {
-                        // Check if the Docker socket exists
-                        let socket_path = self.config.docker.host.trim_start_matches("unix://");
-                        if let Ok(metadata) = std::fs::metadata(socket_path) {
-                            results.push(format!("Docker socket exists: {}", socket_path));
-                            
-                            // Check if it's a socket
-                            #[cfg(unix)]
-                            {
-                                use std::os::unix::fs::FileTypeExt;
-                                if metadata.file_type().is_socket() {
-                                    results.push("File is a valid socket: YES".to_string());
-                                } else {
-                                    results.push("File is a valid socket: NO".to_string());
-                                }
-                            }
-                        } else {
-                            results.push(format!("Docker socket not found at: {}", socket_path));
-                        }
-                    }
+    fn oci_runtime(&self) -> Result<&Arc<crate::oci::OciRuntime>, McpError> {
+        self.oci_runtime
+            .as_ref()
+            .ok_or_else(|| McpError::OperationNotPermitted("OCI runtime backend is not configured".to_string()))
+    }
+
+    fn require_id(args: &serde_json::Value) -> Result<&str, McpError> {
+        args.get("id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| McpError::InvalidParams("Missing id parameter".to_string()))
+    }
+
+    fn text_result(text: String) -> crate::protocol::types::CallToolResult {
+        crate::protocol::types::CallToolResult {
+            content: vec![crate::protocol::types::Content::Text(crate::protocol::types::TextContent {
+                r#type: "text".to_string(),
+                text,
+            })],
+            is_error: false,
+        }
+    }
+
+    async fn oci_state(&self, args: serde_json::Value) -> Result<crate::protocol::types::CallToolResult, McpError> {
+        let id = Self::require_id(&args)?;
+        let state = self.oci_runtime()?.state(id).await?;
+        Ok(Self::text_result(serde_json::to_string_pretty(&state)?))
+    }
+
+    async fn oci_create(&self, args: serde_json::Value) -> Result<crate::protocol::types::CallToolResult, McpError> {
+        let id = Self::require_id(&args)?;
+        self.oci_runtime()?.create(id).await?;
+        Ok(Self::text_result(format!("Container {} created", id)))
+    }
+
+    async fn oci_start(&self, args: serde_json::Value) -> Result<crate::protocol::types::CallToolResult, McpError> {
+        let id = Self::require_id(&args)?;
+        self.oci_runtime()?.start(id).await?;
+        Ok(Self::text_result(format!("Container {} started", id)))
+    }
+
+    async fn oci_kill(&self, args: serde_json::Value) -> Result<crate::protocol::types::CallToolResult, McpError> {
+        let id = Self::require_id(&args)?;
+        let signal = args.get("signal").and_then(|v| v.as_str()).unwrap_or("SIGTERM");
+        self.oci_runtime()?.kill(id, signal).await?;
+        Ok(Self::text_result(format!("Sent {} to container {}", signal, id)))
+    }
+
+    async fn oci_delete(&self, args: serde_json::Value) -> Result<crate::protocol::types::CallToolResult, McpError> {
+        let id = Self::require_id(&args)?;
+        self.oci_runtime()?.delete(id).await?;
+        Ok(Self::text_result(format!("Container {} deleted", id)))
+    }
+
+    async fn stop_subscription(&self, args: serde_json::Value) -> Result<crate::protocol::types::CallToolResult, McpError> {
+        let subscription_id = args
+            .get("subscription_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| McpError::InvalidParams("Missing subscription_id parameter".to_string()))?;
+
+        let removed = self.subscriptions.write().await.remove(subscription_id);
+        self.exec_sessions.write().await.remove(subscription_id);
+
+        match removed {
+            Some(handle) => {
+                handle.abort();
+                Ok(crate::protocol::types::CallToolResult {
+                    content: vec![crate::protocol::types::Content::Text(crate::protocol::types::TextContent {
+                        r#type: "text".to_string(),
+                        text: format!("Subscription {} cancelled", subscription_id),
+                    })],
+                    is_error: false,
+                })
+            }
+            None => Err(McpError::InvalidParams(format!("Unknown subscription_id: {}", subscription_id))),
+        }
+    }
+
+    // Starts a command inside a container via `DockerBackend::start_exec`
+    // and spawns a task that demultiplexes its output into
+    // `docker/exec/chunk` notifications, finishing with a single
+    // `docker/exec/exit` notification carrying the exit code. The returned
+    // subscription id doubles as the key for `docker/exec/stdin` and
+    // `docker/unsubscribe`.
+    async fn start_exec(&self, args: serde_json::Value) -> Result<crate::protocol::types::CallToolResult, McpError> {
+        let (docker_client, _permit) = self.resolve_docker(&args).await?;
+        docker_client.check_read_only("docker/exec/start")?;
+
+        let container_id = args
+            .get("container_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| McpError::InvalidParams("Missing container_id parameter".to_string()))?
+            .to_string();
+
+        let cmd: Vec<String> = args
+            .get("cmd")
+            .and_then(|v| v.as_array())
+            .ok_or_else(|| McpError::InvalidParams("Missing cmd parameter".to_string()))?
+            .iter()
+            .filter_map(|v| v.as_str().map(String::from))
+            .collect();
+
+        let working_dir = args.get("working_dir").and_then(|v| v.as_str()).map(String::from);
+        let env: Option<Vec<String>> = args.get("env").and_then(|v| v.as_array()).map(|arr| {
+            arr.iter().filter_map(|v| v.as_str().map(String::from)).collect()
+        });
+        let pty = args.get("pty").and_then(|v| v.as_bool()).unwrap_or(false);
+
+        let (exec_id, handle) = docker_client
+            .start_exec(&container_id, cmd, working_dir, env, pty)
+            .await?;
+
+        if pty {
+            let rows = args.get("rows").and_then(|v| v.as_u64()).map(|v| v as u16);
+            let cols = args.get("cols").and_then(|v| v.as_u64()).map(|v| v as u16);
+            if let (Some(rows), Some(cols)) = (rows, cols) {
+                // A brand-new PTY session resizing itself isn't critical
+                // enough to fail the whole call over; `cli` backends are
+                // expected to reject this, and a default-size `api` session
+                // is still usable.
+                if let Err(e) = docker_client.resize_exec(&exec_id, rows, cols).await {
+                    log::debug!("Initial PTY resize for exec {} failed: {}", exec_id, e);
                 }
             }
         }
-        
-        if check_compose {
-            results.push("\n=== Docker Compose ===".to_string());
-            
-            let compose_path = &self.config.docker.compose_path;
-            results.push(format!("Docker Compose path: {:?}", compose_path));
-            
-            // Check if the compose binary exists
-            if compose_path.exists() {
-                results.push("Docker Compose binary exists: YES".to_string());
-                
-                // Try to run docker-compose version
-                let output = tokio::process::Command::new(compose_path)
-                    .arg("version")
-                    .output()
-                    .await;
-                
-                match output {
-                    Ok(output) => {
-                        if output.status.success() {
-                            let version = String::from_utf8_lossy(&output.stdout);
-                            results.push(format!("Docker Compose version: {}", version.trim()));
-                            results.push("Docker Compose command: OK".to_string());
-                        } else {
-                            let stderr = String::from_utf8_lossy(&output.stderr);
-                            results.push(format!("Docker Compose command failed: {}", stderr.trim()));
-                        }
-                    },
+
+        let subscription_id = uuid::Uuid::new_v4().to_string();
+        self.exec_sessions.write().await.insert(
+            subscription_id.clone(),
+            ExecSession { stdin_tx: handle.stdin_tx, exec_id: exec_id.clone(), backend: docker_client.clone() },
+        );
+
+        let notification_tx = self.notification_tx.clone();
+        let sub_id = subscription_id.clone();
+        let subscriptions = self.subscriptions.clone();
+        let exec_sessions = self.exec_sessions.clone();
+        let mut output = handle.output;
+
+        let task = tokio::spawn(async move {
+            use futures::StreamExt;
+            use crate::docker::ExecStream;
+
+            while let Some(chunk) = output.next().await {
+                match chunk {
+                    Ok(chunk) => {
+                        let stream_name = match chunk.stream {
+                            ExecStream::Stdout => "stdout",
+                            ExecStream::Stderr => "stderr",
+                            ExecStream::Stdin => continue,
+                        };
+                        let _ = notification_tx.send(JsonRpcNotification::new(
+                            "docker/exec/chunk",
+                            serde_json::json!({
+                                "subscription_id": sub_id,
+                                "stream": stream_name,
+                                "text": String::from_utf8_lossy(&chunk.data),
+                            }),
+                        ));
+                    }
                     Err(e) => {
-                        results.push(format!("Docker Compose command error: {}", e));
+                        let _ = notification_tx.send(JsonRpcNotification::new(
+                            "docker/exec/error",
+                            serde_json::json!({
+                                "subscription_id": sub_id,
+                                "error": e.to_string(),
+                            }),
+                        ));
+                        subscriptions.write().await.remove(&sub_id);
+                        exec_sessions.write().await.remove(&sub_id);
+                        return;
                     }
                 }
-            } else {
-                results.push("Docker Compose binary exists: NO".to_string());
-                results.push("Possible causes:".to_string());
-                results.push(" - Docker Compose not installed".to_string());
-                results.push(" - Incorrect path in configuration".to_string());
-                results.push(format!(" - Current working directory: {:?}", std::env::current_dir().ok()));
             }
+
+            let exit_code = docker_client.exec_exit_code(&exec_id).await.ok().flatten();
+            let _ = notification_tx.send(JsonRpcNotification::new(
+                "docker/exec/exit",
+                serde_json::json!({
+                    "subscription_id": sub_id,
+                    "exit_code": exit_code,
+                }),
+            ));
+
+            // The command has exited and every chunk's been delivered, so
+            // this handle is dead; drop it rather than leaking an entry
+            // that `docker/exec/stdin`/`docker/exec/resize` would otherwise
+            // find and fail confusingly late against a finished session.
+            subscriptions.write().await.remove(&sub_id);
+            exec_sessions.write().await.remove(&sub_id);
+        });
+
+        self.subscriptions
+            .write()
+            .await
+            .insert(subscription_id.clone(), task.abort_handle());
+
+        Ok(crate::protocol::types::CallToolResult {
+            content: vec![crate::protocol::types::Content::Text(crate::protocol::types::TextContent {
+                r#type: "text".to_string(),
+                text: serde_json::json!({ "subscription_id": subscription_id }).to_string(),
+            })],
+            is_error: false,
+        })
+    }
+
+    async fn send_exec_stdin(&self, args: serde_json::Value) -> Result<crate::protocol::types::CallToolResult, McpError> {
+        let subscription_id = args
+            .get("subscription_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| McpError::InvalidParams("Missing subscription_id parameter".to_string()))?;
+
+        let data = args
+            .get("data")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| McpError::InvalidParams("Missing data parameter".to_string()))?;
+
+        let sender = self.exec_sessions.read().await.get(subscription_id).map(|s| s.stdin_tx.clone());
+
+        match sender {
+            Some(sender) => {
+                sender
+                    .send(data.as_bytes().to_vec())
+                    .await
+                    .map_err(|_| McpError::InvalidParams(format!("Exec session {} is no longer running", subscription_id)))?;
+
+                Ok(crate::protocol::types::CallToolResult {
+                    content: vec![crate::protocol::types::Content::Text(crate::protocol::types::TextContent {
+                        r#type: "text".to_string(),
+                        text: "ok".to_string(),
+                    })],
+                    is_error: false,
+                })
+            }
+            None => Err(McpError::InvalidParams(format!("Unknown subscription_id: {}", subscription_id))),
         }
-        
-        if list_env_vars {
-            results.push("\n=== Environment Variables ===".to_string());
-            for (key, value) in std::env::vars() {
-                if key.starts_with("DOCKER_") || key.contains("MCP") || key.contains("RUST") {
-                    results.push(format!("{}={}", key, value));
+    }
+
+    /// Updates the terminal size of a `pty: true` `docker/exec/start`
+    /// session. Only the `api` Docker backend supports this; a `cli`-backed
+    /// session fails with `OperationNotPermitted`.
+    async fn resize_exec(&self, args: serde_json::Value) -> Result<crate::protocol::types::CallToolResult, McpError> {
+        let subscription_id = args
+            .get("subscription_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| McpError::InvalidParams("Missing subscription_id parameter".to_string()))?;
+
+        let rows = args
+            .get("rows")
+            .and_then(|v| v.as_u64())
+            .ok_or_else(|| McpError::InvalidParams("Missing rows parameter".to_string()))? as u16;
+        let cols = args
+            .get("cols")
+            .and_then(|v| v.as_u64())
+            .ok_or_else(|| McpError::InvalidParams("Missing cols parameter".to_string()))? as u16;
+
+        let (exec_id, backend) = self
+            .exec_sessions
+            .read()
+            .await
+            .get(subscription_id)
+            .map(|s| (s.exec_id.clone(), s.backend.clone()))
+            .ok_or_else(|| McpError::InvalidParams(format!("Unknown subscription_id: {}", subscription_id)))?;
+
+        backend.resize_exec(&exec_id, rows, cols).await?;
+
+        Ok(crate::protocol::types::CallToolResult {
+            content: vec![crate::protocol::types::Content::Text(crate::protocol::types::TextContent {
+                r#type: "text".to_string(),
+                text: "ok".to_string(),
+            })],
+            is_error: false,
+        })
+    }
+
+    /// "Simple" mode exec: runs a short-lived command to completion and
+    /// returns its buffered stdout/stderr/exit code directly in the tool
+    /// result, rather than streaming notifications like
+    /// `docker/exec/start`. Bounded by `docker.operation_timeout` since,
+    /// unlike the streaming form, there's no subscription for the caller to
+    /// cancel if the command hangs.
+    async fn run_exec(&self, args: serde_json::Value) -> Result<crate::protocol::types::CallToolResult, McpError> {
+        let (docker_client, _permit) = self.resolve_docker(&args).await?;
+        docker_client.check_read_only("docker/exec/run")?;
+
+        let container_id = args
+            .get("container_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| McpError::InvalidParams("Missing container_id parameter".to_string()))?
+            .to_string();
+
+        let cmd: Vec<String> = args
+            .get("cmd")
+            .and_then(|v| v.as_array())
+            .ok_or_else(|| McpError::InvalidParams("Missing cmd parameter".to_string()))?
+            .iter()
+            .filter_map(|v| v.as_str().map(String::from))
+            .collect();
+
+        let working_dir = args.get("working_dir").and_then(|v| v.as_str()).map(String::from);
+        let env: Option<Vec<String>> = args.get("env").and_then(|v| v.as_array()).map(|arr| {
+            arr.iter().filter_map(|v| v.as_str().map(String::from)).collect()
+        });
+
+        let operation_timeout = self.config.docker.operation_timeout;
+
+        let run = async {
+            use futures::StreamExt;
+            use crate::docker::ExecStream;
+
+            let (exec_id, handle) = docker_client
+                .start_exec(&container_id, cmd, working_dir, env, false)
+                .await?;
+
+            let mut output = handle.output;
+            let mut stdout = String::new();
+            let mut stderr = String::new();
+
+            while let Some(chunk) = output.next().await {
+                let chunk = chunk?;
+                match chunk.stream {
+                    ExecStream::Stdout => stdout.push_str(&String::from_utf8_lossy(&chunk.data)),
+                    ExecStream::Stderr => stderr.push_str(&String::from_utf8_lossy(&chunk.data)),
+                    ExecStream::Stdin => {}
                 }
             }
-        }
-        
-        let result_text = results.join("\n");
-        
+
+            let exit_code = docker_client.exec_exit_code(&exec_id).await.ok().flatten();
+
+            Ok::<_, McpError>((stdout, stderr, exit_code))
+        };
+
+        let (stdout, stderr, exit_code) = tokio::time::timeout(operation_timeout, run)
+            .await
+            .map_err(|_| McpError::OperationTimeout)??;
+
         Ok(crate::protocol::types::CallToolResult {
             content: vec![crate::protocol::types::Content::Text(crate::protocol::types::TextContent {
                 r#type: "text".to_string(),
-                text: result_text,
+                text: serde_json::json!({
+                    "stdout": stdout,
+                    "stderr": stderr,
+                    "exit_code": exit_code,
+                })
+                .to_string(),
             })],
             is_error: false,
         })
@@ -985,4 +3762,45 @@ impl McpServer {
             }),
         }
     }
-}
\ No newline at end of file
+}
+
+/// Extracts `<id>` from a `docker://container/<id>/logs` resource URI, the
+/// log-tailing shape `resources/subscribe` follows.
+fn parse_container_logs_uri(uri: &str) -> Option<String> {
+    uri.strip_prefix("docker://container/")?
+        .strip_suffix("/logs")
+        .map(String::from)
+}
+
+/// Extracts `<id>` from a bare `docker://container/<id>` or
+/// `docker://image/<id>` resource URI (i.e. *not* the `/logs` sub-resource),
+/// the lifecycle-event shape `resources/subscribe` follows. Returns the
+/// Docker event filter key (`"container"` or `"image"`) alongside the id.
+fn parse_lifecycle_uri(uri: &str) -> Option<(&'static str, String)> {
+    if let Some(id) = uri.strip_prefix("docker://container/") {
+        if !id.contains('/') && !id.is_empty() {
+            return Some(("container", id.to_string()));
+        }
+        return None;
+    }
+    if let Some(id) = uri.strip_prefix("docker://image/") {
+        if !id.is_empty() {
+            return Some(("image", id.to_string()));
+        }
+    }
+    None
+}
+
+/// Best-effort extraction of a Docker event's action (e.g. `"destroy"`,
+/// `"die"`, `"delete"`), tolerant of the casing differences between the
+/// `docker events --format '{{json .}}'` CLI output and a serialized
+/// bollard `EventMessage` - both backends feed the same `stream_events`
+/// caller, so neither shape can be assumed.
+fn event_action(event: &serde_json::Value) -> Option<&str> {
+    event
+        .get("Action")
+        .or_else(|| event.get("action"))
+        .or_else(|| event.get("status"))
+        .or_else(|| event.get("Status"))
+        .and_then(|v| v.as_str())
+}
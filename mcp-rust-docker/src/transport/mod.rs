@@ -0,0 +1,68 @@
+pub mod sse;
+pub mod stdio;
+pub mod tcp;
+pub mod websocket;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::protocol::error::McpError;
+use crate::protocol::types::{JsonRpcError, JsonRpcRequest, JsonRpcResponse};
+use crate::server::McpServer;
+
+/// Per-connection framing contract for transports that exchange a single
+/// JSON-RPC object per message over a point-to-point connection, as opposed
+/// to stdio's multi-task pipeline or SSE's HTTP+event-stream split. TCP and
+/// WebSocket both implement this directly against [`serve_connection`] below
+/// so each only has to describe how a message is framed on the wire.
+///
+/// `read_message` returns `Ok(None)` on a clean disconnect so callers can
+/// tell that apart from a framing error.
+pub trait Transport: Send {
+    async fn read_message(&mut self) -> Result<Option<JsonRpcRequest>, McpError>;
+    async fn write_message(&mut self, response: &JsonRpcResponse) -> Result<(), McpError>;
+}
+
+/// Drives a single `Transport` connection to completion: reads one request
+/// at a time, runs it through the same `McpServer::process_request` the
+/// stdio and SSE transports use (so the client's JSON-RPC `id` round-trips
+/// unchanged for correlation), under the server's configured timeout, then
+/// writes back the response. Shared by the TCP and WebSocket listeners.
+pub async fn serve_connection<T: Transport>(server: Arc<McpServer>, mut transport: T, request_timeout: Duration) {
+    loop {
+        let request = match transport.read_message().await {
+            Ok(Some(request)) => request,
+            Ok(None) => break,
+            Err(e) => {
+                log::error!("Transport read error: {}", e);
+                break;
+            }
+        };
+
+        let id = request.id.clone();
+        let method = request.method.clone();
+        log::info!("Received request: method={} id={:?}", method, id);
+
+        let response = match tokio::time::timeout(request_timeout, server.process_request(request)).await {
+            Ok(response) => response,
+            Err(_) => {
+                log::error!("Request timed out: method={} id={:?}", method, id);
+                JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    id,
+                    result: None,
+                    error: Some(JsonRpcError {
+                        code: -32603,
+                        message: "Request processing timed out".to_string(),
+                        data: None,
+                    }),
+                }
+            }
+        };
+
+        if let Err(e) = transport.write_message(&response).await {
+            log::error!("Transport write error: {}", e);
+            break;
+        }
+    }
+}
@@ -0,0 +1,220 @@
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::Arc;
+
+use axum::extract::{Query, State};
+use axum::response::sse::{Event, Sse};
+use axum::response::IntoResponse;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use futures::stream::Stream;
+use tokio::sync::{mpsc, RwLock};
+use tokio::time::Duration;
+use uuid::Uuid;
+
+use crate::config::types::TlsSettings;
+use crate::protocol::error::McpError;
+use crate::protocol::types::{JsonRpcError, JsonRpcId, JsonRpcRequest, JsonRpcResponse};
+use crate::server::McpServer;
+
+/// One entry per connected SSE client, keyed by session id. Responses and
+/// server-initiated events are pushed here and streamed out over the
+/// `GET /sse` connection that created the session.
+type SessionMap = Arc<RwLock<HashMap<String, mpsc::Sender<String>>>>;
+
+#[derive(Clone)]
+struct SseState {
+    server: Arc<McpServer>,
+    sessions: SessionMap,
+    request_timeout: Duration,
+}
+
+pub struct SseTransport {
+    server: Arc<McpServer>,
+    bind_address: String,
+    bind_port: u16,
+    tls: Option<TlsSettings>,
+}
+
+impl SseTransport {
+    pub fn new(server: McpServer) -> Self {
+        let bind_address = server.get_bind_address().to_string();
+        let bind_port = server.get_bind_port();
+        let tls = server.get_tls_settings().cloned();
+        Self {
+            server: Arc::new(server),
+            bind_address,
+            bind_port,
+            tls,
+        }
+    }
+
+    pub async fn run(&mut self) -> Result<(), McpError> {
+        let request_timeout = self.server.get_request_timeout();
+
+        let state = SseState {
+            server: self.server.clone(),
+            sessions: Arc::new(RwLock::new(HashMap::new())),
+            request_timeout,
+        };
+
+        let app = Router::new()
+            .route("/sse", get(handle_sse))
+            .route("/message", post(handle_message))
+            .with_state(state);
+
+        let addr: std::net::SocketAddr = format!("{}:{}", self.bind_address, self.bind_port)
+            .parse()
+            .map_err(|e| {
+                McpError::InternalError(format!(
+                    "Invalid bind address {}:{}: {}",
+                    self.bind_address, self.bind_port, e
+                ))
+            })?;
+
+        match &self.tls {
+            Some(tls) => {
+                log::info!("HTTP transport listening on https://{}", addr);
+
+                let config = axum_server::tls_rustls::RustlsConfig::from_pem_file(&tls.cert_path, &tls.key_path)
+                    .await
+                    .map_err(|e| {
+                        McpError::InternalError(format!(
+                            "Failed to load TLS cert '{}' / key '{}': {}",
+                            tls.cert_path.display(),
+                            tls.key_path.display(),
+                            e
+                        ))
+                    })?;
+
+                axum_server::bind_rustls(addr, config)
+                    .serve(app.into_make_service())
+                    .await
+                    .map_err(|e| McpError::InternalError(format!("HTTP transport error: {}", e)))?;
+            }
+            None => {
+                log::info!("HTTP transport listening on http://{}", addr);
+
+                let listener = tokio::net::TcpListener::bind(addr).await.map_err(|e| {
+                    McpError::InternalError(format!("Failed to bind HTTP listener on {}: {}", addr, e))
+                })?;
+
+                axum::serve(listener, app)
+                    .await
+                    .map_err(|e| McpError::InternalError(format!("HTTP transport error: {}", e)))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// `GET /sse` — opens a long-lived event stream. The first event advertises
+/// the `POST /message` endpoint (with this session's id attached) so the
+/// client knows where to send requests; every subsequent event is either a
+/// `JsonRpcResponse` or a server-initiated notification, serialized as JSON.
+async fn handle_sse(
+    State(state): State<SseState>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let session_id = Uuid::new_v4().to_string();
+    let (tx, mut rx) = mpsc::channel::<String>(128);
+
+    state.sessions.write().await.insert(session_id.clone(), tx.clone());
+
+    // Relay every server-initiated notification to this session too; the
+    // subscription id embedded in each notification's params is how the
+    // client tells its own streams apart from anyone else's.
+    let mut notification_rx = state.server.subscribe_notifications();
+    tokio::spawn(async move {
+        loop {
+            match notification_rx.recv().await {
+                Ok(notification) => {
+                    if let Ok(json) = serde_json::to_string(&notification) {
+                        if tx.send(json).await.is_err() {
+                            break;
+                        }
+                    }
+                }
+                Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    });
+
+    let sessions = state.sessions.clone();
+    let stream = async_stream::stream! {
+        yield Ok(Event::default()
+            .event("endpoint")
+            .data(format!("/message?sessionId={}", session_id)));
+
+        while let Some(payload) = rx.recv().await {
+            yield Ok(Event::default().event("message").data(payload));
+        }
+
+        sessions.write().await.remove(&session_id);
+    };
+
+    Sse::new(stream)
+}
+
+#[derive(serde::Deserialize)]
+struct SessionQuery {
+    #[serde(rename = "sessionId")]
+    session_id: String,
+}
+
+/// `POST /message?sessionId=...` — accepts a single `JsonRpcRequest`, runs it
+/// through the same `McpServer::process_request` the stdio transport uses,
+/// and delivers the response over the matching SSE stream rather than in the
+/// HTTP response body (the HTTP side just acknowledges receipt).
+async fn handle_message(
+    State(state): State<SseState>,
+    Query(query): Query<SessionQuery>,
+    Json(request): Json<JsonRpcRequest>,
+) -> impl IntoResponse {
+    let sender = state.sessions.read().await.get(&query.session_id).cloned();
+
+    let Some(sender) = sender else {
+        return (axum::http::StatusCode::NOT_FOUND, "unknown sessionId");
+    };
+
+    let server = state.server.clone();
+    let request_timeout = state.request_timeout;
+
+    tokio::spawn(async move {
+        let id = request.id.clone();
+        let response = match tokio::time::timeout(request_timeout, server.process_request(request)).await {
+            Ok(response) => response,
+            Err(_) => JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                id,
+                result: None,
+                error: Some(JsonRpcError {
+                    code: -32603,
+                    message: "Request processing timed out".to_string(),
+                    data: None,
+                }),
+            },
+        };
+
+        if let Ok(json) = serde_json::to_string(&response) {
+            let _ = sender.send(json).await;
+        }
+    });
+
+    (axum::http::StatusCode::ACCEPTED, "accepted")
+}
+
+#[allow(dead_code)]
+fn error_event(id: JsonRpcId, message: String) -> JsonRpcResponse {
+    JsonRpcResponse {
+        jsonrpc: "2.0".to_string(),
+        id,
+        result: None,
+        error: Some(JsonRpcError {
+            code: -32603,
+            message,
+            data: None,
+        }),
+    }
+}
@@ -1,5 +1,5 @@
 use tokio::io::{self, AsyncBufReadExt, AsyncWriteExt, BufReader as TokioBufReader};
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, Semaphore};
 use tokio::time::{timeout, Duration};
 use std::sync::Arc;
 
@@ -7,51 +7,108 @@ use crate::protocol::error::McpError;
 use crate::protocol::types::{JsonRpcRequest, JsonRpcResponse};
 use crate::server::McpServer;
 
+/// Default cap on requests being processed concurrently, so a flood of
+/// pipelined requests can't exhaust Docker or the underlying daemon socket.
+const DEFAULT_MAX_IN_FLIGHT: usize = 16;
+
 pub struct StdioTransport {
     server: Arc<McpServer>,
     request_timeout: Duration,
+    max_in_flight: usize,
 }
 
 impl StdioTransport {
     pub fn new(server: McpServer) -> Self {
         let request_timeout = server.get_request_timeout();
-        Self { 
+        Self {
             server: Arc::new(server),
             request_timeout,
+            max_in_flight: DEFAULT_MAX_IN_FLIGHT,
         }
     }
 
+    pub fn with_max_in_flight(mut self, max_in_flight: usize) -> Self {
+        self.max_in_flight = max_in_flight.max(1);
+        self
+    }
+
+    /// Runs three cooperating tasks: a reader that turns stdin lines into
+    /// parsed requests, a dispatcher that spawns one task per request (so a
+    /// slow Docker operation can't block the rest), and a writer that owns
+    /// stdout and serializes whatever comes back first. Because requests run
+    /// concurrently, responses can be written out of arrival order; JSON-RPC
+    /// `id` correlation on the client side is what makes that safe.
     pub async fn run(&mut self) -> Result<(), McpError> {
+        let (request_tx, mut request_rx) = mpsc::channel::<JsonRpcRequest>(100);
+        let (response_tx, mut response_rx) = mpsc::channel::<String>(100);
+
         let stdin = io::stdin();
         let mut stdin_reader = TokioBufReader::new(stdin);
-        let mut stdout = io::stdout();
+        let parse_error_tx = response_tx.clone();
 
-        let (tx, mut rx) = mpsc::channel::<String>(100);
-
-        let server = self.server.clone();
-        let request_timeout = self.request_timeout;
+        // Notification task: forwards server-initiated notifications
+        // (log-follow lines, Docker events, ...) into the same writer
+        // channel so they get interleaved with normal responses on stdout.
+        let mut notification_rx = self.server.subscribe_notifications();
+        let notification_tx_out = response_tx.clone();
+        let notification_task = tokio::spawn(async move {
+            loop {
+                match notification_rx.recv().await {
+                    Ok(notification) => {
+                        if let Ok(json) = serde_json::to_string(&notification) {
+                            if notification_tx_out.send(json).await.is_err() {
+                                break;
+                            }
+                        }
+                    }
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
+                        log::warn!("Notification receiver lagged, skipped {} messages", skipped);
+                    }
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
 
-        // Spawn a task to read from stdin
+        // Reader task: turn stdin lines into parsed requests (or parse-error
+        // responses written directly to the response channel).
         let read_task = tokio::spawn(async move {
             let mut buffer = String::new();
             loop {
                 buffer.clear();
                 match stdin_reader.read_line(&mut buffer).await {
                     Ok(0) => {
-                        // EOF
                         log::debug!("Reached EOF on stdin");
                         break;
                     }
-                    Ok(n) => {
-                        log::debug!("Read {} bytes from stdin", n);
-                        // Skip empty lines
-                        if buffer.trim().is_empty() {
+                    Ok(_) => {
+                        let trimmed = buffer.trim();
+                        if trimmed.is_empty() {
                             continue;
                         }
-                        
-                        if let Err(e) = tx.send(buffer.clone()).await {
-                            log::error!("Failed to send message to channel: {}", e);
-                            break;
+
+                        match serde_json::from_str::<JsonRpcRequest>(trimmed) {
+                            Ok(request) => {
+                                log::info!("Received request: method={} id={:?}", request.method, request.id);
+                                if request_tx.send(request).await.is_err() {
+                                    break;
+                                }
+                            }
+                            Err(e) => {
+                                log::error!("Error parsing JSON-RPC request: {}", e);
+                                let response = JsonRpcResponse {
+                                    jsonrpc: "2.0".to_string(),
+                                    id: crate::protocol::types::JsonRpcId::Null,
+                                    result: None,
+                                    error: Some(crate::protocol::types::JsonRpcError {
+                                        code: -32700,
+                                        message: format!("Parse error: {}", e),
+                                        data: None,
+                                    }),
+                                };
+                                if let Ok(json) = serde_json::to_string(&response) {
+                                    let _ = parse_error_tx.send(json).await;
+                                }
+                            }
                         }
                     }
                     Err(e) => {
@@ -62,114 +119,93 @@ impl StdioTransport {
             }
         });
 
-        // Process messages
-        while let Some(message) = rx.recv().await {
-            let trimmed = message.trim();
-            if trimmed.is_empty() {
-                continue;
-            }
-            
-            log::debug!("Processing message: {}", trimmed);
-            let server = self.server.clone();
-            
-            match serde_json::from_str::<JsonRpcRequest>(trimmed) {
-                Ok(request) => {
-                    log::info!("Received request: method={} id={:?}", request.method, request.id);
-                    
-                    // Process the request with a timeout
-                    let request_clone = request.clone();
-                    match timeout(request_timeout, server.process_request(request)).await {
-                        Ok(response) => {
-                            let response_json = match serde_json::to_string(&response) {
-                                Ok(json) => json,
-                                Err(e) => {
-                                    log::error!("Failed to serialize response: {}", e);
-                                    let error_response = JsonRpcResponse {
-                                        jsonrpc: "2.0".to_string(),
-                                        id: request_clone.id,
-                                        result: None,
-                                        error: Some(crate::protocol::types::JsonRpcError {
-                                            code: -32603,
-                                            message: format!("Internal error: Failed to serialize response: {}", e),
-                                            data: None,
-                                        }),
-                                    };
-                                    serde_json::to_string(&error_response).unwrap_or_else(|_| {
-                                        r#"{"jsonrpc":"2.0","id":null,"error":{"code":-32603,"message":"Critical error: Failed to serialize error response"}}"#.to_string()
-                                    })
-                                }
-                            };
-                            
-                            log::debug!("Sending response: {}", response_json);
-                            if let Err(e) = stdout.write_all(response_json.as_bytes()).await {
-                                log::error!("Failed to write response: {}", e);
-                                break;
-                            }
-                            
-                            if let Err(e) = stdout.write_all(b"\n").await {
-                                log::error!("Failed to write newline: {}", e);
-                                break;
-                            }
-                            
-                            if let Err(e) = stdout.flush().await {
-                                log::error!("Failed to flush stdout: {}", e);
-                                break;
-                            }
-                            
-                            log::info!("Sent response for method={} id={:?}", request_clone.method, request_clone.id);
-                        }
+        // Dispatcher task: spawn one task per request, bounded by a
+        // semaphore so independent operations run in parallel without
+        // letting an unbounded flood of requests pile up on Docker.
+        let server = self.server.clone();
+        let request_timeout = self.request_timeout;
+        let max_in_flight = self.max_in_flight;
+        let dispatch_task = tokio::spawn(async move {
+            let semaphore = Arc::new(Semaphore::new(max_in_flight));
+
+            while let Some(request) = request_rx.recv().await {
+                let server = server.clone();
+                let response_tx = response_tx.clone();
+                let semaphore = semaphore.clone();
+
+                tokio::spawn(async move {
+                    let _permit = semaphore.acquire_owned().await;
+                    let id = request.id.clone();
+                    let method = request.method.clone();
+
+                    let response = match timeout(request_timeout, server.process_request(request)).await {
+                        Ok(response) => response,
                         Err(_) => {
-                            // Request processing timed out
-                            log::error!("Request timed out: method={} id={:?}", request_clone.method, request_clone.id);
-                            let error_response = JsonRpcResponse {
+                            log::error!("Request timed out: method={} id={:?}", method, id);
+                            JsonRpcResponse {
                                 jsonrpc: "2.0".to_string(),
-                                id: request_clone.id,
+                                id,
                                 result: None,
                                 error: Some(crate::protocol::types::JsonRpcError {
                                     code: -32603,
                                     message: "Request processing timed out".to_string(),
                                     data: None,
                                 }),
-                            };
-                            
-                            let response_json = serde_json::to_string(&error_response)
-                                .unwrap_or_else(|_| {
-                                    r#"{"jsonrpc":"2.0","id":null,"error":{"code":-32603,"message":"Request timed out"}}"#.to_string()
-                                });
-                            
-                            stdout.write_all(response_json.as_bytes()).await?;
-                            stdout.write_all(b"\n").await?;
-                            stdout.flush().await?;
+                            }
+                        }
+                    };
+
+                    match serde_json::to_string(&response) {
+                        Ok(json) => {
+                            let _ = response_tx.send(json).await;
+                        }
+                        Err(e) => {
+                            log::error!("Failed to serialize response: {}", e);
                         }
                     }
+                });
+            }
+        });
+
+        // Writer task: owns stdout exclusively and drains completed
+        // responses as they arrive, regardless of request arrival order.
+        let write_task = tokio::spawn(async move {
+            let mut stdout = io::stdout();
+            while let Some(response_json) = response_rx.recv().await {
+                if let Err(e) = stdout.write_all(response_json.as_bytes()).await {
+                    log::error!("Failed to write response: {}", e);
+                    break;
                 }
-                Err(e) => {
-                    log::error!("Error parsing JSON-RPC request: {}", e);
-                    // Send error response
-                    let response = JsonRpcResponse {
-                        jsonrpc: "2.0".to_string(),
-                        id: crate::protocol::types::JsonRpcId::Null,
-                        result: None,
-                        error: Some(crate::protocol::types::JsonRpcError {
-                            code: -32700,
-                            message: format!("Parse error: {}", e),
-                            data: None,
-                        }),
-                    };
-                    
-                    let response_json = serde_json::to_string(&response)?;
-                    stdout.write_all(response_json.as_bytes()).await?;
-                    stdout.write_all(b"\n").await?;
-                    stdout.flush().await?;
+                if let Err(e) = stdout.write_all(b"\n").await {
+                    log::error!("Failed to write newline: {}", e);
+                    break;
+                }
+                if let Err(e) = stdout.flush().await {
+                    log::error!("Failed to flush stdout: {}", e);
+                    break;
                 }
             }
-        }
+        });
 
-        // Wait for read task to complete
         if let Err(e) = read_task.await {
             log::error!("Error in read task: {}", e);
         }
 
+        // Once the reader is done, dropping its sender lets the dispatcher
+        // drain in-flight requests and exit, which in turn closes the
+        // response channel and lets the writer exit.
+        if let Err(e) = dispatch_task.await {
+            log::error!("Error in dispatch task: {}", e);
+        }
+
+        // The notification task holds its own clone of the response sender,
+        // so it has to stop before the response channel can close.
+        notification_task.abort();
+
+        if let Err(e) = write_task.await {
+            log::error!("Error in write task: {}", e);
+        }
+
         Ok(())
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,106 @@
+use std::sync::Arc;
+
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader as TokioBufReader};
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::protocol::error::McpError;
+use crate::protocol::types::{JsonRpcRequest, JsonRpcResponse};
+use crate::server::McpServer;
+use crate::transport::{serve_connection, Transport};
+
+pub struct TcpTransport {
+    server: Arc<McpServer>,
+    bind_address: String,
+    bind_port: u16,
+}
+
+impl TcpTransport {
+    pub fn new(server: McpServer) -> Self {
+        let bind_address = server.get_bind_address().to_string();
+        let bind_port = server.get_bind_port();
+        Self {
+            server: Arc::new(server),
+            bind_address,
+            bind_port,
+        }
+    }
+
+    /// Accepts connections forever, spawning one `serve_connection` task per
+    /// client so a slow or stalled client can't block anyone else. Each
+    /// connection is its own `TcpConnTransport`, framed identically to
+    /// stdio (newline-delimited JSON-RPC).
+    pub async fn run(&mut self) -> Result<(), McpError> {
+        let addr = format!("{}:{}", self.bind_address, self.bind_port);
+        log::info!("TCP transport listening on {}", addr);
+
+        let listener = TcpListener::bind(&addr)
+            .await
+            .map_err(|e| McpError::InternalError(format!("Failed to bind TCP listener on {}: {}", addr, e)))?;
+
+        let request_timeout = self.server.get_request_timeout();
+
+        loop {
+            let (stream, peer_addr) = match listener.accept().await {
+                Ok(accepted) => accepted,
+                Err(e) => {
+                    log::error!("Failed to accept TCP connection: {}", e);
+                    continue;
+                }
+            };
+            log::info!("Accepted TCP connection from {}", peer_addr);
+
+            let server = self.server.clone();
+            tokio::spawn(async move {
+                let transport = TcpConnTransport::new(stream);
+                serve_connection(server, transport, request_timeout).await;
+                log::info!("TCP connection from {} closed", peer_addr);
+            });
+        }
+    }
+}
+
+/// Newline-delimited JSON-RPC over a plain TCP socket: one object per line,
+/// the same framing stdio uses, so `serde_json` round-trips unchanged.
+struct TcpConnTransport {
+    reader: TokioBufReader<OwnedReadHalf>,
+    writer: OwnedWriteHalf,
+}
+
+impl TcpConnTransport {
+    fn new(stream: TcpStream) -> Self {
+        let (read_half, write_half) = stream.into_split();
+        Self {
+            reader: TokioBufReader::new(read_half),
+            writer: write_half,
+        }
+    }
+}
+
+impl Transport for TcpConnTransport {
+    async fn read_message(&mut self) -> Result<Option<JsonRpcRequest>, McpError> {
+        let mut line = String::new();
+        loop {
+            line.clear();
+            let bytes_read = self.reader.read_line(&mut line).await?;
+            if bytes_read == 0 {
+                return Ok(None);
+            }
+
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+
+            return Ok(Some(serde_json::from_str(trimmed)?));
+        }
+    }
+
+    async fn write_message(&mut self, response: &JsonRpcResponse) -> Result<(), McpError> {
+        let mut json = serde_json::to_string(response)?;
+        json.push('\n');
+        self.writer.write_all(json.as_bytes()).await?;
+        self.writer.flush().await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,111 @@
+use std::sync::Arc;
+
+use futures::{SinkExt, StreamExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::WebSocketStream;
+
+use crate::protocol::error::McpError;
+use crate::protocol::types::{JsonRpcRequest, JsonRpcResponse};
+use crate::server::McpServer;
+use crate::transport::{serve_connection, Transport};
+
+pub struct WebSocketTransport {
+    server: Arc<McpServer>,
+    bind_address: String,
+    bind_port: u16,
+}
+
+impl WebSocketTransport {
+    pub fn new(server: McpServer) -> Self {
+        let bind_address = server.get_bind_address().to_string();
+        let bind_port = server.get_bind_port();
+        Self {
+            server: Arc::new(server),
+            bind_address,
+            bind_port,
+        }
+    }
+
+    /// Accepts TCP connections, upgrades each to a WebSocket handshake, and
+    /// hands it off to `serve_connection` as one JSON-RPC `Message::Text`
+    /// frame per request/response.
+    pub async fn run(&mut self) -> Result<(), McpError> {
+        let addr = format!("{}:{}", self.bind_address, self.bind_port);
+        log::info!("WebSocket transport listening on ws://{}", addr);
+
+        let listener = TcpListener::bind(&addr)
+            .await
+            .map_err(|e| McpError::InternalError(format!("Failed to bind WebSocket listener on {}: {}", addr, e)))?;
+
+        let request_timeout = self.server.get_request_timeout();
+
+        loop {
+            let (stream, peer_addr) = match listener.accept().await {
+                Ok(accepted) => accepted,
+                Err(e) => {
+                    log::error!("Failed to accept WebSocket connection: {}", e);
+                    continue;
+                }
+            };
+
+            let server = self.server.clone();
+            tokio::spawn(async move {
+                let ws_stream = match tokio_tungstenite::accept_async(stream).await {
+                    Ok(ws_stream) => ws_stream,
+                    Err(e) => {
+                        log::error!("WebSocket handshake with {} failed: {}", peer_addr, e);
+                        return;
+                    }
+                };
+                log::info!("Accepted WebSocket connection from {}", peer_addr);
+
+                let transport = WebSocketConnTransport::new(ws_stream);
+                serve_connection(server, transport, request_timeout).await;
+                log::info!("WebSocket connection from {} closed", peer_addr);
+            });
+        }
+    }
+}
+
+/// One JSON-RPC object per `Message::Text` frame, the same one-object-per-
+/// message framing the other transports use, just carried over WebSocket
+/// text frames instead of newline-delimited bytes.
+struct WebSocketConnTransport {
+    socket: WebSocketStream<TcpStream>,
+}
+
+impl WebSocketConnTransport {
+    fn new(socket: WebSocketStream<TcpStream>) -> Self {
+        Self { socket }
+    }
+}
+
+impl Transport for WebSocketConnTransport {
+    async fn read_message(&mut self) -> Result<Option<JsonRpcRequest>, McpError> {
+        loop {
+            match self.socket.next().await {
+                None => return Ok(None),
+                Some(Err(e)) => return Err(McpError::InternalError(format!("WebSocket read error: {}", e))),
+                Some(Ok(Message::Close(_))) => return Ok(None),
+                Some(Ok(Message::Text(text))) => {
+                    let trimmed = text.trim();
+                    if trimmed.is_empty() {
+                        continue;
+                    }
+                    return Ok(Some(serde_json::from_str(trimmed)?));
+                }
+                Some(Ok(Message::Ping(_) | Message::Pong(_) | Message::Binary(_) | Message::Frame(_))) => continue,
+            }
+        }
+    }
+
+    async fn write_message(&mut self, response: &JsonRpcResponse) -> Result<(), McpError> {
+        let json = serde_json::to_string(response)?;
+        self.socket
+            .send(Message::Text(json))
+            .await
+            .map_err(|e| McpError::InternalError(format!("WebSocket write error: {}", e)))?;
+        Ok(())
+    }
+}
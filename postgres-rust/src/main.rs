@@ -8,6 +8,9 @@ edition = "2021"
 [dependencies]
 tokio = { version = "1.32", features = ["full"] }
 tokio-postgres = "0.7"
+postgres-protocol = "0.6"
+postgres-native-tls = "0.5"
+native-tls = "0.2"
 postgres-types = "0.2"
 serde = { version = "1.0", features = ["derive"] }
 serde_json = "1.0"
@@ -17,20 +20,31 @@ anyhow = "1.0"
 tracing = "0.1"
 tracing-subscriber = "0.3"
 dotenv = "0.15"
+futures = "0.3"
 */
 
 use anyhow::{Context, Result};
 use bytes::{Buf, BufMut, BytesMut};
+use futures::{pin_mut, TryStreamExt};
+use native_tls::{Certificate, Identity, TlsConnector};
+use postgres_native_tls::MakeTlsConnector;
+use postgres_protocol::authentication::sasl::{ChannelBinding, ScramSha256};
+use postgres_protocol::message::{backend, frontend};
 use postgres_types::Type;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
 use std::env;
+use std::error::Error as _;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
 use thiserror::Error;
 use tokio::{
     io::{AsyncReadExt, AsyncWriteExt},
     net::{TcpListener, TcpStream},
+    sync::{mpsc, Mutex},
 };
-use tokio_postgres::{Client, NoTls};
+use tokio_postgres::{Client, NoTls, Statement};
 use tracing::{error, info, instrument};
 
 // MCP Protocol message types
@@ -39,10 +53,17 @@ const MSG_TYPE_RESPONSE: u8 = 2;
 const MSG_TYPE_ERROR: u8 = 3;
 const MSG_TYPE_HANDSHAKE: u8 = 4;
 const MSG_TYPE_HANDSHAKE_RESPONSE: u8 = 5;
+const MSG_TYPE_RESPONSE_CHUNK: u8 = 6;
+const MSG_TYPE_RESPONSE_END: u8 = 7;
+const MSG_TYPE_SUBSCRIBE: u8 = 8;
+const MSG_TYPE_CHANGE: u8 = 9;
 
 // MCP Protocol version
 const PROTOCOL_VERSION: u16 = 1;
 
+// Rows per MSG_TYPE_RESPONSE_CHUNK frame when QueryRequest.streaming is set.
+const STREAM_BATCH_SIZE: usize = 500;
+
 #[derive(Error, Debug)]
 enum McpError {
     #[error("IO error: {0}")]
@@ -81,6 +102,8 @@ struct HandshakeResponse {
 struct QueryRequest {
     query: String,
     params: Vec<String>,
+    #[serde(default)]
+    streaming: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -90,166 +113,1256 @@ struct QueryResponse {
     row_count: usize,
 }
 
+// Sent as a MSG_TYPE_RESPONSE_CHUNK frame for each batch of a streaming
+// query's results.
+#[derive(Debug, Serialize, Deserialize)]
+struct QueryChunkResponse {
+    columns: Vec<String>,
+    rows: Vec<Vec<serde_json::Value>>,
+}
+
+// Sent as the final MSG_TYPE_RESPONSE_END frame of a streaming query.
+#[derive(Debug, Serialize, Deserialize)]
+struct QueryEndResponse {
+    row_count: usize,
+}
+
+// Carried by a MSG_TYPE_SUBSCRIBE request: the publication to decode and
+// the replication slot to create-or-attach-to for it.
+#[derive(Debug, Serialize, Deserialize)]
+struct SubscribeRequest {
+    publication: String,
+    slot_name: String,
+}
+
+// Sent as a MSG_TYPE_CHANGE frame for each row-level change decoded off a
+// subscribed publication.
+#[derive(Debug, Serialize, Deserialize)]
+struct ChangeEvent {
+    lsn: String,
+    table: String,
+    kind: String,
+    columns: Vec<String>,
+    values: Vec<serde_json::Value>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 struct ErrorResponse {
     code: String,
     message: String,
 }
 
-struct DbConnection {
+// Default number of pooled connections when POSTGRES_POOL_SIZE isn't set.
+const DEFAULT_POOL_SIZE: usize = 4;
+
+// Default number of prepared statements cached per pooled connection when
+// POSTGRES_STATEMENT_CACHE_SIZE isn't set.
+const DEFAULT_STATEMENT_CACHE_SIZE: usize = 100;
+
+// A pooled connection together with its own prepared-statement cache.
+// Statements are connection-scoped in Postgres, so the cache can't be
+// shared across slots - each slot caches only the statements it has
+// prepared on its own client.
+struct PoolSlot {
     client: Client,
+    statements: HashMap<String, Statement>,
+    lru: VecDeque<String>,
+}
+
+impl PoolSlot {
+    fn new(client: Client) -> Self {
+        Self {
+            client,
+            statements: HashMap::new(),
+            lru: VecDeque::new(),
+        }
+    }
+
+    // Returns a cached prepared statement for `query`, preparing and
+    // caching it on first use. Evicts the least-recently-used entry once
+    // the cache grows past `cache_size`, so long-lived servers don't
+    // accumulate statements for ad-hoc one-off queries.
+    async fn get_or_prepare(&mut self, query: &str, cache_size: usize) -> Result<Statement> {
+        if let Some(statement) = self.statements.get(query) {
+            let statement = statement.clone();
+            self.touch(query);
+            return Ok(statement);
+        }
+
+        let statement = self
+            .client
+            .prepare(query)
+            .await
+            .context("Failed to prepare statement")?;
+
+        if self.statements.len() >= cache_size {
+            if let Some(oldest) = self.lru.pop_front() {
+                self.statements.remove(&oldest);
+            }
+        }
+
+        self.statements.insert(query.to_string(), statement.clone());
+        self.lru.push_back(query.to_string());
+
+        Ok(statement)
+    }
+
+    fn touch(&mut self, query: &str) {
+        if let Some(pos) = self.lru.iter().position(|q| q == query) {
+            let q = self.lru.remove(pos).unwrap();
+            self.lru.push_back(q);
+        }
+    }
+}
+
+struct DbConnection {
+    pool: Vec<Arc<Mutex<PoolSlot>>>,
+    next: AtomicUsize,
+    connection_string: String,
+    sslmode: String,
+    statement_cache_size: usize,
+    // Last-confirmed LSN per CDC subscriber (keyed by slot name), so a
+    // resubscribe after a dropped replication connection resumes from
+    // where it left off instead of re-snapshotting. Held only in memory;
+    // a subscriber reconnecting after a full server restart falls back to
+    // the slot's own confirmed position.
+    subscriber_lsn: Mutex<HashMap<String, String>>,
 }
 
 impl DbConnection {
     async fn new() -> Result<Self> {
         dotenv::dotenv().ok();
-        
+
         let db_host = env::var("POSTGRES_HOST").unwrap_or_else(|_| "localhost".to_string());
         let db_port = env::var("POSTGRES_PORT").unwrap_or_else(|_| "5432".to_string());
         let db_name = env::var("POSTGRES_DB").unwrap_or_else(|_| "postgres".to_string());
         let db_user = env::var("POSTGRES_USER").unwrap_or_else(|_| "postgres".to_string());
         let db_pass = env::var("POSTGRES_PASSWORD").expect("POSTGRES_PASSWORD must be set");
-        
+
         let connection_string = format!(
             "host={} port={} dbname={} user={} password={}",
             db_host, db_port, db_name, db_user, db_pass
         );
-        
-        let (client, connection) = tokio_postgres::connect(&connection_string, NoTls)
-            .await
-            .context("Failed to connect to PostgreSQL")?;
-        
-        // Spawn the connection task to the runtime
-        tokio::spawn(async move {
-            if let Err(e) = connection.await {
-                error!("Connection error: {}", e);
+
+        let sslmode = env::var("POSTGRES_SSLMODE").unwrap_or_else(|_| "disable".to_string());
+
+        let pool_size: usize = env::var("POSTGRES_POOL_SIZE")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_POOL_SIZE);
+
+        let statement_cache_size: usize = env::var("POSTGRES_STATEMENT_CACHE_SIZE")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_STATEMENT_CACHE_SIZE);
+
+        let mut pool = Vec::with_capacity(pool_size);
+        for _ in 0..pool_size {
+            let client = Self::connect_client(&connection_string, &sslmode).await?;
+            pool.push(Arc::new(Mutex::new(PoolSlot::new(client))));
+        }
+
+        Ok(Self {
+            pool,
+            next: AtomicUsize::new(0),
+            connection_string,
+            sslmode,
+            statement_cache_size,
+            subscriber_lsn: Mutex::new(HashMap::new()),
+        })
+    }
+
+    // Opens a single connection and spawns its background I/O task. Used
+    // both to fill the initial pool and to replace a slot whose connection
+    // has dropped.
+    async fn connect_client(connection_string: &str, sslmode: &str) -> Result<Client> {
+        let client = if sslmode == "disable" {
+            let (client, connection) = tokio_postgres::connect(connection_string, NoTls)
+                .await
+                .context("Failed to connect to PostgreSQL")?;
+
+            tokio::spawn(async move {
+                if let Err(e) = connection.await {
+                    error!("Connection error: {}", e);
+                }
+            });
+
+            client
+        } else {
+            let connector = Self::build_tls_connector(sslmode)?;
+            let (client, connection) = tokio_postgres::connect(connection_string, connector)
+                .await
+                .context("Failed to connect to PostgreSQL over TLS")?;
+
+            tokio::spawn(async move {
+                if let Err(e) = connection.await {
+                    error!("Connection error: {}", e);
+                }
+            });
+
+            client
+        };
+
+        Ok(client)
+    }
+
+    // Picks the next pool slot round-robin. Cheap and lock-free; contention
+    // on the slot itself is handled by its Mutex when two sessions land on
+    // the same one at once.
+    fn next_slot(&self) -> Arc<Mutex<PoolSlot>> {
+        let index = self.next.fetch_add(1, Ordering::Relaxed) % self.pool.len();
+        self.pool[index].clone()
+    }
+
+    // Builds a native-tls connector for POSTGRES_SSLMODE values other than
+    // "disable". "require" encrypts the connection but doesn't verify the
+    // server's certificate or hostname, since there's no CA to check against
+    // unless POSTGRES_SSL_CA is also set; "verify-full" requires a CA and
+    // validates both the chain and the hostname.
+    fn build_tls_connector(sslmode: &str) -> Result<MakeTlsConnector> {
+        let mut builder = TlsConnector::builder();
+
+        if let Ok(ca_path) = env::var("POSTGRES_SSL_CA") {
+            let ca_pem = std::fs::read(&ca_path)
+                .with_context(|| format!("Failed to read POSTGRES_SSL_CA at {}", ca_path))?;
+            let ca_cert = Certificate::from_pem(&ca_pem)
+                .context("Failed to parse POSTGRES_SSL_CA as a PEM certificate")?;
+            builder.add_root_certificate(ca_cert);
+        }
+
+        if let Ok(cert_path) = env::var("POSTGRES_SSL_CLIENT_CERT") {
+            let pkcs12 = std::fs::read(&cert_path)
+                .with_context(|| format!("Failed to read POSTGRES_SSL_CLIENT_CERT at {}", cert_path))?;
+            let cert_pass = env::var("POSTGRES_SSL_CLIENT_CERT_PASSWORD").unwrap_or_default();
+            let identity = Identity::from_pkcs12(&pkcs12, &cert_pass)
+                .context("Failed to parse POSTGRES_SSL_CLIENT_CERT as PKCS#12")?;
+            builder.identity(identity);
+        }
+
+        match sslmode {
+            "require" => {
+                builder.danger_accept_invalid_certs(true);
+                builder.danger_accept_invalid_hostnames(true);
             }
-        });
-        
-        Ok(Self { client })
+            "verify-full" => {}
+            other => {
+                return Err(McpError::Protocol(format!(
+                    "Unsupported POSTGRES_SSLMODE '{}' (expected disable, require, or verify-full)",
+                    other
+                ))
+                .into());
+            }
+        }
+
+        let connector = builder
+            .build()
+            .context("Failed to build TLS connector")?;
+
+        Ok(MakeTlsConnector::new(connector))
     }
     
     #[instrument(skip(self))]
-    async fn execute_read_query(&self, query: &str, params: &[&(dyn tokio_postgres::types::ToSql + Sync)]) 
+    async fn execute_read_query(&self, query: &str, params: &[&(dyn tokio_postgres::types::ToSql + Sync)])
         -> Result<QueryResponse> {
-        
+
         // Check if query is trying to perform a write operation
         let normalized_query = query.trim().to_lowercase();
-        if normalized_query.starts_with("insert") || 
-           normalized_query.starts_with("update") || 
-           normalized_query.starts_with("delete") || 
-           normalized_query.starts_with("drop") || 
-           normalized_query.starts_with("create") || 
+        if normalized_query.starts_with("insert") ||
+           normalized_query.starts_with("update") ||
+           normalized_query.starts_with("delete") ||
+           normalized_query.starts_with("drop") ||
+           normalized_query.starts_with("create") ||
            normalized_query.starts_with("alter") {
             return Err(McpError::WriteAttempted.into());
         }
-        
-        // Execute the query
-        let rows = self.client
-            .query(query, params)
+
+        let slot = self.next_slot();
+
+        let first_attempt = {
+            let mut slot = slot.lock().await;
+            Self::run_query(&mut slot, query, params, self.statement_cache_size).await
+        };
+
+        match first_attempt {
+            Ok(response) => Ok(response),
+            Err(e) if is_transient_connection_error(&e) => {
+                // The pooled connection in this slot has dropped (closed
+                // socket, I/O error, ...). Since every query handled here is
+                // a read-only SELECT, it's always safe to reconnect and
+                // re-issue it once rather than failing the session. The old
+                // connection's cached statements are invalid on the new one,
+                // so PoolSlot::new starts it with an empty cache.
+                info!("Reconnecting pool slot after transient error: {}", e);
+                let fresh = Self::connect_client(&self.connection_string, &self.sslmode).await?;
+                let mut slot = slot.lock().await;
+                *slot = PoolSlot::new(fresh);
+                Self::run_query(&mut slot, query, params, self.statement_cache_size).await
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    // Runs a single query against a pool slot and shapes the result into a
+    // QueryResponse. Pulled out of execute_read_query so it can be retried
+    // against a freshly reconnected slot without duplicating the
+    // row-conversion logic. Looks the statement up in the slot's cache
+    // first, preparing (and caching) it only on a miss, so column names and
+    // execution both come from a single prepare per distinct query text.
+    async fn run_query(
+        slot: &mut PoolSlot,
+        query: &str,
+        params: &[&(dyn tokio_postgres::types::ToSql + Sync)],
+        cache_size: usize,
+    ) -> Result<QueryResponse> {
+        let statement = slot.get_or_prepare(query, cache_size).await?;
+
+        let columns: Vec<String> = statement
+            .columns()
+            .iter()
+            .map(|col| col.name().to_string())
+            .collect();
+
+        // The syntactic prefix check in execute_read_query is only a fast
+        // rejection (it's trivially bypassed by leading comments, CTEs, or
+        // a volatile function call); running the query inside a
+        // database-enforced READ ONLY transaction is what actually stops a
+        // write from taking effect. Postgres rejects any write attempted
+        // here with a 25006 read_only_sql_transaction error, mapped below
+        // to McpError::WriteAttempted.
+        let transaction = slot
+            .client
+            .build_transaction()
+            .read_only(true)
+            .start()
             .await
-            .context("Failed to execute query")?;
-        
+            .context("Failed to start read-only transaction")?;
+
+        let rows = match transaction.query(&statement, params).await {
+            Ok(rows) => rows,
+            Err(e) => {
+                let _ = transaction.rollback().await;
+                if e.code() == Some(&tokio_postgres::error::SqlState::READ_ONLY_SQL_TRANSACTION) {
+                    return Err(McpError::WriteAttempted.into());
+                }
+                return Err(e).context("Failed to execute query");
+            }
+        };
+
+        transaction
+            .commit()
+            .await
+            .context("Failed to commit read-only transaction")?;
+
         // If no rows, return empty response with column names
         if rows.is_empty() {
-            let statement = self.client
-                .prepare(query)
-                .await
-                .context("Failed to prepare statement")?;
-            
-            let columns = statement
-                .columns()
-                .iter()
-                .map(|col| col.name().to_string())
-                .collect();
-            
             return Ok(QueryResponse {
                 columns,
                 rows: vec![],
                 row_count: 0,
             });
         }
-        
-        // Get column names from the first row
-        let columns = rows[0]
-            .columns()
-            .iter()
-            .map(|col| col.name().to_string())
-            .collect();
-        
+
         // Convert rows to JSON-compatible format
         let mut result_rows = Vec::with_capacity(rows.len());
-        
         for row in &rows {
-            let mut values = Vec::with_capacity(row.columns().len());
-            
-            for (i, column) in row.columns().iter().enumerate() {
-                let value = match column.type_() {
-                    &Type::BOOL => {
-                        let val: Option<bool> = row.get(i);
-                        serde_json::to_value(val)?
-                    },
-                    &Type::INT2 | &Type::INT4 => {
-                        let val: Option<i32> = row.get(i);
-                        serde_json::to_value(val)?
-                    },
-                    &Type::INT8 => {
-                        let val: Option<i64> = row.get(i);
-                        serde_json::to_value(val)?
-                    },
-                    &Type::FLOAT4 => {
-                        let val: Option<f32> = row.get(i);
-                        serde_json::to_value(val)?
-                    },
-                    &Type::FLOAT8 => {
-                        let val: Option<f64> = row.get(i);
-                        serde_json::to_value(val)?
-                    },
-                    &Type::TEXT | &Type::VARCHAR => {
-                        let val: Option<String> = row.get(i);
-                        serde_json::to_value(val)?
-                    },
-                    &Type::JSON | &Type::JSONB => {
-                        // Fix: Convert JSON type data to string first
-                        let val: Option<String> = row.get(i);
-                        match val {
-                            Some(json_str) => {
-                                let parsed: serde_json::Value = serde_json::from_str(&json_str)?;
-                                serde_json::to_value(Some(parsed))?
-                            },
-                            None => serde_json::to_value(None::<serde_json::Value>)?
-                        }
-                    },
-                    &Type::TIMESTAMP | &Type::TIMESTAMPTZ => {
-                        // Fix: Get timestamp as string to avoid generic parameter issues
-                        let val: Option<String> = row.get(i);
-                        serde_json::to_value(val)?
-                    },
-                    &Type::DATE => {
-                        // Fix: Get date as string to avoid generic parameter issues
-                        let val: Option<String> = row.get(i);
-                        serde_json::to_value(val)?
-                    },
-                    _ => {
-                        // For other types, get as string representation
-                        let val: Option<String> = row.try_get(i)
-                            .unwrap_or_else(|_| Some("<binary data>".to_string()));
-                        serde_json::to_value(val)?
-                    }
-                };
-                
-                values.push(value);
-            }
-            
-            result_rows.push(values);
+            result_rows.push(row_to_json_values(row)?);
         }
-        
+
         Ok(QueryResponse {
             columns,
             rows: result_rows,
             row_count: rows.len(),
         })
     }
+
+    // Streaming counterpart of execute_read_query: instead of buffering the
+    // whole result set into one QueryResponse/MSG_TYPE_RESPONSE frame, pulls
+    // rows off a RowStream in batches of STREAM_BATCH_SIZE and writes each
+    // batch out as its own MSG_TYPE_RESPONSE_CHUNK frame, finishing with a
+    // MSG_TYPE_RESPONSE_END frame carrying the total row count. Memory use
+    // is bounded to one batch regardless of how many rows the query matches.
+    async fn execute_read_query_streaming(
+        &self,
+        query: &str,
+        params: &[&(dyn tokio_postgres::types::ToSql + Sync)],
+        stream: &mut TcpStream,
+    ) -> Result<()> {
+        let normalized_query = query.trim().to_lowercase();
+        if normalized_query.starts_with("insert") ||
+           normalized_query.starts_with("update") ||
+           normalized_query.starts_with("delete") ||
+           normalized_query.starts_with("drop") ||
+           normalized_query.starts_with("create") ||
+           normalized_query.starts_with("alter") {
+            return Err(McpError::WriteAttempted.into());
+        }
+
+        let slot = self.next_slot();
+        let mut slot = slot.lock().await;
+        let statement = slot.get_or_prepare(query, self.statement_cache_size).await?;
+
+        let columns: Vec<String> = statement
+            .columns()
+            .iter()
+            .map(|col| col.name().to_string())
+            .collect();
+
+        // Same database-enforced read-only guard as run_query: a READ ONLY
+        // transaction rather than trusting the syntactic prefix check above.
+        let transaction = slot
+            .client
+            .build_transaction()
+            .read_only(true)
+            .start()
+            .await
+            .context("Failed to start read-only transaction")?;
+
+        let row_stream = match transaction.query_raw(&statement, params.iter().copied()).await {
+            Ok(row_stream) => row_stream,
+            Err(e) => {
+                let _ = transaction.rollback().await;
+                if e.code() == Some(&tokio_postgres::error::SqlState::READ_ONLY_SQL_TRANSACTION) {
+                    return Err(McpError::WriteAttempted.into());
+                }
+                return Err(e).context("Failed to execute streaming query");
+            }
+        };
+        pin_mut!(row_stream);
+
+        let mut batch = Vec::with_capacity(STREAM_BATCH_SIZE);
+        let mut total_rows = 0usize;
+
+        loop {
+            match row_stream.try_next().await {
+                Ok(Some(row)) => {
+                    batch.push(row_to_json_values(&row)?);
+                    if batch.len() >= STREAM_BATCH_SIZE {
+                        total_rows += batch.len();
+                        write_frame(stream, MSG_TYPE_RESPONSE_CHUNK, &QueryChunkResponse {
+                            columns: columns.clone(),
+                            rows: std::mem::take(&mut batch),
+                        })
+                        .await?;
+                    }
+                }
+                Ok(None) => break,
+                Err(e) => {
+                    if e.code() == Some(&tokio_postgres::error::SqlState::READ_ONLY_SQL_TRANSACTION) {
+                        return Err(McpError::WriteAttempted.into());
+                    }
+                    return Err(e).context("Failed to read row from stream");
+                }
+            }
+        }
+
+        if !batch.is_empty() {
+            total_rows += batch.len();
+            write_frame(stream, MSG_TYPE_RESPONSE_CHUNK, &QueryChunkResponse {
+                columns: columns.clone(),
+                rows: batch,
+            })
+            .await?;
+        }
+
+        transaction
+            .commit()
+            .await
+            .context("Failed to commit read-only transaction")?;
+
+        write_frame(stream, MSG_TYPE_RESPONSE_END, &QueryEndResponse { row_count: total_rows }).await
+    }
+
+    // Opens a dedicated logical-replication connection (replication=database)
+    // and streams row-level changes for `publication`/`slot_name` to `sender`
+    // as MSG_TYPE_CHANGE frames until the stream ends or the subscribing
+    // session disconnects. Resumes from this process's last-confirmed LSN
+    // for the slot, falling back to the slot's own start position on first
+    // use, rather than re-snapshotting on every (re)subscribe.
+    //
+    // tokio-postgres's `Client` only exposes copy_in/copy_out/simple_query
+    // publicly - there's no bidirectional COPY, which is what
+    // START_REPLICATION needs. So this opens its own connection and drives
+    // the startup/auth/COPY BOTH handshake directly via `postgres-protocol`,
+    // the same message (de)serializers tokio-postgres itself is built on,
+    // rather than depending on an unpublished feature of the client.
+    async fn subscribe_changes(
+        &self,
+        publication: String,
+        slot_name: String,
+        sender: mpsc::Sender<(u8, Vec<u8>)>,
+    ) -> Result<()> {
+        let mut conn = ReplicationConnection::connect(&self.connection_string)
+            .await
+            .context("Failed to open logical replication connection")?;
+
+        // A slot that already exists just gets attached to by
+        // START_REPLICATION below, so resubscribing isn't an error.
+        let create_slot = format!(
+            "CREATE_REPLICATION_SLOT {} LOGICAL pgoutput NOEXPORT_SNAPSHOT",
+            slot_name
+        );
+        if let Err(e) = conn.simple_query(&create_slot).await {
+            info!("Replication slot '{}' not created (likely already exists): {}", slot_name, e);
+        }
+
+        let start_lsn = self
+            .subscriber_lsn
+            .lock()
+            .await
+            .get(&slot_name)
+            .cloned()
+            .unwrap_or_else(|| "0/0".to_string());
+
+        let start_replication = format!(
+            "START_REPLICATION SLOT {} LOGICAL {} (proto_version '1', publication_names '{}')",
+            slot_name, start_lsn, publication
+        );
+
+        conn.start_replication(&start_replication)
+            .await
+            .context("Failed to start logical replication")?;
+
+        let mut relations: HashMap<i32, RelationInfo> = HashMap::new();
+
+        while let Some(data) = conn.next_copy_data().await.context("Replication stream error")? {
+            if data.is_empty() {
+                continue;
+            }
+
+            match data[0] {
+                b'w' => {
+                    // XLogData: tag(1) + wal start(8) + wal end(8) + send time(8) + payload
+                    if data.len() < 25 {
+                        continue;
+                    }
+                    let wal_start = u64::from_be_bytes(data[1..9].try_into().unwrap());
+                    let payload = data.slice(25..);
+
+                    if let Some(change) = decode_pgoutput_message(payload, &mut relations)? {
+                        let lsn = format_lsn(wal_start);
+                        self.subscriber_lsn.lock().await.insert(slot_name.clone(), lsn.clone());
+
+                        let event = ChangeEvent {
+                            lsn,
+                            table: change.table,
+                            kind: change.kind.to_string(),
+                            columns: change.columns,
+                            values: change.values,
+                        };
+                        let json = serde_json::to_vec(&event)?;
+                        if sender.send((MSG_TYPE_CHANGE, json)).await.is_err() {
+                            // Subscribing session has gone away; stop streaming.
+                            break;
+                        }
+                    }
+
+                    conn.send_standby_status_update(wal_start).await?;
+                }
+                b'k' => {
+                    // Primary keepalive: tag(1) + end lsn(8) + time(8) + reply-requested(1)
+                    if data.len() >= 18 && data[17] == 1 {
+                        let wal_end = u64::from_be_bytes(data[1..9].try_into().unwrap());
+                        conn.send_standby_status_update(wal_end).await?;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// Splits a libpq key=value connection string (the form `DbConnection::new`
+// builds) into its parts. Doesn't handle quoted values - this server never
+// generates any, so plain whitespace-splitting is enough.
+fn parse_conn_params(connection_string: &str) -> HashMap<&str, &str> {
+    connection_string
+        .split_whitespace()
+        .filter_map(|pair| pair.split_once('='))
+        .collect()
+}
+
+// Drives a single Postgres wire-protocol connection dedicated to logical
+// replication: the startup/auth handshake, then CREATE_REPLICATION_SLOT /
+// START_REPLICATION over the simple query protocol, then the COPY BOTH
+// stream of XLogData/keepalive messages and the standby status updates
+// sent back in response.
+struct ReplicationConnection {
+    stream: TcpStream,
+    read_buf: BytesMut,
+}
+
+impl ReplicationConnection {
+    async fn connect(connection_string: &str) -> Result<Self> {
+        let params = parse_conn_params(connection_string);
+        let host = params.get("host").copied().unwrap_or("localhost");
+        let port: u16 = params.get("port").and_then(|p| p.parse().ok()).unwrap_or(5432);
+        let user = params.get("user").copied().unwrap_or("postgres");
+        let dbname = params.get("dbname").copied().unwrap_or("postgres");
+        let password = params.get("password").copied().unwrap_or("");
+
+        let stream = TcpStream::connect((host, port))
+            .await
+            .with_context(|| format!("Failed to open TCP connection to {}:{}", host, port))?;
+
+        let mut conn = Self { stream, read_buf: BytesMut::new() };
+        conn.authenticate(user, dbname, password).await?;
+        conn.wait_until_ready().await?;
+        Ok(conn)
+    }
+
+    async fn authenticate(&mut self, user: &str, dbname: &str, password: &str) -> Result<()> {
+        let mut buf = BytesMut::new();
+        frontend::startup_message(
+            [("user", user), ("database", dbname), ("replication", "database")],
+            &mut buf,
+        )
+        .context("Failed to build startup message")?;
+        self.stream.write_all(&buf).await?;
+
+        loop {
+            match self.read_backend_message().await? {
+                backend::Message::AuthenticationOk => return Ok(()),
+                backend::Message::AuthenticationCleartextPassword => {
+                    let mut buf = BytesMut::new();
+                    frontend::password_message(password.as_bytes(), &mut buf)
+                        .context("Failed to build password message")?;
+                    self.stream.write_all(&buf).await?;
+                }
+                backend::Message::AuthenticationMd5Password(body) => {
+                    let hashed = postgres_protocol::authentication::md5_hash(
+                        user.as_bytes(),
+                        password.as_bytes(),
+                        body.salt(),
+                    );
+                    let mut buf = BytesMut::new();
+                    frontend::password_message(&hashed, &mut buf)
+                        .context("Failed to build password message")?;
+                    self.stream.write_all(&buf).await?;
+                }
+                backend::Message::AuthenticationSasl(body) => {
+                    let mechanism = body
+                        .mechanisms()
+                        .next()
+                        .transpose()
+                        .context("Malformed SASL mechanism list")?
+                        .ok_or_else(|| McpError::Protocol("Server offered no SASL mechanism".to_string()))?;
+                    if mechanism != "SCRAM-SHA-256" {
+                        return Err(McpError::Protocol(format!("Unsupported SASL mechanism '{}'", mechanism)).into());
+                    }
+
+                    let mut scram = ScramSha256::new(password.as_bytes(), ChannelBinding::unsupported());
+
+                    let mut buf = BytesMut::new();
+                    frontend::sasl_initial_response("SCRAM-SHA-256", scram.message(), &mut buf)
+                        .context("Failed to build SASL initial response")?;
+                    self.stream.write_all(&buf).await?;
+
+                    let continue_body = match self.read_backend_message().await? {
+                        backend::Message::AuthenticationSaslContinue(body) => body,
+                        backend::Message::ErrorResponse(body) => return Err(auth_error(body)),
+                        _ => return Err(McpError::Protocol("Expected AuthenticationSASLContinue".to_string()).into()),
+                    };
+                    scram
+                        .update(continue_body.data())
+                        .context("SCRAM exchange failed")?;
+
+                    let mut buf = BytesMut::new();
+                    frontend::sasl_response(scram.message(), &mut buf)
+                        .context("Failed to build SASL response")?;
+                    self.stream.write_all(&buf).await?;
+
+                    let final_body = match self.read_backend_message().await? {
+                        backend::Message::AuthenticationSaslFinal(body) => body,
+                        backend::Message::ErrorResponse(body) => return Err(auth_error(body)),
+                        _ => return Err(McpError::Protocol("Expected AuthenticationSASLFinal".to_string()).into()),
+                    };
+                    scram
+                        .finish(final_body.data())
+                        .context("SCRAM server signature verification failed")?;
+                }
+                backend::Message::ErrorResponse(body) => return Err(auth_error(body)),
+                _ => {}
+            }
+        }
+    }
+
+    // Drains ParameterStatus/BackendKeyData/NoticeResponse until the server
+    // reports ReadyForQuery, completing the startup phase.
+    async fn wait_until_ready(&mut self) -> Result<()> {
+        loop {
+            match self.read_backend_message().await? {
+                backend::Message::ReadyForQuery(_) => return Ok(()),
+                backend::Message::ErrorResponse(body) => return Err(auth_error(body)),
+                _ => {}
+            }
+        }
+    }
+
+    async fn simple_query(&mut self, query: &str) -> Result<()> {
+        let mut buf = BytesMut::new();
+        frontend::query(query, &mut buf).context("Failed to build query message")?;
+        self.stream.write_all(&buf).await?;
+
+        loop {
+            match self.read_backend_message().await? {
+                backend::Message::ReadyForQuery(_) => return Ok(()),
+                backend::Message::ErrorResponse(body) => {
+                    return Err(McpError::Protocol(format!("{:?}", collect_error_fields(body))).into())
+                }
+                _ => {}
+            }
+        }
+    }
+
+    // Sends `query` (a START_REPLICATION command) and consumes messages up
+    // to and including the server's CopyBothResponse, leaving the
+    // connection positioned to read XLogData/keepalive frames via
+    // `next_copy_data`.
+    async fn start_replication(&mut self, query: &str) -> Result<()> {
+        let mut buf = BytesMut::new();
+        frontend::query(query, &mut buf).context("Failed to build query message")?;
+        self.stream.write_all(&buf).await?;
+
+        loop {
+            match self.read_backend_message().await? {
+                backend::Message::CopyBothResponse(_) => return Ok(()),
+                backend::Message::ErrorResponse(body) => {
+                    return Err(McpError::Protocol(format!("{:?}", collect_error_fields(body))).into())
+                }
+                _ => {}
+            }
+        }
+    }
+
+    // Returns the next CopyData payload (an XLogData or keepalive message,
+    // tagged the same way tokio-postgres's own `copy_both_simple` stream
+    // yielded them), or `None` once the server ends the COPY with CopyDone.
+    async fn next_copy_data(&mut self) -> Result<Option<bytes::Bytes>> {
+        loop {
+            match self.read_backend_message().await? {
+                backend::Message::CopyData(body) => return Ok(Some(body.into_bytes())),
+                backend::Message::CopyDone => return Ok(None),
+                backend::Message::ErrorResponse(body) => {
+                    return Err(McpError::Protocol(format!("{:?}", collect_error_fields(body))).into())
+                }
+                _ => {}
+            }
+        }
+    }
+
+    // Sends a standby status update ('r') framed as CopyData, so Postgres
+    // can advance the slot's confirmed position; `lsn` is the last WAL
+    // position processed, reported as received/flushed/applied since this
+    // server doesn't buffer changes once handed to a subscriber.
+    async fn send_standby_status_update(&mut self, lsn: u64) -> Result<()> {
+        let mut status = BytesMut::with_capacity(34);
+        status.put_u8(b'r');
+        status.put_u64(lsn + 1);
+        status.put_u64(lsn + 1);
+        status.put_u64(lsn + 1);
+        status.put_i64(postgres_epoch_micros());
+        status.put_u8(0);
+
+        let mut buf = BytesMut::new();
+        frontend::copy_data(status.freeze(), &mut buf).context("Failed to build standby status update")?;
+        self.stream
+            .write_all(&buf)
+            .await
+            .context("Failed to send standby status update")?;
+
+        Ok(())
+    }
+
+    async fn read_backend_message(&mut self) -> Result<backend::Message> {
+        loop {
+            if let Some(message) = backend::Message::parse(&mut self.read_buf)
+                .context("Failed to parse backend message")?
+            {
+                return Ok(message);
+            }
+
+            let bytes_read = self.stream.read_buf(&mut self.read_buf).await?;
+            if bytes_read == 0 {
+                return Err(McpError::Protocol("Replication connection closed unexpectedly".to_string()).into());
+            }
+        }
+    }
+}
+
+fn collect_error_fields(body: backend::ErrorResponseBody) -> Vec<String> {
+    body.fields()
+        .map(|f| f.map(|field| format!("{}: {}", field.type_() as char, field.value())))
+        .filter_map(|f| f.ok())
+        .collect()
+}
+
+fn auth_error(body: backend::ErrorResponseBody) -> anyhow::Error {
+    McpError::Protocol(format!("Authentication failed: {:?}", collect_error_fields(body))).into()
+}
+
+// Caches the column layout of a replicated table, keyed by the relation id
+// pgoutput assigns it for the lifetime of the replication stream. A 'R'
+// Relation message (re)populates this before any Insert/Update/Delete
+// referencing that id can be decoded.
+struct RelationInfo {
+    namespace: String,
+    name: String,
+    columns: Vec<(String, u32)>,
+}
+
+// One decoded row-level change, before the LSN (filled in by the caller
+// from the enclosing XLogData header) is attached.
+struct DecodedChange {
+    table: String,
+    kind: &'static str,
+    columns: Vec<String>,
+    values: Vec<serde_json::Value>,
+}
+
+// Decodes a single pgoutput message. Returns None for message kinds that
+// don't carry row data (Begin/Commit/Origin/Truncate/Type) or that only
+// update `relations`' bookkeeping (Relation).
+fn decode_pgoutput_message(
+    mut buf: bytes::Bytes,
+    relations: &mut HashMap<i32, RelationInfo>,
+) -> Result<Option<DecodedChange>> {
+    if !buf.has_remaining() {
+        return Ok(None);
+    }
+
+    let tag = buf.get_u8();
+    match tag {
+        b'R' => {
+            let relation_id = buf.get_i32();
+            let namespace = read_cstr(&mut buf);
+            let name = read_cstr(&mut buf);
+            let _replica_identity = buf.get_u8();
+            let column_count = buf.get_i16();
+
+            let mut columns = Vec::with_capacity(column_count.max(0) as usize);
+            for _ in 0..column_count {
+                let _flags = buf.get_u8();
+                let col_name = read_cstr(&mut buf);
+                let type_oid = buf.get_i32() as u32;
+                let _type_modifier = buf.get_i32();
+                columns.push((col_name, type_oid));
+            }
+
+            relations.insert(relation_id, RelationInfo { namespace, name, columns });
+            Ok(None)
+        }
+        b'I' => {
+            let relation_id = buf.get_i32();
+            let _new_tuple_tag = buf.get_u8(); // always 'N' for inserts
+            let relation = relations
+                .get(&relation_id)
+                .ok_or_else(|| McpError::Protocol(format!("Insert for unknown relation {}", relation_id)))?;
+            let (columns, values) = read_tuple_data(&mut buf, &relation.columns)?;
+            Ok(Some(DecodedChange {
+                table: format!("{}.{}", relation.namespace, relation.name),
+                kind: "insert",
+                columns,
+                values,
+            }))
+        }
+        b'U' => {
+            let relation_id = buf.get_i32();
+            let relation = relations
+                .get(&relation_id)
+                .ok_or_else(|| McpError::Protocol(format!("Update for unknown relation {}", relation_id)))?;
+
+            // An update may carry the old row's key ('K') or full image
+            // ('O') before the mandatory new row ('N'); only the new image
+            // is surfaced to subscribers.
+            let mut tuple_tag = buf.get_u8();
+            if tuple_tag == b'K' || tuple_tag == b'O' {
+                let _ = read_tuple_data(&mut buf, &relation.columns)?;
+                tuple_tag = buf.get_u8();
+            }
+            if tuple_tag != b'N' {
+                return Err(McpError::Protocol(format!("Unexpected update tuple tag '{}'", tuple_tag as char)).into());
+            }
+
+            let (columns, values) = read_tuple_data(&mut buf, &relation.columns)?;
+            Ok(Some(DecodedChange {
+                table: format!("{}.{}", relation.namespace, relation.name),
+                kind: "update",
+                columns,
+                values,
+            }))
+        }
+        b'D' => {
+            let relation_id = buf.get_i32();
+            let relation = relations
+                .get(&relation_id)
+                .ok_or_else(|| McpError::Protocol(format!("Delete for unknown relation {}", relation_id)))?;
+            let _key_or_old_tag = buf.get_u8(); // 'K' (replica identity key) or 'O' (full row)
+            let (columns, values) = read_tuple_data(&mut buf, &relation.columns)?;
+            Ok(Some(DecodedChange {
+                table: format!("{}.{}", relation.namespace, relation.name),
+                kind: "delete",
+                columns,
+                values,
+            }))
+        }
+        _ => Ok(None),
+    }
+}
+
+// Reads pgoutput's tuple-data format (column count, then one tagged value
+// per column) out of `buf`, converting each column's text-format value
+// using the table's type OIDs from the cached Relation message.
+fn read_tuple_data(
+    buf: &mut bytes::Bytes,
+    columns: &[(String, u32)],
+) -> Result<(Vec<String>, Vec<serde_json::Value>)> {
+    let column_count = buf.get_i16() as usize;
+    let mut names = Vec::with_capacity(column_count);
+    let mut values = Vec::with_capacity(column_count);
+
+    for i in 0..column_count {
+        let (name, type_oid) = columns
+            .get(i)
+            .cloned()
+            .unwrap_or_else(|| (format!("column_{}", i), 0));
+
+        let value = match buf.get_u8() {
+            b'n' => serde_json::Value::Null,
+            b'u' => serde_json::Value::String("<unchanged toast>".to_string()),
+            b't' => {
+                let len = buf.get_i32() as usize;
+                let text_bytes = buf.copy_to_bytes(len);
+                let text = String::from_utf8_lossy(&text_bytes).to_string();
+                pgoutput_value_to_json(type_oid, &text)
+            }
+            other => {
+                return Err(McpError::Protocol(format!("Unknown tuple column tag '{}'", other as char)).into());
+            }
+        };
+
+        names.push(name);
+        values.push(value);
+    }
+
+    Ok((names, values))
+}
+
+// Parses a pgoutput text-format column value into the same JSON shape
+// execute_read_query's binary-format conversion produces, so CDC change
+// events and query results look the same to clients. Types without a
+// dedicated case fall back to their raw text, matching the "_" arm of
+// row_to_json_values.
+fn pgoutput_value_to_json(type_oid: u32, text: &str) -> serde_json::Value {
+    match Type::from_oid(type_oid) {
+        Some(Type::BOOL) => serde_json::Value::Bool(text == "t"),
+        Some(Type::INT2) | Some(Type::INT4) | Some(Type::INT8) => text
+            .parse::<i64>()
+            .map(|n| serde_json::Value::Number(n.into()))
+            .unwrap_or_else(|_| serde_json::Value::String(text.to_string())),
+        Some(Type::FLOAT4) | Some(Type::FLOAT8) => text
+            .parse::<f64>()
+            .ok()
+            .and_then(serde_json::Number::from_f64)
+            .map(serde_json::Value::Number)
+            .unwrap_or_else(|| serde_json::Value::String(text.to_string())),
+        Some(Type::JSON) | Some(Type::JSONB) => {
+            serde_json::from_str(text).unwrap_or_else(|_| serde_json::Value::String(text.to_string()))
+        }
+        _ => serde_json::Value::String(text.to_string()),
+    }
+}
+
+// Reads a null-terminated string out of a pgoutput message buffer.
+fn read_cstr(buf: &mut bytes::Bytes) -> String {
+    let mut raw = Vec::new();
+    while buf.has_remaining() {
+        let b = buf.get_u8();
+        if b == 0 {
+            break;
+        }
+        raw.push(b);
+    }
+    String::from_utf8_lossy(&raw).to_string()
+}
+
+// Formats a WAL position the way Postgres prints LSNs ("XXXXXXXX/XXXXXXXX",
+// hex, high 32 bits / low 32 bits).
+fn format_lsn(lsn: u64) -> String {
+    format!("{:X}/{:X}", lsn >> 32, lsn & 0xFFFF_FFFF)
+}
+
+// Microseconds since the Postgres epoch (2000-01-01 00:00:00 UTC), the
+// timestamp format standby status update messages use.
+fn postgres_epoch_micros() -> i64 {
+    const PG_EPOCH_UNIX_SECONDS: i64 = 946_684_800;
+    let since_unix_epoch = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+    (since_unix_epoch.as_secs() as i64 - PG_EPOCH_UNIX_SECONDS) * 1_000_000 + since_unix_epoch.subsec_micros() as i64
+}
+
+// Shared by the buffered and streaming query paths: converts one Row into
+// the JSON-compatible values used by QueryResponse/QueryChunkResponse.
+fn row_to_json_values(row: &tokio_postgres::Row) -> Result<Vec<serde_json::Value>> {
+    let mut values = Vec::with_capacity(row.columns().len());
+
+    for (i, column) in row.columns().iter().enumerate() {
+        let value = match column.type_() {
+            &Type::BOOL => {
+                let val: Option<bool> = row.get(i);
+                serde_json::to_value(val)?
+            },
+            &Type::INT2 | &Type::INT4 => {
+                let val: Option<i32> = row.get(i);
+                serde_json::to_value(val)?
+            },
+            &Type::INT8 => {
+                let val: Option<i64> = row.get(i);
+                serde_json::to_value(val)?
+            },
+            &Type::FLOAT4 => {
+                let val: Option<f32> = row.get(i);
+                serde_json::to_value(val)?
+            },
+            &Type::FLOAT8 => {
+                let val: Option<f64> = row.get(i);
+                serde_json::to_value(val)?
+            },
+            &Type::TEXT | &Type::VARCHAR => {
+                let val: Option<String> = row.get(i);
+                serde_json::to_value(val)?
+            },
+            &Type::JSON | &Type::JSONB => {
+                // Fix: Convert JSON type data to string first
+                let val: Option<String> = row.get(i);
+                match val {
+                    Some(json_str) => {
+                        let parsed: serde_json::Value = serde_json::from_str(&json_str)?;
+                        serde_json::to_value(Some(parsed))?
+                    },
+                    None => serde_json::to_value(None::<serde_json::Value>)?
+                }
+            },
+            &Type::TIMESTAMP | &Type::TIMESTAMPTZ => {
+                // Fix: Get timestamp as string to avoid generic parameter issues
+                let val: Option<String> = row.get(i);
+                serde_json::to_value(val)?
+            },
+            &Type::DATE => {
+                // Fix: Get date as string to avoid generic parameter issues
+                let val: Option<String> = row.get(i);
+                serde_json::to_value(val)?
+            },
+            _ => {
+                // For other types, get as string representation
+                let val: Option<String> = row.try_get(i)
+                    .unwrap_or_else(|_| Some("<binary data>".to_string()));
+                serde_json::to_value(val)?
+            }
+        };
+
+        values.push(value);
+    }
+
+    Ok(values)
+}
+
+// Serializes `data` and writes it out as a single framed message, the same
+// wire format McpSession::send_message uses. Free function since the
+// streaming path writes directly to the TcpStream rather than going through
+// a McpSession (execute_read_query_streaming only has DbConnection state).
+async fn write_frame<T: Serialize>(stream: &mut TcpStream, msg_type: u8, data: &T) -> Result<()> {
+    let json = serde_json::to_vec(data)?;
+
+    let mut buffer = BytesMut::with_capacity(5 + json.len());
+    buffer.put_u8(msg_type);
+    buffer.put_u32(json.len() as u32);
+    buffer.extend_from_slice(&json);
+
+    stream.write_all(&buffer).await?;
+
+    Ok(())
+}
+
+// True for connection-level failures (closed socket, I/O error) as opposed
+// to SQL errors (syntax, constraint violation, ...), which a reconnect and
+// retry would never fix.
+fn is_transient_connection_error(e: &anyhow::Error) -> bool {
+    match e.downcast_ref::<tokio_postgres::Error>() {
+        Some(pg_err) => {
+            pg_err.is_closed()
+                || pg_err
+                    .source()
+                    .and_then(|s| s.downcast_ref::<std::io::Error>())
+                    .is_some()
+        }
+        None => false,
+    }
+}
+
+// Shared by the TCP handshake and the HTTP /sql endpoint: checks a bearer
+// token against AUTH_TOKEN (falling back to the same development default
+// used everywhere else in this server).
+fn check_auth_token(token: &str) -> bool {
+    let expected_token = env::var("AUTH_TOKEN").unwrap_or_else(|_| "development_token".to_string());
+    token == expected_token
+}
+
+// Runs a query and shapes the result (or error) into values a front-end
+// can serialize without depending on McpSession, so MSG_TYPE_QUERY and the
+// HTTP /sql endpoint share one code path instead of duplicating the
+// error-code mapping.
+async fn dispatch_query(
+    connection: &DbConnection,
+    query_req: &QueryRequest,
+) -> std::result::Result<QueryResponse, (u16, ErrorResponse)> {
+    let param_refs: Vec<&(dyn tokio_postgres::types::ToSql + Sync)> = query_req
+        .params
+        .iter()
+        .map(|p| p as &(dyn tokio_postgres::types::ToSql + Sync))
+        .collect();
+
+    connection
+        .execute_read_query(&query_req.query, &param_refs)
+        .await
+        .map_err(|e| {
+            let (status, code) = match e.downcast_ref::<McpError>() {
+                Some(McpError::WriteAttempted) => (403, "WRITE_ATTEMPT"),
+                Some(McpError::Database(_)) => (400, "DB_ERROR"),
+                _ => (400, "QUERY_ERROR"),
+            };
+            (status, ErrorResponse { code: code.to_string(), message: e.to_string() })
+        })
+}
+
+// A minimal hand-rolled HTTP/1.1 front-end: just enough to accept
+// `POST /sql` with a JSON body and return a JSON response, so browser
+// tools and scripts can run read-only queries without implementing the
+// MCP_PORT binary framing. Not a general-purpose HTTP server.
+async fn run_http_server(connection: Arc<DbConnection>, address: String) -> Result<()> {
+    let listener = TcpListener::bind(&address).await?;
+    info!("HTTP query endpoint listening on {}", address);
+
+    loop {
+        match listener.accept().await {
+            Ok((stream, addr)) => {
+                info!("New HTTP connection from: {}", addr);
+                let connection = Arc::clone(&connection);
+                tokio::spawn(async move {
+                    if let Err(e) = handle_http_connection(connection, stream).await {
+                        error!("HTTP connection error: {}", e);
+                    }
+                });
+            }
+            Err(e) => {
+                error!("Failed to accept HTTP connection: {}", e);
+            }
+        }
+    }
+}
+
+async fn handle_http_connection(connection: Arc<DbConnection>, mut stream: TcpStream) -> Result<()> {
+    let mut buffer = BytesMut::with_capacity(4096);
+
+    let header_end = loop {
+        if let Some(pos) = find_subslice(&buffer, b"\r\n\r\n") {
+            break pos;
+        }
+        if buffer.len() > 64 * 1024 {
+            return write_http_error(&mut stream, 400, "INVALID_REQUEST", "Request headers too large").await;
+        }
+        if stream.read_buf(&mut buffer).await? == 0 {
+            return Ok(());
+        }
+    };
+
+    let header_text = String::from_utf8_lossy(&buffer[..header_end]).to_string();
+    let mut lines = header_text.split("\r\n");
+    let request_line = lines.next().unwrap_or_default();
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let path = parts.next().unwrap_or_default();
+
+    if method != "POST" || path != "/sql" {
+        return write_http_error(&mut stream, 404, "NOT_FOUND", "Only POST /sql is supported").await;
+    }
+
+    let mut content_length = 0usize;
+    let mut auth_header: Option<String> = None;
+    for line in lines {
+        if let Some((name, value)) = line.split_once(':') {
+            let name = name.trim().to_ascii_lowercase();
+            let value = value.trim().to_string();
+            if name == "content-length" {
+                content_length = value.parse().unwrap_or(0);
+            } else if name == "authorization" {
+                auth_header = Some(value);
+            }
+        }
+    }
+
+    let body_start = header_end + 4;
+    while buffer.len() < body_start + content_length {
+        if stream.read_buf(&mut buffer).await? == 0 {
+            return Ok(());
+        }
+    }
+    let body = &buffer[body_start..body_start + content_length];
+
+    let token = auth_header
+        .as_deref()
+        .and_then(|h| h.strip_prefix("Bearer "))
+        .unwrap_or("");
+    if !check_auth_token(token) {
+        return write_http_error(&mut stream, 401, "AUTH_REQUIRED", "Missing or invalid bearer token").await;
+    }
+
+    let query_req: QueryRequest = match serde_json::from_slice(body) {
+        Ok(req) => req,
+        Err(e) => {
+            let message = format!("Invalid JSON body: {}", e);
+            return write_http_error(&mut stream, 400, "INVALID_REQUEST", &message).await;
+        }
+    };
+
+    match dispatch_query(&connection, &query_req).await {
+        Ok(response) => write_http_json(&mut stream, 200, &response).await,
+        Err((status, error)) => write_http_json(&mut stream, status, &error).await,
+    }
+}
+
+fn find_subslice(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|window| window == needle)
+}
+
+async fn write_http_error(stream: &mut TcpStream, status: u16, code: &str, message: &str) -> Result<()> {
+    let error = ErrorResponse { code: code.to_string(), message: message.to_string() };
+    write_http_json(stream, status, &error).await
+}
+
+async fn write_http_json<T: Serialize>(stream: &mut TcpStream, status: u16, data: &T) -> Result<()> {
+    let json = serde_json::to_vec(data)?;
+    let reason = http_reason_phrase(status);
+    let head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        status,
+        reason,
+        json.len()
+    );
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(&json).await?;
+    Ok(())
+}
+
+fn http_reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        _ => "Internal Server Error",
+    }
 }
 
 struct McpSession {
@@ -257,29 +1370,43 @@ struct McpSession {
     stream: TcpStream,
     buffer: BytesMut,
     authenticated: bool,
+    // Fed by subscribe_changes tasks spawned for this session; drained
+    // alongside the socket read so CDC pushes don't wait on the next
+    // client request.
+    cdc_tx: mpsc::Sender<(u8, Vec<u8>)>,
+    cdc_rx: mpsc::Receiver<(u8, Vec<u8>)>,
 }
 
 impl McpSession {
     fn new(connection: Arc<DbConnection>, stream: TcpStream) -> Self {
+        let (cdc_tx, cdc_rx) = mpsc::channel(64);
         Self {
             connection,
             stream,
             buffer: BytesMut::with_capacity(4096),
             authenticated: false,
+            cdc_tx,
+            cdc_rx,
         }
     }
-    
+
     async fn process(&mut self) -> Result<()> {
         loop {
-            // Read data from the client
-            let bytes_read = self.stream.read_buf(&mut self.buffer).await?;
-            if bytes_read == 0 {
-                // Client disconnected
-                return Ok(());
+            tokio::select! {
+                result = self.stream.read_buf(&mut self.buffer) => {
+                    let bytes_read = result?;
+                    if bytes_read == 0 {
+                        // Client disconnected
+                        return Ok(());
+                    }
+
+                    // Process the message
+                    self.process_message().await?;
+                }
+                Some((msg_type, json)) = self.cdc_rx.recv() => {
+                    self.write_raw_frame(msg_type, &json).await?;
+                }
             }
-            
-            // Process the message
-            self.process_message().await?;
         }
     }
     
@@ -313,6 +1440,13 @@ impl McpSession {
                 }
                 self.handle_query(payload).await?;
             },
+            MSG_TYPE_SUBSCRIBE => {
+                if !self.authenticated {
+                    self.send_error("Not authenticated", "AUTH_REQUIRED").await?;
+                    return Ok(());
+                }
+                self.handle_subscribe(payload).await?;
+            },
             _ => {
                 self.send_error("Unknown message type", "INVALID_MESSAGE").await?;
             }
@@ -325,10 +1459,7 @@ impl McpSession {
         // Parse handshake request
         let handshake: HandshakeRequest = serde_json::from_slice(&payload)?;
         
-        // In a real app, validate the auth_token
-        // For this example, we use a simple environment variable token
-        let expected_token = env::var("AUTH_TOKEN").unwrap_or_else(|_| "development_token".to_string());
-        let success = handshake.auth_token == expected_token;
+        let success = check_auth_token(&handshake.auth_token);
         
         if success {
             self.authenticated = true;
@@ -365,25 +1496,71 @@ impl McpSession {
             .map(|p| p as &(dyn tokio_postgres::types::ToSql + Sync))
             .collect();
         
-        // Execute the query
-        match self.connection.execute_read_query(&query_req.query, &param_refs).await {
-            Ok(response) => {
-                self.send_message(MSG_TYPE_RESPONSE, &response).await?;
-            },
-            Err(e) => {
+        if query_req.streaming {
+            let result = self
+                .connection
+                .execute_read_query_streaming(&query_req.query, &param_refs, &mut self.stream)
+                .await;
+
+            if let Err(e) = result {
                 let error_code = match e.downcast_ref::<McpError>() {
                     Some(McpError::WriteAttempted) => "WRITE_ATTEMPT",
                     Some(McpError::Database(_)) => "DB_ERROR",
                     _ => "QUERY_ERROR",
                 };
-                
+
                 self.send_error(&e.to_string(), error_code).await?;
             }
+
+            return Ok(());
         }
-        
+
+        // Execute the query
+        match dispatch_query(&self.connection, &query_req).await {
+            Ok(response) => {
+                self.send_message(MSG_TYPE_RESPONSE, &response).await?;
+            },
+            Err((_status, error)) => {
+                self.send_message(MSG_TYPE_ERROR, &error).await?;
+            }
+        }
+
         Ok(())
     }
-    
+
+    async fn handle_subscribe(&mut self, payload: bytes::Bytes) -> Result<()> {
+        // Parse subscribe request
+        let subscribe_req: SubscribeRequest = serde_json::from_slice(&payload)?;
+
+        let connection = self.connection.clone();
+        let sender = self.cdc_tx.clone();
+
+        // The replication stream runs for the lifetime of the subscription,
+        // independent of this session's request/response loop; changes are
+        // pushed back to process() via the cdc_tx/cdc_rx channel.
+        tokio::spawn(async move {
+            if let Err(e) = connection
+                .subscribe_changes(subscribe_req.publication, subscribe_req.slot_name, sender)
+                .await
+            {
+                error!("CDC subscription ended with error: {}", e);
+            }
+        });
+
+        Ok(())
+    }
+
+    async fn write_raw_frame(&mut self, msg_type: u8, json: &[u8]) -> Result<()> {
+        let mut buffer = BytesMut::with_capacity(5 + json.len());
+        buffer.put_u8(msg_type);
+        buffer.put_u32(json.len() as u32);
+        buffer.extend_from_slice(json);
+
+        self.stream.write_all(&buffer).await?;
+
+        Ok(())
+    }
+
     async fn send_message<T: Serialize>(&mut self, msg_type: u8, data: &T) -> Result<()> {
         // Serialize the data
         let json = serde_json::to_vec(data)?;
@@ -425,11 +1602,23 @@ async fn main() -> Result<()> {
     
     // Create database connection
     let db_connection = Arc::new(DbConnection::new().await?);
-    
+
+    // The HTTP /sql endpoint is optional: it only starts when MCP_HTTP_PORT
+    // is set, alongside the binary protocol's listener.
+    if let Ok(http_port) = env::var("MCP_HTTP_PORT") {
+        let http_address = format!("{}:{}", host, http_port);
+        let http_connection = Arc::clone(&db_connection);
+        tokio::spawn(async move {
+            if let Err(e) = run_http_server(http_connection, http_address).await {
+                error!("HTTP server error: {}", e);
+            }
+        });
+    }
+
     // Create TCP listener
     let listener = TcpListener::bind(&address).await?;
     info!("MCP Server listening on {}", address);
-    
+
     // Accept connections
     loop {
         match listener.accept().await {
@@ -491,6 +1680,7 @@ async fn client_example() -> Result<()> {
             let query = QueryRequest {
                 query: "SELECT * FROM users LIMIT 10".to_string(),
                 params: vec![],
+                streaming: false,
             };
             
             let json = serde_json::to_vec(&query)?;